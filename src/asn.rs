@@ -0,0 +1,202 @@
+use std::{fmt, str::FromStr};
+
+use crate::{AsnError, SetClass, SetName};
+
+/// An autonomous system number.
+///
+/// AS numbers are parsed from the `AS` prefixed notation used by RPSL, e.g. `AS65536`, as well
+/// as from plain numbers, and may be written in asplain or asdot notation as described in
+/// [RFC 5396](https://datatracker.ietf.org/doc/html/rfc5396). They are always displayed in
+/// `AS` prefixed asplain notation.
+///
+/// # Example
+/// ```
+/// # use rpsl::Asn;
+/// let asn: Asn = "AS1.10".parse()?;
+///
+/// assert_eq!(asn, Asn::from(65546));
+/// assert_eq!(asn, "as65546".parse()?);
+/// assert_eq!(asn.to_string(), "AS65546");
+/// assert_eq!(asn.to_asdot(), "AS1.10");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Asn(u32);
+
+impl Asn {
+    /// Create an AS number.
+    #[must_use]
+    pub const fn new(number: u32) -> Self {
+        Self(number)
+    }
+
+    /// The AS number as an integer.
+    #[must_use]
+    pub const fn number(self) -> u32 {
+        self.0
+    }
+
+    /// The AS number in `AS` prefixed asdot notation, which only differs from asplain notation
+    /// for AS numbers that do not fit into 16 bits.
+    #[must_use]
+    pub fn to_asdot(self) -> String {
+        if self.0 > u32::from(u16::MAX) {
+            format!("AS{}.{}", self.0 >> 16, self.0 & 0xffff)
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+impl From<u32> for Asn {
+    fn from(number: u32) -> Self {
+        Self(number)
+    }
+}
+
+impl From<Asn> for u32 {
+    fn from(asn: Asn) -> Self {
+        asn.0
+    }
+}
+
+impl FromStr for Asn {
+    type Err = AsnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = match s.get(..2) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("AS") => &s[2..],
+            _ => s,
+        };
+        let number = match number.split_once('.') {
+            Some((high, low)) => parse_digits::<u16>(high)
+                .zip(parse_digits::<u16>(low))
+                .map(|(high, low)| u32::from(high) << 16 | u32::from(low)),
+            None => parse_digits(number),
+        };
+        number.map(Self).ok_or_else(|| AsnError(s.to_string()))
+    }
+}
+
+impl fmt::Display for Asn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AS{}", self.0)
+    }
+}
+
+/// An AS number or the name of an `as-set`, as contained in the `members` of an `as-set` and
+/// in the AS expressions of policies.
+///
+/// # Example
+/// ```
+/// # use rpsl::{Asn, AsTerm};
+/// assert_eq!("AS65536".parse::<AsTerm>()?, AsTerm::Asn(Asn::from(65536)));
+/// assert!(matches!("AS65536:AS-CUSTOMERS".parse::<AsTerm>()?, AsTerm::Set(_)));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AsTerm {
+    /// An AS number.
+    Asn(Asn),
+    /// The name of an `as-set`.
+    Set(SetName),
+}
+
+impl FromStr for AsTerm {
+    type Err = AsnError;
+
+    /// Parse an `AS` prefixed AS number or the name of an `as-set`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(set) = s.parse::<SetName>() {
+            if set.class() == SetClass::AsSet {
+                return Ok(Self::Set(set));
+            }
+        }
+        match s.get(..2) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("AS") => s.parse().map(Self::Asn),
+            _ => Err(AsnError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for AsTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Asn(asn) => write!(f, "{asn}"),
+            Self::Set(set) => write!(f, "{set}"),
+        }
+    }
+}
+
+/// Parse a number consisting of ASCII digits only.
+fn parse_digits<T: FromStr>(digits: &str) -> Option<T> {
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("AS65536", 65536)]
+    #[case("as65536", 65536)]
+    #[case("65536", 65536)]
+    #[case("AS0", 0)]
+    #[case("AS4294967295", u32::MAX)]
+    #[case("AS1.10", 65546)]
+    #[case("1.10", 65546)]
+    #[case("AS0.65535", 65535)]
+    #[case("AS65535.65535", u32::MAX)]
+    fn asn_from_str(#[case] s: &str, #[case] expected: u32) {
+        assert_eq!(s.parse::<Asn>().unwrap(), Asn::new(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("AS")]
+    #[case("ASN1")]
+    #[case("AS-FOO")]
+    #[case("AS+1")]
+    #[case("AS 1")]
+    #[case("AS4294967296")]
+    #[case("AS1.")]
+    #[case(".1")]
+    #[case("AS65536.1")]
+    #[case("AS1.65536")]
+    #[case("AS1.2.3")]
+    fn invalid_asn_is_err(#[case] s: &str) {
+        assert_eq!(s.parse::<Asn>(), Err(AsnError(s.to_string())));
+    }
+
+    #[rstest]
+    #[case(65535, "AS65535", "AS65535")]
+    #[case(65536, "AS65536", "AS1.0")]
+    #[case(u32::MAX, "AS4294967295", "AS65535.65535")]
+    fn asn_display(#[case] number: u32, #[case] asplain: &str, #[case] asdot: &str) {
+        let asn = Asn::from(number);
+        assert_eq!(asn.to_string(), asplain);
+        assert_eq!(asn.to_asdot(), asdot);
+    }
+
+    #[rstest]
+    #[case("AS65536", AsTerm::Asn(Asn::new(65536)))]
+    #[case("as-foo", AsTerm::Set("AS-FOO".parse().unwrap()))]
+    #[case("AS65536:AS-FOO", AsTerm::Set("AS65536:AS-FOO".parse().unwrap()))]
+    fn as_term_from_str(#[case] s: &str, #[case] expected: AsTerm) {
+        assert_eq!(s.parse::<AsTerm>().unwrap(), expected);
+        assert_eq!(expected.to_string().parse::<AsTerm>().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("65536")]
+    #[case("RS-FOO")]
+    #[case("AS65536:RS-FOO")]
+    fn invalid_as_term_is_err(#[case] s: &str) {
+        assert!(s.parse::<AsTerm>().is_err());
+    }
+}
@@ -1,9 +1,18 @@
-use std::{borrow::Cow, fmt, ops::Deref, str::FromStr};
+use std::{
+    borrow::Cow,
+    fmt,
+    ops::{Deref, Range},
+    str::FromStr,
+};
 
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{de, Deserialize, Deserializer, Serialize};
 
-use crate::error::{InvalidNameError, InvalidValueError};
+use crate::{
+    error::{InvalidNameError, InvalidValueError},
+    format::Formatter,
+    Asn, AsnError, SetName, SetNameError,
+};
 
 /// An attribute of an [`Object`](crate::Object).
 ///
@@ -18,21 +27,123 @@ use crate::error::{InvalidNameError, InvalidValueError};
 /// assert_eq!(object[0], attribute);
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-#[derive(Debug, PartialEq, Eq, Clone)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Attribute<'a> {
     /// The name of the attribute.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub name: Name<'a>,
     /// The value of the attribute.
-    #[cfg_attr(feature = "serde", serde(rename = "values"))]
+    #[cfg_attr(feature = "serde", serde(rename = "values", borrow))]
     pub value: Value<'a>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    comments: Vec<Comment<'a>>,
+    /// Contains the lines the attribute was parsed from, if it was created by parsing RPSL.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    source: Option<Source<'a>>,
 }
 
 impl<'a> Attribute<'a> {
     /// Create a new attribute.
     #[must_use]
     pub fn new(name: Name<'a>, value: Value<'a>) -> Self {
-        Self { name, value }
+        Self {
+            name,
+            value,
+            comments: Vec::new(),
+            source: None,
+        }
+    }
+
+    /// Create a new attribute from a parsed name, value and the comments found on its lines.
+    pub(crate) fn from_parsed(
+        name: Name<'a>,
+        value: Value<'a>,
+        comments: Vec<Comment<'a>>,
+    ) -> Self {
+        Self {
+            name,
+            value,
+            comments,
+            source: None,
+        }
+    }
+
+    /// Keep the lines the attribute was parsed from, so that it can be displayed unchanged.
+    ///
+    /// The name and value must borrow from the given lines.
+    pub(crate) fn with_source(mut self, source: &'a str) -> Self {
+        self.source = Some(Source::new(source, &self.name, &self.value));
+        self
+    }
+
+    /// The lines the attribute was parsed from, if it was created by parsing RPSL.
+    pub(crate) fn source(&self) -> Option<&str> {
+        self.source.as_ref().map(|source| source.text.as_ref())
+    }
+
+    /// The lines the attribute was parsed from, if they still describe the attribute.
+    ///
+    /// Since the name and value can be changed after parsing, the source is only returned if
+    /// they are still equal to the name and value found at their position within it.
+    pub(crate) fn intact_source(&self) -> Option<&str> {
+        let source = self.source.as_ref()?;
+        source
+            .describes(&self.name, &self.value)
+            .then_some(source.text.as_ref())
+    }
+
+    /// The comments on the lines of the attribute, in order of their appearance.
+    ///
+    /// Comments are not part of the value and are ignored when comparing attributes.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::parse_object;
+    /// let object = parse_object("
+    /// members:        AS65536, # transit
+    ///                 AS65537
+    /// ## peers follow
+    ///                 AS65538
+    ///
+    /// ")?;
+    /// let comments = object[0].comments();
+    /// assert_eq!(object[0].value, vec!["AS65536,", "AS65537", "AS65538"]);
+    /// assert_eq!(comments[0].text(), " transit");
+    /// assert!(!comments[0].is_own_line());
+    /// assert_eq!(comments[1].text(), " peers follow");
+    /// assert_eq!(comments[1].line(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn comments(&self) -> &[Comment<'a>] {
+        &self.comments
+    }
+
+    /// Convert the attribute into one that owns its name and value.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{parse_object, Attribute};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rpsl = String::from("
+    /// mntner:  MAINT-AS3257
+    ///
+    /// ");
+    /// let attribute: Attribute<'static> = parse_object(&rpsl)?[0].clone().into_owned();
+    /// drop(rpsl);
+    /// assert_eq!(attribute.value, "MAINT-AS3257");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn into_owned(self) -> Attribute<'static> {
+        Attribute {
+            name: self.name.into_owned(),
+            value: self.value.into_owned(),
+            comments: self.comments.into_iter().map(Comment::into_owned).collect(),
+            source: self.source.map(Source::into_owned),
+        }
     }
 
     #[cfg(test)]
@@ -42,7 +153,7 @@ impl<'a> Attribute<'a> {
     {
         let name = Name::unchecked(name);
         let value = Value::unchecked_single(value);
-        Self { name, value }
+        Self::new(name, value)
     }
 
     #[cfg(test)]
@@ -53,35 +164,155 @@ impl<'a> Attribute<'a> {
     {
         let name = Name::unchecked(name);
         let value = Value::unchecked_multi(values);
-        Self { name, value }
+        Self::new(name, value)
     }
 }
 
+impl PartialEq for Attribute<'_> {
+    /// Compare two attributes by their name and value, ignoring comments.
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
+}
+
+impl Eq for Attribute<'_> {}
+
 impl fmt::Display for Attribute<'_> {
+    /// Display the attribute as RPSL.
+    ///
+    /// An attribute created by parsing RPSL is displayed exactly like the lines it was parsed
+    /// from, unless its name or value has been changed since. Otherwise, it is laid out using
+    /// the default [`FormatOptions`](crate::format::FormatOptions).
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let values = self.value.values();
+        if let Some(source) = self.intact_source() {
+            return f.write_str(source);
+        }
 
-        let first_value = values.first().expect("must contain at least one value");
-        match first_value {
-            Some(value) => {
-                writeln!(f, "{:16}{}", format!("{}:", self.name), value)?;
-            }
-            None => writeln!(f, "{}:", self.name)?,
+        f.write_str(&Formatter::default().format_attribute(self))
+    }
+}
+
+/// The lines an [`Attribute`] was parsed from, together with the position of the name and
+/// each value line within them, so that changes to the attribute can be detected.
+#[derive(Debug, Clone)]
+struct Source<'a> {
+    text: Cow<'a, str>,
+    name: Range<usize>,
+    value: SourceValue,
+}
+
+/// The positions of the value lines within the [`Source`] of an attribute, `None` for empty lines.
+#[derive(Debug, Clone)]
+enum SourceValue {
+    SingleLine(Option<Range<usize>>),
+    MultiLine(Vec<Option<Range<usize>>>),
+}
+
+impl<'a> Source<'a> {
+    fn new(text: &'a str, name: &Name<'a>, value: &Value<'a>) -> Self {
+        let position = |part: &str| {
+            let start = part.as_ptr() as usize - text.as_ptr() as usize;
+            start..start + part.len()
+        };
+        Self {
+            text: Cow::Borrowed(text),
+            name: position(name),
+            value: match value {
+                Value::SingleLine(value) => SourceValue::SingleLine(value.as_deref().map(position)),
+                Value::MultiLine(values) => SourceValue::MultiLine(
+                    values
+                        .iter()
+                        .map(|value| value.as_deref().map(position))
+                        .collect(),
+                ),
+            },
         }
+    }
 
-        let remaining_values = &values[1..];
-        for value in remaining_values {
-            match value {
-                Some(value) => {
-                    writeln!(f, "{:16}{}", " ", value)?;
+    /// Whether the name and value are still the ones found within the text.
+    fn describes(&self, name: &Name, value: &Value) -> bool {
+        let line = |position: &Option<Range<usize>>| position.clone().map(|p| &self.text[p]);
+        **name == self.text[self.name.clone()]
+            && match (value, &self.value) {
+                (Value::SingleLine(value), SourceValue::SingleLine(position)) => {
+                    value.as_deref() == line(position)
                 }
-                None => {
-                    writeln!(f, " ")?;
+                (Value::MultiLine(values), SourceValue::MultiLine(positions)) => {
+                    values.len() == positions.len()
+                        && values
+                            .iter()
+                            .zip(positions)
+                            .all(|(value, position)| value.as_deref() == line(position))
                 }
+                _ => false,
             }
+    }
+
+    fn into_owned(self) -> Source<'static> {
+        Source {
+            text: Cow::Owned(self.text.into_owned()),
+            name: self.name,
+            value: self.value,
         }
+    }
+}
 
-        Ok(())
+/// A comment on a line of an [`Attribute`], starting with `#` and extending to the end of the line.
+///
+/// As described in [RFC 2622, Section 2](https://datatracker.ietf.org/doc/html/rfc2622#section-2),
+/// a comment may follow a value or take up a line of its own.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Comment<'a> {
+    text: Cow<'a, str>,
+    line: usize,
+    own_line: bool,
+}
+
+impl<'a> Comment<'a> {
+    /// Create a comment following the value on the given line of an attribute.
+    pub(crate) fn inline(text: &'a str, line: usize) -> Self {
+        Self {
+            text: Cow::Borrowed(text),
+            line,
+            own_line: false,
+        }
+    }
+
+    /// Create a comment taking up a line of its own after the given line of an attribute.
+    pub(crate) fn own_line(text: &'a str, line: usize) -> Self {
+        Self {
+            text: Cow::Borrowed(text),
+            line,
+            own_line: true,
+        }
+    }
+
+    /// The text of the comment following the `#`.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The index of the value line the comment follows, starting at 0 for the first line.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Whether the comment takes up a line of its own rather than following a value.
+    #[must_use]
+    pub fn is_own_line(&self) -> bool {
+        self.own_line
+    }
+
+    /// Convert the comment into one that owns its text.
+    #[must_use]
+    pub fn into_owned(self) -> Comment<'static> {
+        Comment {
+            text: Cow::Owned(self.text.into_owned()),
+            line: self.line,
+            own_line: self.own_line,
+        }
     }
 }
 
@@ -95,6 +326,12 @@ impl<'a> Name<'a> {
         Self(Cow::Borrowed(name))
     }
 
+    /// Convert the name into one that owns its data.
+    #[must_use]
+    pub fn into_owned(self) -> Name<'static> {
+        Name(Cow::Owned(self.0.into_owned()))
+    }
+
     fn validate(name: &str) -> Result<(), InvalidNameError> {
         if name.trim().is_empty() {
             return Err(InvalidNameError::Empty);
@@ -146,6 +383,18 @@ impl fmt::Display for Name<'_> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de: 'a, 'a> Deserialize<'de> for Name<'a> {
+    /// Deserialize a name from a string, validating it like [`Name::from_str`].
+    ///
+    /// The name borrows from the input if the format allows it.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let CowStr(name) = CowStr::deserialize(deserializer)?;
+        Self::validate(&name).map_err(|err| de::Error::custom(crate::AttributeError::from(err)))?;
+        Ok(Self(name))
+    }
+}
+
 /// The value of an [`Attribute`].
 /// Since only some values contain multiple lines and single line values do not require
 /// additional heap allocation, an Enum is used to represent both variants.
@@ -215,8 +464,26 @@ impl<'a> Value<'a> {
         s
     }
 
+    /// Convert the value into one that owns its data.
+    #[must_use]
+    pub fn into_owned(self) -> Value<'static> {
+        match self {
+            Self::SingleLine(value) => Value::SingleLine(value.map(|v| Cow::Owned(v.into_owned()))),
+            Self::MultiLine(values) => Value::MultiLine(
+                values
+                    .into_iter()
+                    .map(|v| v.map(|v| Cow::Owned(v.into_owned())))
+                    .collect(),
+            ),
+        }
+    }
+
     fn validate(value: &str) -> Result<(), InvalidValueError> {
-        value.chars().try_for_each(Self::validate_char)
+        value.chars().try_for_each(|c| match c {
+            // A value containing a "#" could not be told apart from a value followed by a comment.
+            '#' => Err(InvalidValueError::ContainsCommentChar),
+            c => Self::validate_char(c),
+        })
     }
 
     /// Even though RFC 2622 requires values to be ASCII, in practice some WHOIS databases
@@ -259,7 +526,7 @@ impl<'a> Value<'a> {
         }
     }
 
-    fn values(&'a self) -> Vec<Option<&'a str>> {
+    pub(crate) fn values(&'a self) -> Vec<Option<&'a str>> {
         match self {
             Value::SingleLine(value) => {
                 vec![value.as_ref().map(std::convert::AsRef::as_ref)]
@@ -299,6 +566,79 @@ impl<'a> Value<'a> {
             Self::MultiLine(v) => v.iter().flatten().map(AsRef::as_ref).collect(),
         }
     }
+
+    /// Parse the value into a typed value, with lines containing content joined by a single space.
+    ///
+    /// # Errors
+    /// Returns the error of the typed value if the value cannot be parsed into it.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{AddressPrefix, Value};
+    /// let value: Value = "192.0.2.0/24".parse()?;
+    /// let prefix: AddressPrefix = value.parse()?;
+    /// assert_eq!(prefix.length(), 24);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parse<T: FromStr>(&self) -> Result<T, T::Err> {
+        self.with_content().join(" ").trim().parse()
+    }
+
+    /// Parse each of the comma separated items contained in the value into a typed value.
+    ///
+    /// # Errors
+    /// Returns the error of the first item that cannot be parsed into the typed value.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{AsTerm, Asn, Value};
+    /// let value: Value = vec!["AS65536, AS1.10,", "AS3257:AS-CUSTOMERS"].try_into()?;
+    /// let members: Vec<AsTerm> = value.parse_list()?;
+    /// assert_eq!(members[1], AsTerm::Asn(Asn::from(65546)));
+    /// assert_eq!(members.len(), 3);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parse_list<T: FromStr>(&self) -> Result<Vec<T>, T::Err> {
+        self.with_content()
+            .into_iter()
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+
+    /// Parse the value into an AS number, e.g. the value of an `origin` or `aut-num` attribute.
+    ///
+    /// # Errors
+    /// Returns an error if the value is not a valid AS number.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{Asn, Value};
+    /// let value: Value = "AS1.10".parse()?;
+    /// assert_eq!(value.asn()?, Asn::from(65546));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn asn(&self) -> Result<Asn, AsnError> {
+        self.parse()
+    }
+
+    /// Parse the value into the name of a set, e.g. the value of an `as-set` attribute.
+    ///
+    /// # Errors
+    /// Returns an error if the value is not a valid set name.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{SetClass, Value};
+    /// let value: Value = "AS3257:AS-CUSTOMERS".parse()?;
+    /// assert_eq!(value.set_name()?.class(), SetClass::AsSet);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_name(&self) -> Result<SetName, SetNameError> {
+        self.parse()
+    }
 }
 
 impl FromStr for Value<'_> {
@@ -306,7 +646,8 @@ impl FromStr for Value<'_> {
 
     /// Create a new single line value from a string slice.
     ///
-    /// A valid value may consist of any ASCII character, excluding control characters.
+    /// A valid value may consist of any ASCII character, excluding control characters
+    /// and the "#" character, which starts a comment.
     ///
     /// # Errors
     /// Returns an error if the value contains invalid characters.
@@ -421,6 +762,67 @@ impl PartialEq<Vec<Option<&str>>> for Value<'_> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de: 'a, 'a> Deserialize<'de> for Value<'a> {
+    /// Deserialize a value from a sequence of lines, each either a string or `null` for an
+    /// empty line, validating every line like [`Value::from_str`].
+    ///
+    /// Lines borrow from the input if the format allows it.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut values = Vec::<Option<CowStr<'a>>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|value| match value {
+                Some(CowStr(value)) => {
+                    Self::validate(&value)
+                        .map_err(|err| de::Error::custom(crate::AttributeError::from(err)))?;
+                    Ok(coerce_empty_value(value))
+                }
+                None => Ok(None),
+            })
+            .collect::<Result<Vec<_>, D::Error>>()?;
+        match values.len() {
+            0 => Err(de::Error::invalid_length(0, &"at least one line")),
+            1 => Ok(Self::SingleLine(values.pop().flatten())),
+            _ => Ok(Self::MultiLine(values)),
+        }
+    }
+}
+
+/// A string deserialized by borrowing from the input if the format allows it.
+///
+/// Unlike `Cow<str>` itself, which is always deserialized into an owned string.
+#[cfg(feature = "serde")]
+struct CowStr<'a>(Cow<'a, str>);
+
+#[cfg(feature = "serde")]
+impl<'de: 'a, 'a> Deserialize<'de> for CowStr<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CowStrVisitor;
+
+        impl<'de> de::Visitor<'de> for CowStrVisitor {
+            type Value = Cow<'de, str>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(Cow::Borrowed(v))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(Cow::Owned(v.to_string()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(Cow::Owned(v))
+            }
+        }
+
+        deserializer.deserialize_str(CowStrVisitor).map(CowStr)
+    }
+}
+
 /// Coerce an empty value to `None`.
 fn coerce_empty_value<S>(value: S) -> Option<S>
 where
@@ -444,7 +846,7 @@ mod tests {
     use proptest::prelude::*;
     use rstest::*;
     #[cfg(feature = "serde")]
-    use serde_test::{assert_ser_tokens, Token};
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Token};
 
     use super::*;
 
@@ -466,6 +868,10 @@ mod tests {
         Attribute::unchecked_single("Ref", "https://rdap.arin.net/registry/autnum/32934"),
         "Ref:            https://rdap.arin.net/registry/autnum/32934\n"
     )]
+    #[case(
+        Attribute::unchecked_single("administrative-contact", "RPSL1-RIPE"),
+        "administrative-contact:RPSL1-RIPE\n"
+    )]
     fn attribute_display_single_line(#[case] attribute: Attribute, #[case] expected: &str) {
         assert_eq!(attribute.to_string(), expected);
     }
@@ -502,6 +908,87 @@ mod tests {
         assert_eq!(attribute.to_string(), expected);
     }
 
+    #[rstest]
+    #[case(
+        Attribute::from_parsed(
+            Name::unchecked("members"),
+            Value::unchecked_multi(["AS65536,", "AS65537"]),
+            vec![
+                Comment::inline(" transit", 0),
+                Comment::own_line(" peers", 0),
+                Comment::inline(" peer", 1),
+            ]
+        ),
+        concat!(
+            "members:        AS65536, # transit\n",
+            "# peers\n",
+            "                AS65537 # peer\n",
+        )
+    )]
+    #[case(
+        Attribute::from_parsed(
+            Name::unchecked("remarks"),
+            Value::unchecked_single(None),
+            vec![Comment::inline("####", 0)]
+        ),
+        "remarks:        #####\n"
+    )]
+    fn attribute_display_comments(#[case] attribute: Attribute, #[case] expected: &str) {
+        assert_eq!(attribute.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::unchanged(|_: &mut Attribute| {}, "remarks:   Peering # transit\n+          Policy\n")]
+    #[case::changed_back(
+        |attribute: &mut Attribute| {
+            attribute.value = vec!["Peering", "Policy"].try_into().unwrap();
+        },
+        "remarks:   Peering # transit\n+          Policy\n"
+    )]
+    #[case::name_case_changed(
+        |attribute: &mut Attribute| attribute.name = "Remarks".parse().unwrap(),
+        "Remarks:        Peering # transit\n                Policy\n"
+    )]
+    #[case::value_line_changed(
+        |attribute: &mut Attribute| {
+            attribute.value = vec!["Peering", "Routing"].try_into().unwrap();
+        },
+        "remarks:        Peering # transit\n                Routing\n"
+    )]
+    #[case::value_lines_removed(
+        |attribute: &mut Attribute| attribute.value = "Peering".parse().unwrap(),
+        "remarks:        Peering # transit\n"
+    )]
+    /// Parsed attributes, owned or not, display like their source until they are changed.
+    fn attribute_display_source_until_changed(
+        #[case] change: fn(&mut Attribute),
+        #[case] expected: &str,
+    ) {
+        let object =
+            crate::parse_object("remarks:   Peering # transit\n+          Policy\n\n").unwrap();
+        let mut borrowed = object[0].clone();
+        let mut owned = borrowed.clone().into_owned();
+
+        change(&mut borrowed);
+        change(&mut owned);
+        assert_eq!(borrowed.to_string(), expected);
+        assert_eq!(owned.to_string(), expected);
+    }
+
+    #[test]
+    fn attribute_eq_ignores_comments() {
+        let commented = Attribute::from_parsed(
+            Name::unchecked("remarks"),
+            Value::unchecked_single("Peering"),
+            vec![Comment::inline(" policy", 0)],
+        );
+        assert_eq!(commented, Attribute::unchecked_single("remarks", "Peering"));
+        assert_eq!(
+            commented.clone().into_owned().comments(),
+            commented.comments()
+        );
+    }
+
     #[rstest]
     #[case(
         Attribute::unchecked_single("ASNumber", "32934"),
@@ -543,6 +1030,47 @@ mod tests {
         assert_ser_tokens(&attribute, expected);
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn attribute_deserialize() {
+        assert_de_tokens(
+            &Attribute::unchecked_multi("remarks", [Some("Locations"), None]),
+            &[
+                Token::Struct {
+                    name: "Attribute",
+                    len: 2,
+                },
+                Token::Str("name"),
+                Token::BorrowedStr("remarks"),
+                Token::Str("values"),
+                Token::Seq { len: Some(2) },
+                Token::Some,
+                Token::BorrowedStr("Locations"),
+                Token::None,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[rstest]
+    #[case(
+        Attribute::unchecked_single("ASNumber", "32934"),
+        Attribute::new("ASNumber".parse().unwrap(), "32934".parse().unwrap())
+    )]
+    #[case(
+        Attribute::unchecked_multi("remarks", [Some("Locations"), None, Some("LA1")]),
+        Attribute::new(
+            "remarks".parse().unwrap(),
+            vec!["Locations", "", "LA1"].try_into().unwrap()
+        )
+    )]
+    fn attribute_into_owned(#[case] borrowed: Attribute, #[case] expected: Attribute<'static>) {
+        let owned = borrowed.into_owned();
+        assert!(matches!(owned.name.0, Cow::Owned(_)));
+        assert_eq!(owned, expected);
+    }
+
     #[test]
     fn name_display() {
         let name_display = Name::unchecked("address").to_string();
@@ -593,6 +1121,30 @@ mod tests {
         assert_ser_tokens(&name, &[expected]);
     }
 
+    #[rstest]
+    #[case(Token::BorrowedStr("ASNumber"))]
+    #[case(Token::Str("ASNumber"))]
+    #[case(Token::String("ASNumber"))]
+    #[cfg(feature = "serde")]
+    fn name_deserialize(#[case] token: Token) {
+        assert_de_tokens(&Name::unchecked("ASNumber"), &[token]);
+    }
+
+    #[rstest]
+    #[case("", "Invalid attribute name: cannot be empty")]
+    #[case(
+        "1name",
+        "Invalid attribute name: cannot start with a non-letter ASCII character"
+    )]
+    #[case(
+        "name-",
+        "Invalid attribute name: cannot end with a non-letter or non-digit ASCII character"
+    )]
+    #[cfg(feature = "serde")]
+    fn name_deserialize_invalid(#[case] name: &'static str, #[case] error: &str) {
+        assert_de_tokens_error::<Name>(&[Token::BorrowedStr(name)], error);
+    }
+
     #[rstest]
     #[case("This is a valid attribute value", Value::SingleLine(Some(Cow::Owned("This is a valid attribute value".to_string()))))]
     #[case("   ", Value::SingleLine(None))]
@@ -605,11 +1157,42 @@ mod tests {
         assert_eq!(Value::from_str(s).unwrap(), Value::SingleLine(None));
     }
 
+    #[rstest]
+    #[case(Value::unchecked_single("AS65536"), Ok(Asn::from(65536)))]
+    #[case(Value::unchecked_single(" as1.0 "), Ok(Asn::from(65536)))]
+    #[case(Value::unchecked_multi([None, Some("AS65536")]), Ok(Asn::from(65536)))]
+    #[case(Value::unchecked_single(None), Err(AsnError(String::new())))]
+    #[case(Value::unchecked_single("AS-FOO"), Err(AsnError("AS-FOO".to_string())))]
+    fn value_asn(#[case] value: Value, #[case] expected: Result<Asn, AsnError>) {
+        assert_eq!(value.asn(), expected);
+    }
+
+    #[rstest]
+    #[case(Value::unchecked_single(None), Ok(vec![]))]
+    #[case(
+        Value::unchecked_multi([Some("AS1, AS2,"), None, Some(",AS3")]),
+        Ok(vec![Asn::from(1), Asn::from(2), Asn::from(3)])
+    )]
+    #[case(Value::unchecked_single("AS1, AS-FOO"), Err(AsnError("AS-FOO".to_string())))]
+    fn value_parse_list(#[case] value: Value, #[case] expected: Result<Vec<Asn>, AsnError>) {
+        assert_eq!(value.parse_list(), expected);
+    }
+
+    #[test]
+    fn value_set_name() {
+        let value = Value::unchecked_single("as3257:as-customers");
+        assert_eq!(
+            value.set_name().unwrap(),
+            "AS3257:AS-CUSTOMERS".parse().unwrap()
+        );
+    }
+
     proptest! {
         #[test]
         fn value_validation_any_non_control_extended_ascii_valid(
             s in r"[\x00-\xFF]+"
-                .prop_filter("Must not contain control chars", |s| !s.chars().any(|c| c.is_ascii_control())))
+                .prop_filter("Must not contain control chars", |s| !s.chars().any(|c| c.is_ascii_control()))
+                .prop_filter("Must not contain comment chars", |s| !s.contains('#')))
             {
                 Value::validate(&s).unwrap();
         }
@@ -623,6 +1206,11 @@ mod tests {
         fn value_validation_any_ascii_control_is_err(s in r"[\x00-\x1F\x7F]+") {
             matches!(Value::validate(&s).unwrap_err(), InvalidValueError::ContainsControlChar);
         }
+
+        #[test]
+        fn value_validation_any_comment_char_is_err(s in r"[\x20-\x7E]*#[\x20-\x7E]*") {
+            matches!(Value::validate(&s).unwrap_err(), InvalidValueError::ContainsCommentChar);
+        }
     }
 
     #[rstest]
@@ -661,6 +1249,85 @@ mod tests {
         assert_ser_tokens(&value, expected);
     }
 
+    #[rstest]
+    #[case(
+        &[Token::Seq { len: Some(1) }, Token::Some, Token::BorrowedStr("32934"), Token::SeqEnd],
+        Value::unchecked_single("32934"),
+    )]
+    #[case(
+        &[Token::Seq { len: Some(1) }, Token::None, Token::SeqEnd],
+        Value::unchecked_single(None),
+    )]
+    #[case(
+        &[Token::Seq { len: Some(1) }, Token::Some, Token::BorrowedStr("  "), Token::SeqEnd],
+        Value::unchecked_single(None),
+    )]
+    #[case(
+        &[
+            Token::Seq { len: Some(3) },
+            Token::Some,
+            Token::BorrowedStr("Packet Street 6"),
+            Token::None,
+            Token::Some,
+            Token::String("Internet"),
+            Token::SeqEnd,
+        ],
+        Value::unchecked_multi([Some("Packet Street 6"), None, Some("Internet")]),
+    )]
+    #[cfg(feature = "serde")]
+    fn value_deserialize(#[case] tokens: &[Token], #[case] expected: Value) {
+        assert_de_tokens(&expected, tokens);
+    }
+
+    #[rstest]
+    #[case(
+        &[Token::Seq { len: Some(0) }, Token::SeqEnd],
+        "invalid length 0, expected at least one line"
+    )]
+    #[case(
+        &[Token::Seq { len: Some(1) }, Token::Some, Token::BorrowedStr("\u{2603}"), Token::SeqEnd],
+        "Invalid attribute value: cannot contain characters that are not part of the extended ASCII set"
+    )]
+    #[case(
+        &[Token::Seq { len: Some(2) }, Token::None, Token::Some, Token::BorrowedStr("A\x07"), Token::SeqEnd],
+        "Invalid attribute value: cannot contain ASCII control characters"
+    )]
+    #[case(&[Token::Str("32934")], "invalid type: string \"32934\", expected a sequence")]
+    #[cfg(feature = "serde")]
+    fn value_deserialize_invalid(#[case] tokens: &[Token], #[case] error: &str) {
+        assert_de_tokens_error::<Value>(tokens, error);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_borrows_from_json() {
+        let json = r#"{"name": "address", "values": ["Packet Street 6", null, "Inter\u006eet"]}"#;
+        let attribute: Attribute = serde_json::from_str(json).unwrap();
+        assert!(matches!(attribute.name.0, Cow::Borrowed("address")));
+        match &attribute.value {
+            Value::MultiLine(values) => {
+                assert!(matches!(values[0], Some(Cow::Borrowed(_))));
+                assert!(matches!(values[2], Some(Cow::Owned(_))));
+            }
+            Value::SingleLine(_) => unreachable!("three lines are deserialized"),
+        }
+        assert_eq!(attribute.value, vec!["Packet Street 6", "", "Internet"]);
+    }
+
+    #[rstest]
+    #[case(Value::unchecked_single("single value"))]
+    #[case(Value::unchecked_single(None))]
+    #[case(Value::unchecked_multi([Some("multi"), None, Some("value")]))]
+    fn value_into_owned(#[case] value: Value) {
+        let owned = value.clone().into_owned();
+        let all_owned = match &owned {
+            Value::SingleLine(v) => v.iter().all(|v| matches!(v, Cow::Owned(_))),
+            Value::MultiLine(v) => v.iter().flatten().all(|v| matches!(v, Cow::Owned(_))),
+        };
+        assert!(all_owned);
+        assert_eq!(owned, value);
+    }
+
     #[rstest]
     #[case(Value::unchecked_single(""), Value::unchecked_single(None))]
     #[case(Value::unchecked_single("   "), Value::unchecked_single(None))]
@@ -0,0 +1,471 @@
+//! Command-line tool for validating, formatting, searching and converting RPSL.
+//!
+//! Every subcommand reads the given files, or standard input if none are given, one object at a
+//! time and writes its output as each object is read. It exits with one of the following codes:
+//! - `0` on success.
+//! - `1` if a check failed, i.e. `validate` found an error, `fmt --check` found an object that
+//!   is not formatted or `grep` selected no objects.
+//! - `2` if the tool failed, e.g. due to invalid arguments, unreadable input or, for all
+//!   subcommands except `validate`, invalid RPSL.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rpsl::{
+    format::{Alignment, Continuation, FormatOptions, Formatter},
+    schema::Schema,
+    JsonLayout, Object, ParseError, ParseWarning, ParserOptions, ReadError,
+};
+use serde::ser::{SerializeSeq, Serializer};
+
+const FAILURE: u8 = 1;
+const ERROR: u8 = 2;
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Parse RPSL and report the location of every error.
+    Validate {
+        #[command(flatten)]
+        input: Input,
+        /// Fail if a deviation from RPSL was accepted in lenient mode.
+        #[arg(long, requires = "lenient")]
+        deny_warnings: bool,
+    },
+    /// Reformat RPSL objects.
+    Fmt {
+        #[command(flatten)]
+        input: Input,
+        #[command(flatten)]
+        layout: Layout,
+        /// Report objects that are not formatted instead of printing the formatted objects.
+        #[arg(long)]
+        check: bool,
+    },
+    /// Print the objects of the given classes or containing the given attribute values.
+    Grep {
+        #[command(flatten)]
+        input: Input,
+        /// Select objects of the class, ignoring ASCII case. May be given multiple times to
+        /// select objects of any of the classes.
+        #[arg(short, long = "class", value_name = "CLASS")]
+        classes: Vec<String>,
+        /// Select objects containing the attribute, with a line of its value equal to VALUE if
+        /// given, ignoring ASCII case. May be given multiple times to select objects matching
+        /// all of them.
+        #[arg(short, long = "attribute", value_name = "NAME[=VALUE]")]
+        attributes: Vec<String>,
+        /// Select the objects that do not match.
+        #[arg(short = 'v', long)]
+        invert_match: bool,
+        /// Print the number of selected objects instead of the objects.
+        #[arg(long)]
+        count: bool,
+    },
+    /// Convert RPSL objects to JSON.
+    Convert {
+        #[command(flatten)]
+        input: Input,
+        /// The output format.
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+        /// The layout of each object.
+        #[arg(short, long, value_enum, default_value_t = JsonStyle::Attributes)]
+        layout: JsonStyle,
+    },
+}
+
+#[derive(Args)]
+struct Input {
+    /// The files to read, or `-` for standard input. Standard input is read if none are given.
+    files: Vec<PathBuf>,
+    /// Accept deviations from RPSL commonly found in real world data.
+    #[arg(long)]
+    lenient: bool,
+}
+
+#[derive(Args)]
+struct Layout {
+    /// The column at which values start, or `auto` to start values after the longest name.
+    #[arg(long, value_name = "COLUMN|auto", default_value = "16", value_parser = parse_alignment)]
+    align: Alignment,
+    /// The character starting continuation lines.
+    #[arg(long, value_enum, default_value_t = ContinuationStyle::Space)]
+    continuation: ContinuationStyle,
+    /// Wrap `descr` and `remarks` values at spaces to lines of at most WIDTH characters.
+    #[arg(long, value_name = "WIDTH")]
+    wrap: Option<usize>,
+    /// Order attributes as listed in the template of their class.
+    #[arg(long)]
+    order: bool,
+    /// Remove trailing and repeated empty continuation lines.
+    #[arg(long)]
+    trim: bool,
+}
+
+impl Layout {
+    fn formatter(&self) -> Formatter {
+        let mut options = FormatOptions::default()
+            .with_alignment(self.align)
+            .with_continuation(match self.continuation {
+                ContinuationStyle::Space => Continuation::Space,
+                ContinuationStyle::Plus => Continuation::Plus,
+                ContinuationStyle::PlusIfEmpty => Continuation::PlusIfEmpty,
+            });
+        if let Some(width) = self.wrap {
+            options = options.with_wrapping(width);
+        }
+        if self.order {
+            options = options.with_template_order(Schema::builtin());
+        }
+        if self.trim {
+            options = options.with_blank_line_trimming();
+        }
+        Formatter::new(options)
+    }
+}
+
+fn parse_alignment(s: &str) -> Result<Alignment, String> {
+    if s.eq_ignore_ascii_case("auto") {
+        return Ok(Alignment::Auto);
+    }
+    s.parse()
+        .map(Alignment::Column)
+        .map_err(|_| format!("expected a column or `auto`, found `{s}`"))
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ContinuationStyle {
+    /// Start continuation lines with a space.
+    Space,
+    /// Start continuation lines with a `+`.
+    Plus,
+    /// Start empty continuation lines with a `+` and others with a space.
+    PlusIfEmpty,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// A JSON array containing all objects.
+    Json,
+    /// A JSON object per line.
+    Jsonl,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum JsonStyle {
+    /// A list of attributes with the lines of their values, using `null` for empty lines.
+    Attributes,
+    /// Attribute names mapped to the non-empty lines of all attributes of that name.
+    Map,
+    /// Attribute names mapped to their joined values, or a list of values if repeated.
+    Joined,
+}
+
+impl From<JsonStyle> for JsonLayout {
+    fn from(style: JsonStyle) -> Self {
+        match style {
+            JsonStyle::Attributes => Self::Attributes,
+            JsonStyle::Map => Self::Map,
+            JsonStyle::Joined => Self::Joined,
+        }
+    }
+}
+
+/// RPSL read from a file or standard input.
+struct Source {
+    name: String,
+    reader: Box<dyn BufRead>,
+}
+
+impl Source {
+    /// Open all files, so that none is read unless all of them exist.
+    fn open_all(files: &[PathBuf]) -> io::Result<Vec<Self>> {
+        if files.is_empty() {
+            return Ok(vec![Self::stdin()]);
+        }
+        files
+            .iter()
+            .map(|path| {
+                if path.as_os_str() == "-" {
+                    return Ok(Self::stdin());
+                }
+                let file = File::open(path).map_err(|err| {
+                    io::Error::new(err.kind(), format!("{}: {err}", path.display()))
+                })?;
+                Ok(Self {
+                    name: path.display().to_string(),
+                    reader: Box::new(BufReader::new(file)),
+                })
+            })
+            .collect()
+    }
+
+    fn stdin() -> Self {
+        Self {
+            name: "<stdin>".to_string(),
+            reader: Box::new(BufReader::new(io::stdin())),
+        }
+    }
+
+    /// Read the objects contained in the source one at a time, passing each valid object to
+    /// `visit` and reporting every error as well as, if `warn` is set, every accepted deviation.
+    fn read(
+        self,
+        lenient: bool,
+        warn: bool,
+        mut visit: impl FnMut(&str, Object<'static>) -> io::Result<()>,
+    ) -> io::Result<Report> {
+        let options = if lenient {
+            ParserOptions::lenient()
+        } else {
+            ParserOptions::strict()
+        };
+        let mut report = Report::default();
+        let mut objects = options.read_objects(self.reader);
+        while let Some(result) = objects.next() {
+            if warn {
+                for warning in objects.warnings() {
+                    report_warning(&self.name, warning);
+                }
+            }
+            report.warnings += objects.warnings().len();
+            match result {
+                Ok(object) => visit(&self.name, object)?,
+                Err(ReadError::Parse(err)) => {
+                    report_error(&self.name, &err);
+                    report.errors += 1;
+                }
+                Err(ReadError::Io(err)) => {
+                    return Err(io::Error::new(err.kind(), format!("{}: {err}", self.name)));
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// The number of errors and accepted deviations found in a source.
+#[derive(Default)]
+struct Report {
+    errors: usize,
+    warnings: usize,
+}
+
+fn report_error(source: &str, err: &ParseError) {
+    let attribute = err
+        .attribute()
+        .map(|name| format!(" in attribute `{name}`"))
+        .unwrap_or_default();
+    eprintln!(
+        "{source}:{}:{}: error: {}{attribute}",
+        err.line(),
+        err.column(),
+        err.kind()
+    );
+}
+
+fn report_warning(source: &str, warning: &ParseWarning) {
+    eprintln!(
+        "{source}:{}:{}: warning: {}",
+        warning.line(),
+        warning.column(),
+        warning.kind()
+    );
+}
+
+/// Read the objects of all sources one at a time, passing each valid object to `visit` and
+/// reporting every error.
+///
+/// Returns whether all sources contain valid RPSL.
+fn read_all(
+    sources: Vec<Source>,
+    lenient: bool,
+    mut visit: impl FnMut(&str, Object<'static>) -> io::Result<()>,
+) -> io::Result<bool> {
+    let mut valid = true;
+    for source in sources {
+        valid &= source.read(lenient, false, &mut visit)?.errors == 0;
+    }
+    Ok(valid)
+}
+
+fn validate(sources: Vec<Source>, lenient: bool, deny_warnings: bool) -> io::Result<u8> {
+    let mut code = 0;
+    for source in sources {
+        let report = source.read(lenient, true, |_, _| Ok(()))?;
+        if report.errors > 0 || (deny_warnings && report.warnings > 0) {
+            code = FAILURE;
+        }
+    }
+    Ok(code)
+}
+
+fn fmt(sources: Vec<Source>, lenient: bool, formatter: &Formatter, check: bool) -> io::Result<u8> {
+    let mut stdout = io::stdout().lock();
+    let mut code = 0;
+    let valid = read_all(sources, lenient, |source, object| {
+        let formatted = formatter.format(&object);
+        if !check {
+            stdout.write_all(formatted.as_bytes())?;
+        } else if object.to_string() != formatted {
+            let key = object.iter().next().map_or(String::new(), |attribute| {
+                format!(
+                    " `{}: {}`",
+                    attribute.name,
+                    attribute.value.with_content().join(" ")
+                )
+            });
+            eprintln!("{source}: object{key} is not formatted");
+            code = FAILURE;
+        }
+        Ok(())
+    })?;
+    Ok(if valid { code } else { ERROR })
+}
+
+fn grep(
+    sources: Vec<Source>,
+    lenient: bool,
+    classes: &[String],
+    attributes: &[(String, Option<String>)],
+    invert_match: bool,
+    count: bool,
+) -> io::Result<u8> {
+    let selects = |object: &Object| {
+        let class_matches = classes.is_empty()
+            || object
+                .class()
+                .is_some_and(|class| classes.iter().any(|c| c.eq_ignore_ascii_case(class)));
+        let attributes_match = attributes.iter().all(|(name, value)| {
+            object
+                .iter()
+                .filter(|attribute| attribute.name.eq_ignore_ascii_case(name))
+                .any(|attribute| match value {
+                    Some(value) => attribute
+                        .value
+                        .with_content()
+                        .iter()
+                        .any(|line| line.eq_ignore_ascii_case(value)),
+                    None => true,
+                })
+        });
+        (class_matches && attributes_match) != invert_match
+    };
+
+    let mut stdout = io::stdout().lock();
+    let mut selected = 0;
+    let valid = read_all(sources, lenient, |_, object| {
+        if !selects(&object) {
+            return Ok(());
+        }
+        selected += 1;
+        if !count {
+            let rpsl = object.to_string();
+            stdout.write_all(rpsl.as_bytes())?;
+            // The last object of a response may not be followed by a blank line.
+            if !rpsl.ends_with("\n\n") && !rpsl.ends_with("\r\n\r\n") {
+                stdout.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    })?;
+    if !valid {
+        return Ok(ERROR);
+    }
+    if count {
+        writeln!(stdout, "{selected}")?;
+    }
+    Ok(if selected == 0 { FAILURE } else { 0 })
+}
+
+fn convert(
+    sources: Vec<Source>,
+    lenient: bool,
+    format: OutputFormat,
+    layout: JsonLayout,
+) -> io::Result<u8> {
+    let mut stdout = io::stdout().lock();
+    let valid = match format {
+        OutputFormat::Json => {
+            let mut serializer = serde_json::Serializer::pretty(&mut stdout);
+            let mut objects = serializer.serialize_seq(None)?;
+            let valid = read_all(sources, lenient, |_, object| {
+                Ok(objects.serialize_element(&object.json_with(layout))?)
+            })?;
+            objects.end()?;
+            writeln!(stdout)?;
+            valid
+        }
+        OutputFormat::Jsonl => read_all(sources, lenient, |_, object| {
+            serde_json::to_writer(&mut stdout, &object.json_with(layout))?;
+            writeln!(stdout)
+        })?,
+    };
+    Ok(if valid { 0 } else { ERROR })
+}
+
+fn run(command: Command) -> io::Result<u8> {
+    let input = match &command {
+        Command::Validate { input, .. }
+        | Command::Fmt { input, .. }
+        | Command::Grep { input, .. }
+        | Command::Convert { input, .. } => input,
+    };
+    let sources = Source::open_all(&input.files)?;
+    let lenient = input.lenient;
+
+    match &command {
+        Command::Validate { deny_warnings, .. } => validate(sources, lenient, *deny_warnings),
+        Command::Fmt { layout, check, .. } => fmt(sources, lenient, &layout.formatter(), *check),
+        Command::Grep {
+            classes,
+            attributes,
+            invert_match,
+            count,
+            ..
+        } => {
+            let attributes: Vec<(String, Option<String>)> = attributes
+                .iter()
+                .map(|attribute| match attribute.split_once('=') {
+                    Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                    None => (attribute.clone(), None),
+                })
+                .collect();
+            grep(
+                sources,
+                lenient,
+                classes,
+                &attributes,
+                *invert_match,
+                *count,
+            )
+        }
+        Command::Convert { format, layout, .. } => {
+            convert(sources, lenient, *format, (*layout).into())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(code) => ExitCode::from(code),
+        // Writing to a closed pipe, e.g. when piping into `head`, is not an error.
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("rpsl: {err}");
+            ExitCode::from(ERROR)
+        }
+    }
+}
@@ -0,0 +1,786 @@
+//! Typed representations of common RPSL classes.
+//!
+//! Each type wraps a generic [`Object`] of the respective class and provides accessors for its
+//! attributes. Objects are converted into a class using [`TryFrom`], which ensures that the object
+//! is of the correct class, contains all mandatory attributes and that the values identifying
+//! the object, like its AS number or prefix, are valid. Converting back into an [`Object`] is
+//! possible using [`From`].
+//!
+//! Accessors return values as typed as possible, e.g. an [`Asn`] or a parsed
+//! [`Policy`]. Attributes that may contain invalid values are parsed when accessed and return
+//! the first error encountered. The raw values remain available using [`AutNum::as_object`]
+//! and its counterparts of the other classes.
+//!
+//! The mandatory attributes of each class are those of its template in the
+//! [built-in schema](crate::schema::Schema::builtin), which is based on
+//! [RFC 2622](https://datatracker.ietf.org/doc/html/rfc2622),
+//! [RFC 2725](https://datatracker.ietf.org/doc/html/rfc2725) and
+//! [RFC 4012](https://datatracker.ietf.org/doc/html/rfc4012), as implemented by
+//! current IRR databases.
+//!
+//! # Example
+//! ```
+//! # use rpsl::{
+//! #     class::AutNum,
+//! #     parse_object,
+//! #     policy::{Policy, PolicyKind},
+//! #     Asn,
+//! # };
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let object = parse_object("
+//! aut-num:        AS65536
+//! as-name:        EXAMPLE
+//! import:         from AS65537
+//!                 accept AS-EXAMPLE
+//! export:         to AS65537 announce AS65536
+//! admin-c:        RPSL1-RIPE
+//! tech-c:         RPSL1-RIPE
+//! mnt-by:         MAINT-EXAMPLE, MAINT-EXAMPLE2
+//! source:         RIPE
+//!
+//! ")?;
+//! let aut_num = AutNum::try_from(object)?;
+//! assert_eq!(aut_num.aut_num(), Asn::from(65536));
+//! assert_eq!(aut_num.as_name(), "EXAMPLE");
+//! assert_eq!(
+//!     aut_num.imports()?,
+//!     vec![Policy::parse(PolicyKind::Import, "from AS65537 accept AS-EXAMPLE")?]
+//! );
+//! assert_eq!(aut_num.mnt_by(), vec!["MAINT-EXAMPLE", "MAINT-EXAMPLE2"]);
+//! # Ok(())
+//! # }
+//! ```
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use crate::{
+    policy::{Policy, PolicyError},
+    schema::{AttributeTemplate, ClassTemplate, Schema},
+    AddressPrefix, AsTerm, Asn, ClassError, Object, SetName,
+};
+
+/// Ensure an object is of the given class and contains content for all attributes that are
+/// mandatory according to the template of the class.
+fn validate(object: &Object, template: &'static ClassTemplate) -> Result<(), ClassError> {
+    let found = object.class().unwrap_or_default();
+    if !found.eq_ignore_ascii_case(template.name()) {
+        return Err(ClassError::WrongClass {
+            expected: template.name(),
+            found: found.to_string(),
+        });
+    }
+
+    let missing: Vec<&'static str> = template
+        .attributes()
+        .iter()
+        .filter(|attribute| attribute.is_mandatory())
+        .map(AttributeTemplate::name)
+        .filter(|name| object.first(name).is_none())
+        .collect();
+    if !missing.is_empty() {
+        return Err(ClassError::MissingAttributes(missing));
+    }
+
+    Ok(())
+}
+
+/// Ensure the value of an attribute can be parsed, if its accessor is infallible.
+macro_rules! check {
+    ($object:ident, parsed($attr:literal) -> $ty:ty) => {
+        if let Some(value) = $object.first($attr) {
+            if value.parse::<$ty>().is_err() {
+                return Err(ClassError::InvalidValue {
+                    name: $attr,
+                    value: value.to_string(),
+                });
+            }
+        }
+    };
+    ($object:ident, $kind:ident($attr:literal) $(-> $ty:ty)?) => {};
+}
+
+/// Generate an accessor for an attribute, depending on how its values are represented.
+macro_rules! accessor {
+    ($(#[$meta:meta])* $fn:ident: parsed($attr:literal) -> $ty:ty) => {
+        $(#[$meta])*
+        ///
+        /// # Panics
+        /// Never, as the value is validated when converting an object into the class.
+        #[must_use]
+        pub fn $fn(&self) -> $ty {
+            // Mandatory attributes are guaranteed to be present and valid by `check`.
+            self.0
+                .first($attr)
+                .and_then(|value| value.parse().ok())
+                .expect("the value is validated when converting")
+        }
+    };
+    ($(#[$meta:meta])* $fn:ident: parsed_list($attr:literal) -> $ty:ty) => {
+        $(#[$meta])*
+        ///
+        /// # Errors
+        /// Returns the error of the first item that cannot be parsed.
+        pub fn $fn(&self) -> Result<Vec<$ty>, <$ty as FromStr>::Err> {
+            self.0.list($attr).into_iter().map(str::parse).collect()
+        }
+    };
+    ($(#[$meta:meta])* $fn:ident: policies($attr:literal)) => {
+        $(#[$meta])*
+        ///
+        /// # Errors
+        /// Returns the error of the first policy that cannot be parsed.
+        pub fn $fn(&self) -> Result<Vec<Policy>, PolicyError> {
+            self.0
+                .attributes_named($attr)
+                .filter(|attribute| !attribute.value.with_content().is_empty())
+                .map(Policy::try_from)
+                .collect()
+        }
+    };
+    ($(#[$meta:meta])* $fn:ident: single($attr:literal)) => {
+        $(#[$meta])*
+        #[must_use]
+        pub fn $fn(&self) -> &str {
+            // Mandatory attributes are guaranteed to be present by `validate`.
+            self.0.first($attr).unwrap_or_default()
+        }
+    };
+    ($(#[$meta:meta])* $fn:ident: lines($attr:literal)) => {
+        $(#[$meta])*
+        #[must_use]
+        pub fn $fn(&self) -> Vec<&str> {
+            self.0.lines($attr)
+        }
+    };
+    ($(#[$meta:meta])* $fn:ident: list($attr:literal)) => {
+        $(#[$meta])*
+        #[must_use]
+        pub fn $fn(&self) -> Vec<&str> {
+            self.0.list($attr)
+        }
+    };
+    ($(#[$meta:meta])* $fn:ident: joined($attr:literal)) => {
+        $(#[$meta])*
+        #[must_use]
+        pub fn $fn(&self) -> Vec<Cow<'_, str>> {
+            self.0.joined($attr)
+        }
+    };
+}
+
+/// Generate a typed RPSL class wrapping an [`Object`].
+macro_rules! class {
+    (
+        $(#[$meta:meta])*
+        $ty:ident($class:literal) {
+            $(
+                $(#[$accessor_meta:meta])*
+                $fn:ident: $kind:ident($attr:literal) $(-> $value:ty)?,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $ty<'a>(Object<'a>);
+
+        impl<'a> $ty<'a> {
+            /// The name of the class.
+            pub const CLASS: &'static str = $class;
+
+            /// The template of the class in the [built-in schema](Schema::builtin), which
+            /// determines the attributes an object of this class must contain.
+            ///
+            /// # Panics
+            /// Never, as all typed classes are part of the built-in schema.
+            #[must_use]
+            pub fn template() -> &'static ClassTemplate {
+                Schema::builtin_ref()
+                    .get(Self::CLASS)
+                    .expect("typed classes are part of the built-in schema")
+            }
+
+            /// Ensure an object is of this class, contains all mandatory attributes and that the
+            /// values of infallible typed accessors can be parsed.
+            fn check(object: &Object) -> Result<(), ClassError> {
+                validate(object, Self::template())?;
+                $(
+                    check!(object, $kind($attr) $(-> $value)?);
+                )*
+                Ok(())
+            }
+
+            /// The underlying generic object.
+            #[must_use]
+            pub fn as_object(&self) -> &Object<'a> {
+                &self.0
+            }
+
+            $(
+                accessor!($(#[$accessor_meta])* $fn: $kind($attr) $(-> $value)?);
+            )*
+
+            accessor!(
+                /// The maintainers that protect the object.
+                mnt_by: list("mnt-by")
+            );
+            accessor!(
+                /// The addresses notified of changes to the object.
+                notify: lines("notify")
+            );
+            accessor!(
+                /// Remarks about the object.
+                remarks: lines("remarks")
+            );
+            accessor!(
+                /// The database the object is registered in.
+                source: single("source")
+            );
+        }
+
+        impl<'a> TryFrom<Object<'a>> for $ty<'a> {
+            type Error = ClassError;
+
+            fn try_from(object: Object<'a>) -> Result<Self, Self::Error> {
+                Self::check(&object)?;
+                Ok(Self(object))
+            }
+        }
+
+        impl<'a> TryFrom<&Object<'a>> for $ty<'a> {
+            type Error = ClassError;
+
+            fn try_from(object: &Object<'a>) -> Result<Self, Self::Error> {
+                Self::check(object)?;
+                Ok(Self(object.clone()))
+            }
+        }
+
+        impl<'a> From<$ty<'a>> for Object<'a> {
+            fn from(value: $ty<'a>) -> Self {
+                value.0
+            }
+        }
+
+        impl fmt::Display for $ty<'_> {
+            /// Display the object as RPSL.
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+class! {
+    /// An `aut-num` object, describing an autonomous system and its routing policy.
+    AutNum("aut-num") {
+        /// The AS number of the autonomous system.
+        aut_num: parsed("aut-num") -> Asn,
+        /// The name of the autonomous system.
+        as_name: single("as-name"),
+        /// A description of the autonomous system.
+        descr: lines("descr"),
+        /// The sets the autonomous system claims membership of.
+        member_of: parsed_list("member-of") -> SetName,
+        /// The IPv4 unicast import policies.
+        imports: policies("import"),
+        /// The IPv4 unicast export policies.
+        exports: policies("export"),
+        /// The multiprotocol import policies.
+        mp_imports: policies("mp-import"),
+        /// The multiprotocol export policies.
+        mp_exports: policies("mp-export"),
+        /// The IPv4 unicast default routing policies.
+        defaults: joined("default"),
+        /// The multiprotocol default routing policies.
+        mp_defaults: joined("mp-default"),
+        /// The administrative contacts.
+        admin_c: list("admin-c"),
+        /// The technical contacts.
+        tech_c: list("tech-c"),
+    }
+}
+
+class! {
+    /// A `route` object, describing an IPv4 route originated by an autonomous system.
+    Route("route") {
+        /// The IPv4 prefix of the route.
+        route: parsed("route") -> AddressPrefix,
+        /// The AS number originating the route.
+        origin: parsed("origin") -> Asn,
+        /// A description of the route.
+        descr: lines("descr"),
+        /// The more specific prefixes that are not reachable through this route.
+        holes: parsed_list("holes") -> AddressPrefix,
+        /// The sets the route claims membership of.
+        member_of: parsed_list("member-of") -> SetName,
+    }
+}
+
+class! {
+    /// A `route6` object, describing an IPv6 route originated by an autonomous system.
+    Route6("route6") {
+        /// The IPv6 prefix of the route.
+        route6: parsed("route6") -> AddressPrefix,
+        /// The AS number originating the route.
+        origin: parsed("origin") -> Asn,
+        /// A description of the route.
+        descr: lines("descr"),
+        /// The more specific prefixes that are not reachable through this route.
+        holes: parsed_list("holes") -> AddressPrefix,
+        /// The sets the route claims membership of.
+        member_of: parsed_list("member-of") -> SetName,
+    }
+}
+
+class! {
+    /// An `as-set` object, describing a set of autonomous systems.
+    AsSet("as-set") {
+        /// The name of the set.
+        as_set: parsed("as-set") -> SetName,
+        /// A description of the set.
+        descr: lines("descr"),
+        /// The AS numbers and sets that are members of the set.
+        members: parsed_list("members") -> AsTerm,
+        /// The maintainers whose objects may claim membership of the set.
+        mbrs_by_ref: list("mbrs-by-ref"),
+        /// The administrative contacts.
+        admin_c: list("admin-c"),
+        /// The technical contacts.
+        tech_c: list("tech-c"),
+    }
+}
+
+class! {
+    /// A `route-set` object, describing a set of routes.
+    RouteSet("route-set") {
+        /// The name of the set.
+        route_set: parsed("route-set") -> SetName,
+        /// A description of the set.
+        descr: lines("descr"),
+        /// The IPv4 prefixes and sets that are members of the set.
+        members: list("members"),
+        /// The IPv4 and IPv6 prefixes and sets that are members of the set.
+        mp_members: list("mp-members"),
+        /// The maintainers whose objects may claim membership of the set.
+        mbrs_by_ref: list("mbrs-by-ref"),
+        /// The administrative contacts.
+        admin_c: list("admin-c"),
+        /// The technical contacts.
+        tech_c: list("tech-c"),
+    }
+}
+
+class! {
+    /// A `mntner` object, describing a maintainer that is authorized to change objects.
+    Mntner("mntner") {
+        /// The name of the maintainer.
+        mntner: single("mntner"),
+        /// A description of the maintainer.
+        descr: lines("descr"),
+        /// The addresses notified of failed updates.
+        upd_to: lines("upd-to"),
+        /// The addresses notified of successful updates.
+        mnt_nfy: lines("mnt-nfy"),
+        /// The authentication schemes used to authorize updates.
+        auth: lines("auth"),
+        /// The administrative contacts.
+        admin_c: list("admin-c"),
+        /// The technical contacts.
+        tech_c: list("tech-c"),
+    }
+}
+
+class! {
+    /// A `person` object, describing a natural person acting as a contact.
+    Person("person") {
+        /// The full name of the person.
+        person: single("person"),
+        /// The postal address of the person.
+        address: lines("address"),
+        /// The telephone numbers of the person.
+        phone: lines("phone"),
+        /// The fax numbers of the person.
+        fax_no: lines("fax-no"),
+        /// The e-mail addresses of the person.
+        e_mail: lines("e-mail"),
+        /// The handle referencing the person.
+        nic_hdl: single("nic-hdl"),
+    }
+}
+
+class! {
+    /// A `role` object, describing a role performed by one or more people.
+    Role("role") {
+        /// The name of the role.
+        role: single("role"),
+        /// The postal address of the role.
+        address: lines("address"),
+        /// The telephone numbers of the role.
+        phone: lines("phone"),
+        /// The fax numbers of the role.
+        fax_no: lines("fax-no"),
+        /// The e-mail addresses of the role.
+        e_mail: lines("e-mail"),
+        /// The handle referencing the role.
+        nic_hdl: single("nic-hdl"),
+        /// The administrative contacts.
+        admin_c: list("admin-c"),
+        /// The technical contacts.
+        tech_c: list("tech-c"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::{parse_object, policy::PolicyKind, Attribute};
+
+    fn policies(kind: PolicyKind, texts: &[&str]) -> Vec<Policy> {
+        texts
+            .iter()
+            .map(|text| Policy::parse(kind, text).unwrap())
+            .collect()
+    }
+
+    fn set_names(names: &[&str]) -> Vec<SetName> {
+        names.iter().map(|name| name.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn aut_num_accessors() {
+        let object = parse_object(concat!(
+            "aut-num:        AS3257\n",
+            "as-name:        GTT-BACKBONE\n",
+            "descr:          GTT\n",
+            "member-of:      AS-TIER1, AS-GTT\n",
+            "import:         from AS12 accept AS12\n",
+            "import:         from AS14\n",
+            "                accept AS14\n",
+            "export:         to AS12 announce AS3257\n",
+            "mp-import:      afi ipv6.unicast from AS12 accept AS12\n",
+            "mp-export:      afi ipv6.unicast to AS12 announce AS3257\n",
+            "admin-c:        GTT-RIPE\n",
+            "tech-c:         GTT-RIPE\n",
+            "remarks:        Peering requests\n",
+            "mnt-by:         GTT-MNT\n",
+            "source:         RIPE\n",
+            "\n",
+        ))
+        .unwrap();
+        let aut_num = AutNum::try_from(object).unwrap();
+
+        assert_eq!(aut_num.aut_num(), Asn::from(3257));
+        assert_eq!(aut_num.as_name(), "GTT-BACKBONE");
+        assert_eq!(aut_num.descr(), vec!["GTT"]);
+        assert_eq!(
+            aut_num.member_of().unwrap(),
+            set_names(&["AS-TIER1", "AS-GTT"])
+        );
+        assert_eq!(
+            aut_num.imports().unwrap(),
+            policies(
+                PolicyKind::Import,
+                &["from AS12 accept AS12", "from AS14 accept AS14"]
+            )
+        );
+        assert_eq!(
+            aut_num.exports().unwrap(),
+            policies(PolicyKind::Export, &["to AS12 announce AS3257"])
+        );
+        assert_eq!(
+            aut_num.mp_imports().unwrap(),
+            policies(
+                PolicyKind::MpImport,
+                &["afi ipv6.unicast from AS12 accept AS12"]
+            )
+        );
+        assert_eq!(
+            aut_num.mp_exports().unwrap(),
+            policies(
+                PolicyKind::MpExport,
+                &["afi ipv6.unicast to AS12 announce AS3257"]
+            )
+        );
+        assert!(aut_num.defaults().is_empty());
+        assert_eq!(aut_num.admin_c(), vec!["GTT-RIPE"]);
+        assert_eq!(aut_num.tech_c(), vec!["GTT-RIPE"]);
+        assert_eq!(aut_num.remarks(), vec!["Peering requests"]);
+        assert_eq!(aut_num.mnt_by(), vec!["GTT-MNT"]);
+        assert_eq!(aut_num.source(), "RIPE");
+    }
+
+    #[test]
+    fn route_accessors() {
+        let route = Route::try_from(crate::object! {
+            "route": "192.0.2.0/24";
+            "descr": "Example";
+            "origin": "AS65536";
+            "holes": "192.0.2.0/28, 192.0.2.128/28";
+            "member-of": "RS-EXAMPLE";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        })
+        .unwrap();
+
+        assert_eq!(route.route(), "192.0.2.0/24".parse().unwrap());
+        assert_eq!(route.origin(), Asn::from(65536));
+        assert_eq!(route.descr(), vec!["Example"]);
+        assert_eq!(
+            route.holes().unwrap(),
+            vec![
+                "192.0.2.0/28".parse().unwrap(),
+                "192.0.2.128/28".parse().unwrap()
+            ]
+        );
+        assert_eq!(route.member_of().unwrap(), set_names(&["RS-EXAMPLE"]));
+    }
+
+    #[test]
+    fn route_set_accessors() {
+        let route_set = RouteSet::try_from(crate::object! {
+            "route-set": "AS65536:RS-EXAMPLE";
+            "members": "192.0.2.0/24^+", "198.51.100.0/24";
+            "mp-members": "2001:db8::/32^48";
+            "mbrs-by-ref": "ANY";
+            "admin-c": "RPSL1-RIPE";
+            "tech-c": "RPSL1-RIPE";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        })
+        .unwrap();
+
+        assert_eq!(route_set.route_set(), "AS65536:RS-EXAMPLE".parse().unwrap());
+        assert_eq!(
+            route_set.members(),
+            vec!["192.0.2.0/24^+", "198.51.100.0/24"]
+        );
+        assert_eq!(route_set.mp_members(), vec!["2001:db8::/32^48"]);
+        assert_eq!(route_set.mbrs_by_ref(), vec!["ANY"]);
+    }
+
+    #[rstest]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("route6", "2001:db8::/32"),
+            Attribute::unchecked_single("origin", "AS65536"),
+            Attribute::unchecked_single("mnt-by", "MAINT-EXAMPLE"),
+            Attribute::unchecked_single("source", "RIPE"),
+        ]),
+        Route6::CLASS
+    )]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("as-set", "AS-EXAMPLE"),
+            Attribute::unchecked_single("members", "AS65536, AS65537"),
+            Attribute::unchecked_single("admin-c", "RPSL1-RIPE"),
+            Attribute::unchecked_single("tech-c", "RPSL1-RIPE"),
+            Attribute::unchecked_single("mnt-by", "MAINT-EXAMPLE"),
+            Attribute::unchecked_single("source", "RIPE"),
+        ]),
+        AsSet::CLASS
+    )]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("mntner", "MAINT-EXAMPLE"),
+            Attribute::unchecked_single("admin-c", "RPSL1-RIPE"),
+            Attribute::unchecked_single("upd-to", "noc@example.com"),
+            Attribute::unchecked_single("auth", "PGPKEY-8F9D4C3B"),
+            Attribute::unchecked_single("mnt-by", "MAINT-EXAMPLE"),
+            Attribute::unchecked_single("source", "RIPE"),
+        ]),
+        Mntner::CLASS
+    )]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("person", "John Doe"),
+            Attribute::unchecked_single("address", "Packet Street 6"),
+            Attribute::unchecked_single("phone", "+49 176 07071964"),
+            Attribute::unchecked_single("nic-hdl", "JD1-RIPE"),
+            Attribute::unchecked_single("mnt-by", "MAINT-EXAMPLE"),
+            Attribute::unchecked_single("source", "RIPE"),
+        ]),
+        Person::CLASS
+    )]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("role", "ACME Company"),
+            Attribute::unchecked_single("address", "Packet Street 6"),
+            Attribute::unchecked_single("e-mail", "rpsl-rs@github.com"),
+            Attribute::unchecked_single("nic-hdl", "RPSL1-RIPE"),
+            Attribute::unchecked_single("mnt-by", "MAINT-EXAMPLE"),
+            Attribute::unchecked_single("source", "RIPE"),
+        ]),
+        Role::CLASS
+    )]
+    /// Objects containing all mandatory attributes convert into their class and back.
+    fn class_roundtrip(#[case] object: Object<'static>, #[case] class: &str) {
+        let back: Object = match class {
+            "route6" => Route6::try_from(&object).unwrap().into(),
+            "as-set" => AsSet::try_from(&object).unwrap().into(),
+            "mntner" => Mntner::try_from(&object).unwrap().into(),
+            "person" => Person::try_from(&object).unwrap().into(),
+            "role" => Role::try_from(&object).unwrap().into(),
+            _ => unreachable!(),
+        };
+        assert_eq!(back, object);
+    }
+
+    #[test]
+    /// Every typed class has a template in the built-in schema.
+    fn classes_have_template() {
+        for (template, class) in [
+            (AutNum::template(), AutNum::CLASS),
+            (Route::template(), Route::CLASS),
+            (Route6::template(), Route6::CLASS),
+            (AsSet::template(), AsSet::CLASS),
+            (RouteSet::template(), RouteSet::CLASS),
+            (Mntner::template(), Mntner::CLASS),
+            (Person::template(), Person::CLASS),
+            (Role::template(), Role::CLASS),
+        ] {
+            assert_eq!(template.name(), class);
+        }
+    }
+
+    #[test]
+    fn wrong_class_is_err() {
+        let object = crate::object! {
+            "route": "192.0.2.0/24";
+            "origin": "AS65536";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        };
+        assert_eq!(
+            Route6::try_from(&object).unwrap_err(),
+            ClassError::WrongClass {
+                expected: "route6",
+                found: "route".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn empty_object_is_wrong_class() {
+        assert_eq!(
+            Route::try_from(Object::new(vec![])).unwrap_err(),
+            ClassError::WrongClass {
+                expected: "route",
+                found: String::new()
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("route", "192.0.2.0/24"),
+            Attribute::unchecked_single("source", "RIPE"),
+        ]),
+        vec!["origin", "mnt-by"]
+    )]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("route", "192.0.2.0/24"),
+            Attribute::unchecked_single("origin", None),
+            Attribute::unchecked_single("mnt-by", "MAINT-EXAMPLE"),
+            Attribute::unchecked_single("source", "RIPE"),
+        ]),
+        vec!["origin"]
+    )]
+    /// Missing mandatory attributes, including those without a value, are reported.
+    fn missing_mandatory_attributes_is_err(
+        #[case] object: Object<'static>,
+        #[case] expected: Vec<&'static str>,
+    ) {
+        assert_eq!(
+            Route::try_from(object).unwrap_err(),
+            ClassError::MissingAttributes(expected)
+        );
+    }
+
+    #[test]
+    /// The class name and attribute names are matched regardless of their case.
+    fn class_is_case_insensitive() {
+        let route = Route::try_from(crate::object! {
+            "Route": "192.0.2.0/24";
+            "ORIGIN": "AS65536";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        })
+        .unwrap();
+        assert_eq!(route.origin(), Asn::from(65536));
+    }
+
+    #[test]
+    fn as_set_members() {
+        let as_set = AsSet::try_from(crate::object! {
+            "as-set": "AS65536:AS-CUSTOMERS";
+            "members": "AS65537, AS-PEERS", "AS1.2";
+            "admin-c": "RPSL1-RIPE";
+            "tech-c": "RPSL1-RIPE";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        })
+        .unwrap();
+
+        assert_eq!(as_set.as_set(), "AS65536:AS-CUSTOMERS".parse().unwrap());
+        assert_eq!(
+            as_set.members().unwrap(),
+            vec![
+                AsTerm::Asn(Asn::from(65537)),
+                AsTerm::Set("AS-PEERS".parse().unwrap()),
+                AsTerm::Asn(Asn::from(65538)),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(Route::try_from(crate::object! {
+        "route": "192.0.2.1/24";
+        "origin": "AS65536";
+        "mnt-by": "MAINT-EXAMPLE";
+        "source": "RIPE";
+    }), "route", "192.0.2.1/24")]
+    #[case(Route::try_from(crate::object! {
+        "route": "192.0.2.0/24";
+        "origin": "AS-EXAMPLE";
+        "mnt-by": "MAINT-EXAMPLE";
+        "source": "RIPE";
+    }), "origin", "AS-EXAMPLE")]
+    /// Values returned by infallible typed accessors are validated when converting.
+    fn invalid_value_is_err(
+        #[case] result: Result<Route<'static>, ClassError>,
+        #[case] name: &'static str,
+        #[case] value: &str,
+    ) {
+        assert_eq!(
+            result.unwrap_err(),
+            ClassError::InvalidValue {
+                name,
+                value: value.to_string()
+            }
+        );
+    }
+
+    #[test]
+    /// Invalid items and policies are only reported when accessed.
+    fn invalid_items_are_err_when_accessed() {
+        let aut_num = AutNum::try_from(crate::object! {
+            "aut-num": "AS65536";
+            "as-name": "EXAMPLE";
+            "member-of": "AS-EXAMPLE, 1";
+            "import": "from AS65537 accept AS65537";
+            "import": "from AS65537 announce AS65536";
+            "admin-c": "RPSL1-RIPE";
+            "tech-c": "RPSL1-RIPE";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        })
+        .unwrap();
+
+        assert!(aut_num.member_of().is_err());
+        assert_eq!(aut_num.imports().unwrap_err().line(), 1);
+        assert!(aut_num.exports().unwrap().is_empty());
+    }
+}
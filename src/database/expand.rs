@@ -0,0 +1,600 @@
+use std::collections::{BTreeSet, HashMap};
+
+use thiserror::Error;
+
+use super::{IrrDatabase, ObjectKey};
+use crate::{
+    AddressPrefix, AddressPrefixRange, AsTerm, Asn, Object, RangeOperator, SetClass, SetName,
+};
+
+/// Recursively expands `as-set` and `route-set` objects contained in an [`IrrDatabase`].
+///
+/// Sets are expanded by following their `members` and `mp-members`, as well as the objects
+/// claiming membership using `member-of` that are allowed to do so by the `mbrs-by-ref` of the
+/// set, as described in [RFC 2622, Section 5](https://datatracker.ietf.org/doc/html/rfc2622#section-5).
+/// Expansion never fails. Instead, references that cannot be resolved, loops and sets exceeding
+/// the maximum depth are reported as [`ExpansionIssue`]s alongside the members found.
+///
+/// # Example
+/// ```
+/// # use rpsl::{database::{Expander, IrrDatabase}, object, Asn};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut database = IrrDatabase::new();
+/// database.insert(object! {
+///     "as-set": "AS-EXAMPLE";
+///     "members": "AS65536, AS-CUSTOMERS";
+/// })?;
+/// database.insert(object! {
+///     "as-set": "AS-CUSTOMERS";
+///     "members": "AS65537, AS-EXAMPLE";
+/// })?;
+/// database.insert(object! {
+///     "route": "192.0.2.0/24";
+///     "origin": "AS65537";
+/// })?;
+///
+/// let expander = Expander::new(&database);
+/// let asns = expander.asns(&"AS-EXAMPLE".parse()?);
+/// assert_eq!(asns.members, [65536, 65537].map(Asn::from).into());
+/// assert_eq!(asns.issues.len(), 1);
+///
+/// let prefixes = expander.prefixes(&"AS-EXAMPLE".parse()?);
+/// assert_eq!(prefixes.members, ["192.0.2.0/24".parse()?].into());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Expander<'d> {
+    database: &'d IrrDatabase,
+    max_depth: Option<usize>,
+}
+
+/// The result of expanding a set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expansion<T> {
+    /// The members found, including those of nested sets.
+    pub members: BTreeSet<T>,
+    /// The issues encountered, which may cause members to be missing.
+    pub issues: Vec<ExpansionIssue>,
+}
+
+impl<T> Expansion<T> {
+    /// Whether the set was expanded without any issues.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// An issue encountered while expanding a set.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ExpansionIssue {
+    /// A referenced set does not exist in the database.
+    #[error("set `{0}` does not exist")]
+    Unresolved(SetName),
+    /// A member of a set is neither a valid reference nor a valid prefix.
+    #[error("invalid member `{member}` of set `{set}`")]
+    InvalidMember {
+        /// The set containing the member.
+        set: SetName,
+        /// The invalid member.
+        member: String,
+    },
+    /// A set contains itself, directly or through other sets. The sets forming the loop are
+    /// listed in the order they were expanded, starting and ending with the same set.
+    #[error("loop detected: {}", display_path(.0))]
+    Loop(Vec<SetName>),
+    /// A set is nested deeper than the maximum depth and was not expanded.
+    #[error("set `{0}` exceeds the maximum expansion depth")]
+    DepthExceeded(SetName),
+}
+
+fn display_path(path: &[SetName]) -> String {
+    let path: Vec<String> = path.iter().map(ToString::to_string).collect();
+    path.join(" -> ")
+}
+
+/// The state shared while expanding a set and all sets nested within it.
+#[derive(Default)]
+struct State {
+    path: Vec<SetName>,
+    asns: HashMap<SetName, Expanded<Asn>>,
+    prefixes: HashMap<SetName, Expanded<AddressPrefixRange>>,
+    issues: Vec<ExpansionIssue>,
+    /// The number of loops and sets nested too deep encountered so far.
+    cutoffs: usize,
+    /// The length of the longest path reached while expanding the current set.
+    deepest: usize,
+}
+
+impl State {
+    fn report(&mut self, issue: ExpansionIssue) {
+        if !self.issues.contains(&issue) {
+            self.issues.push(issue);
+        }
+    }
+}
+
+/// The members of a set expanded without running into a loop or the maximum depth.
+struct Expanded<T> {
+    members: BTreeSet<T>,
+    /// How deep the sets nested within the set go, where the set itself is at height 0.
+    height: usize,
+}
+
+impl<'d> Expander<'d> {
+    /// Create an expander resolving sets in the given database, without limiting the depth
+    /// sets may be nested.
+    #[must_use]
+    pub fn new(database: &'d IrrDatabase) -> Self {
+        Self {
+            database,
+            max_depth: None,
+        }
+    }
+
+    /// Limit the depth sets may be nested, where members of the expanded set are at depth 1.
+    /// Sets nested deeper are not expanded and reported as [`ExpansionIssue::DepthExceeded`].
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{database::{Expander, ExpansionIssue, IrrDatabase}, object, Asn};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut database = IrrDatabase::new();
+    /// database.insert(object! {
+    ///     "as-set": "AS-OUTER";
+    ///     "members": "AS65536, AS-INNER";
+    /// })?;
+    /// database.insert(object! {
+    ///     "as-set": "AS-INNER";
+    ///     "members": "AS65537";
+    /// })?;
+    ///
+    /// let asns = Expander::new(&database).with_max_depth(0).asns(&"AS-OUTER".parse()?);
+    /// assert_eq!(asns.members, [Asn::from(65536)].into());
+    /// assert_eq!(asns.issues, vec![ExpansionIssue::DepthExceeded("AS-INNER".parse()?)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Expand an `as-set` into the AS numbers it contains.
+    #[must_use]
+    pub fn asns(&self, name: &SetName) -> Expansion<Asn> {
+        let mut state = State::default();
+        let members = self.as_set(name, &mut state);
+        Expansion {
+            members,
+            issues: state.issues,
+        }
+    }
+
+    /// Expand an `as-set` or `route-set` into the prefix ranges it contains.
+    ///
+    /// The prefixes of an AS number are those of the `route` and `route6` objects it
+    /// originates. Range operators applied to members of a `route-set` are applied to all
+    /// prefixes of the member, dropping prefixes the operator cannot be applied to.
+    #[must_use]
+    pub fn prefixes(&self, name: &SetName) -> Expansion<AddressPrefixRange> {
+        let mut state = State::default();
+        let members = self.prefix_set(name, &mut state);
+        Expansion {
+            members,
+            issues: state.issues,
+        }
+    }
+
+    /// The prefixes of the `route` and `route6` objects originated by an AS number.
+    #[must_use]
+    pub fn routes(&self, asn: Asn) -> BTreeSet<AddressPrefix> {
+        self.database
+            .referencing("origin", &asn.to_string())
+            .into_iter()
+            .filter_map(route_prefix)
+            .collect()
+    }
+
+    fn prefix_set(&self, name: &SetName, state: &mut State) -> BTreeSet<AddressPrefixRange> {
+        match name.class() {
+            SetClass::AsSet => self
+                .as_set(name, state)
+                .into_iter()
+                .flat_map(|asn| self.routes(asn))
+                .map(AddressPrefixRange::from)
+                .collect(),
+            SetClass::RouteSet => self.route_set(name, state),
+            _ => {
+                state.report(ExpansionIssue::Unresolved(name.clone()));
+                BTreeSet::new()
+            }
+        }
+    }
+
+    fn as_set(&self, name: &SetName, state: &mut State) -> BTreeSet<Asn> {
+        self.resolve(
+            name,
+            state,
+            |state| &mut state.asns,
+            |object, state| {
+                let mut members = BTreeSet::new();
+                for member in object.list("members") {
+                    match member.parse::<AsTerm>() {
+                        Ok(AsTerm::Asn(asn)) => {
+                            members.insert(asn);
+                        }
+                        Ok(AsTerm::Set(set)) => members.extend(self.as_set(&set, state)),
+                        Err(_) => state.report(ExpansionIssue::InvalidMember {
+                            set: name.clone(),
+                            member: member.to_string(),
+                        }),
+                    }
+                }
+                members.extend(
+                    self.members_by_ref(name, object, &["aut-num"])
+                        .into_iter()
+                        .filter_map(|object| object.first("aut-num")?.parse::<Asn>().ok()),
+                );
+                members
+            },
+        )
+    }
+
+    fn route_set(&self, name: &SetName, state: &mut State) -> BTreeSet<AddressPrefixRange> {
+        self.resolve(
+            name,
+            state,
+            |state| &mut state.prefixes,
+            |object, state| {
+                let mut members = BTreeSet::new();
+                for member in object
+                    .list("members")
+                    .into_iter()
+                    .chain(object.list("mp-members"))
+                {
+                    if let Ok(range) = member.parse::<AddressPrefixRange>() {
+                        members.insert(range);
+                        continue;
+                    }
+                    let (reference, operator) = match member.split_once('^') {
+                        Some((reference, _)) => {
+                            match member[reference.len()..].parse::<RangeOperator>() {
+                                Ok(operator) => (reference, Some(operator)),
+                                Err(_) => (member, None),
+                            }
+                        }
+                        None => (member, None),
+                    };
+                    let prefixes =
+                        if let Ok(AsTerm::Asn(asn)) = reference.parse::<AsTerm>() {
+                            self.routes(asn)
+                                .into_iter()
+                                .map(AddressPrefixRange::from)
+                                .collect()
+                        } else if let Some(set) = reference.parse::<SetName>().ok().filter(|set| {
+                            matches!(set.class(), SetClass::AsSet | SetClass::RouteSet)
+                        }) {
+                            self.prefix_set(&set, state)
+                        } else {
+                            state.report(ExpansionIssue::InvalidMember {
+                                set: name.clone(),
+                                member: member.to_string(),
+                            });
+                            continue;
+                        };
+                    members.extend(
+                        prefixes
+                            .into_iter()
+                            .filter_map(|range| operator.map_or(Some(range), |op| range.apply(op))),
+                    );
+                }
+                members.extend(
+                    self.members_by_ref(name, object, &["route", "route6"])
+                        .into_iter()
+                        .filter_map(route_prefix)
+                        .map(AddressPrefixRange::from),
+                );
+                members
+            },
+        )
+    }
+
+    /// Look up and expand a set, reporting unresolved sets, loops and sets nested too deep.
+    ///
+    /// The members of a set are reused if it was expanded before without being cut short, and
+    /// none of its nested sets exceed the maximum depth at the current position. Sets cut short
+    /// are expanded again wherever they appear, so the result does not depend on member order.
+    fn resolve<T, C, E>(
+        &self,
+        name: &SetName,
+        state: &mut State,
+        cache: C,
+        expand: E,
+    ) -> BTreeSet<T>
+    where
+        T: Clone,
+        C: Fn(&mut State) -> &mut HashMap<SetName, Expanded<T>>,
+        E: FnOnce(&'d Object<'static>, &mut State) -> BTreeSet<T>,
+    {
+        let depth = state.path.len();
+        if let Some(expanded) = cache(state).get(name) {
+            let height = expanded.height;
+            if !self.max_depth.is_some_and(|max| depth + height > max) {
+                let members = expanded.members.clone();
+                state.deepest = state.deepest.max(depth + height);
+                return members;
+            }
+        }
+        if let Some(start) = state.path.iter().position(|set| set == name) {
+            let mut path = state.path[start..].to_vec();
+            path.push(name.clone());
+            state.cutoffs += 1;
+            state.report(ExpansionIssue::Loop(path));
+            return BTreeSet::new();
+        }
+        if self.max_depth.is_some_and(|max| depth > max) {
+            state.cutoffs += 1;
+            state.report(ExpansionIssue::DepthExceeded(name.clone()));
+            return BTreeSet::new();
+        }
+
+        let cutoffs = state.cutoffs;
+        let deepest = std::mem::replace(&mut state.deepest, depth);
+        let key = ObjectKey::new(name.class().class_name(), [name.normalized()]);
+        let members = if let Some(object) = self.database.get(&key) {
+            state.path.push(name.clone());
+            let members = expand(object, state);
+            state.path.pop();
+            members
+        } else {
+            state.report(ExpansionIssue::Unresolved(name.clone()));
+            BTreeSet::new()
+        };
+        let height = state.deepest - depth;
+        state.deepest = state.deepest.max(deepest);
+        if state.cutoffs == cutoffs {
+            let expanded = Expanded {
+                members: members.clone(),
+                height,
+            };
+            cache(state).insert(name.clone(), expanded);
+        }
+        members
+    }
+
+    /// The objects of the given classes that claim membership of the set using `member-of` and
+    /// are maintained by one of the maintainers listed in its `mbrs-by-ref`.
+    fn members_by_ref(
+        &self,
+        name: &SetName,
+        set: &Object,
+        classes: &[&str],
+    ) -> Vec<&'d Object<'static>> {
+        let maintainers: Vec<String> = set
+            .list("mbrs-by-ref")
+            .into_iter()
+            .map(str::to_ascii_uppercase)
+            .collect();
+        let mut candidates = if maintainers.is_empty() {
+            Vec::new()
+        } else {
+            self.database.referencing("member-of", name.normalized())
+        };
+        candidates.retain(|object| {
+            object
+                .class()
+                .is_some_and(|class| classes.iter().any(|c| c.eq_ignore_ascii_case(class)))
+                && (maintainers.iter().any(|m| m == "ANY")
+                    || object
+                        .list("mnt-by")
+                        .into_iter()
+                        .any(|m| maintainers.contains(&m.to_ascii_uppercase())))
+        });
+        candidates
+    }
+}
+
+/// The prefix of a `route` or `route6` object.
+fn route_prefix(object: &Object) -> Option<AddressPrefix> {
+    let class = object.class()?;
+    if !(class.eq_ignore_ascii_case("route") || class.eq_ignore_ascii_case("route6")) {
+        return None;
+    }
+    object.first(class)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::object;
+
+    fn database() -> IrrDatabase {
+        let mut database = IrrDatabase::new();
+        for object in [
+            object! {
+                "as-set": "AS-EXAMPLE";
+                "members": "AS65536, AS-CUSTOMERS";
+                "members": "AS65536:AS-PEERS";
+            },
+            object! {
+                "as-set": "AS-CUSTOMERS";
+                "members": "AS65537, AS-CUSTOMERS2";
+                "mbrs-by-ref": "MAINT-CUSTOMER";
+            },
+            object! {
+                "as-set": "AS-CUSTOMERS2";
+                "members": "AS1.2, AS-CUSTOMERS";
+            },
+            object! {
+                "as-set": "AS65536:AS-PEERS";
+                "members": "AS65537, AS-MISSING, FOO";
+            },
+            object! {
+                "aut-num": "AS65540";
+                "member-of": "AS-CUSTOMERS";
+                "mnt-by": "MAINT-CUSTOMER";
+            },
+            object! {
+                "aut-num": "AS65541";
+                "member-of": "AS-CUSTOMERS";
+                "mnt-by": "MAINT-OTHER";
+            },
+            object! {
+                "route": "192.0.2.0/24";
+                "origin": "AS65537";
+                "member-of": "RS-EXAMPLE";
+                "mnt-by": "MAINT-CUSTOMER";
+            },
+            object! {
+                "route6": "2001:db8::/32";
+                "origin": "AS65538";
+            },
+            object! {
+                "route": "198.51.100.0/24";
+                "origin": "AS65540";
+            },
+            object! {
+                "route-set": "RS-EXAMPLE";
+                "members": "203.0.113.0/24^+, AS65538^48, AS-CUSTOMERS2";
+                "mp-members": "RS-NESTED^-";
+                "mbrs-by-ref": "ANY";
+            },
+            object! {
+                "route-set": "RS-NESTED";
+                "mp-members": "2001:db8:1::/48^48-56, RS-EXAMPLE";
+            },
+            object! {
+                "as-set": "AS-TOP";
+                "members": "AS-A, AS-B";
+            },
+            object! {
+                "as-set": "AS-TOP-REORDERED";
+                "members": "AS-B, AS-A";
+            },
+            object! {
+                "as-set": "AS-A";
+                "members": "AS-B";
+            },
+            object! {
+                "as-set": "AS-B";
+                "members": "AS-C";
+            },
+            object! {
+                "as-set": "AS-C";
+                "members": "AS1";
+            },
+        ] {
+            database.insert(object).unwrap();
+        }
+        database
+    }
+
+    fn name(name: &str) -> SetName {
+        name.parse().unwrap()
+    }
+
+    #[test]
+    fn as_set_is_expanded() {
+        let database = database();
+        let expansion = Expander::new(&database).asns(&name("as-example"));
+
+        assert_eq!(
+            expansion.members,
+            [65536, 65537, 65538, 65540].map(Asn::from).into()
+        );
+        assert_eq!(
+            expansion.issues,
+            vec![
+                ExpansionIssue::Loop(vec![
+                    name("AS-CUSTOMERS"),
+                    name("AS-CUSTOMERS2"),
+                    name("AS-CUSTOMERS"),
+                ]),
+                ExpansionIssue::Unresolved(name("AS-MISSING")),
+                ExpansionIssue::InvalidMember {
+                    set: name("AS65536:AS-PEERS"),
+                    member: "FOO".to_string(),
+                },
+            ]
+        );
+        assert!(!expansion.is_complete());
+    }
+
+    #[rstest]
+    #[case("AS-EXAMPLE", 0, vec![65536], vec!["AS-CUSTOMERS", "AS65536:AS-PEERS"])]
+    #[case("AS-EXAMPLE", 1, vec![65536, 65537, 65540], vec!["AS-CUSTOMERS2", "AS-MISSING"])]
+    #[case("AS-TOP", 2, vec![1], vec!["AS-C"])]
+    #[case("AS-TOP-REORDERED", 2, vec![1], vec!["AS-C"])]
+    fn max_depth_is_reported(
+        #[case] set: &str,
+        #[case] max_depth: usize,
+        #[case] asns: Vec<u32>,
+        #[case] exceeded: Vec<&str>,
+    ) {
+        let database = database();
+        let expansion = Expander::new(&database)
+            .with_max_depth(max_depth)
+            .asns(&name(set));
+
+        assert_eq!(expansion.members, asns.into_iter().map(Asn::from).collect());
+        for exceeded in exceeded {
+            assert!(expansion
+                .issues
+                .contains(&ExpansionIssue::DepthExceeded(name(exceeded))));
+        }
+    }
+
+    #[test]
+    fn route_set_is_expanded() {
+        let database = database();
+        let expansion = Expander::new(&database).prefixes(&name("RS-EXAMPLE"));
+
+        let expected: BTreeSet<AddressPrefixRange> = [
+            "203.0.113.0/24^+",
+            "2001:db8::/32^48",
+            "2001:db8::/32",
+            "192.0.2.0/24",
+            "198.51.100.0/24",
+            "2001:db8:1::/48^-",
+        ]
+        .iter()
+        .map(|range| range.parse().unwrap())
+        .collect();
+        assert_eq!(expansion.members, expected);
+        assert!(expansion.issues.contains(&ExpansionIssue::Loop(vec![
+            name("RS-EXAMPLE"),
+            name("RS-NESTED"),
+            name("RS-EXAMPLE"),
+        ])));
+    }
+
+    #[test]
+    fn unknown_set_is_unresolved() {
+        let database = database();
+        let expansion = Expander::new(&database).prefixes(&name("RS-MISSING"));
+
+        assert!(expansion.members.is_empty());
+        assert_eq!(
+            expansion.issues,
+            vec![ExpansionIssue::Unresolved(name("RS-MISSING"))]
+        );
+    }
+
+    #[test]
+    fn routes_of_asn() {
+        let database = database();
+        let routes = Expander::new(&database).routes(Asn::from(65537));
+        assert_eq!(routes, ["192.0.2.0/24".parse().unwrap()].into());
+    }
+
+    #[test]
+    fn issue_display() {
+        let issue = ExpansionIssue::Loop(vec![name("AS-A"), name("as-b"), name("AS-A")]);
+        assert_eq!(issue.to_string(), "loop detected: AS-A -> as-b -> AS-A");
+    }
+}
@@ -0,0 +1,573 @@
+//! An in-memory database of RPSL objects, indexed for lookups.
+//!
+//! An [`IrrDatabase`] stores objects by their [`ObjectKey`], made up of the class of an object
+//! and the values of the attributes forming the primary key of that class, as described by a
+//! [`Schema`]. In addition, objects are indexed by the values of inverse keys such as `mnt-by`
+//! or `origin`, allowing to find all objects referencing a maintainer or AS number.
+//!
+//! Keys are compared case insensitively. AS numbers, address prefixes and set names contained in
+//! keys are compared in their canonical form, so that e.g. `AS1.10` and `AS65546` are equal.
+//!
+//! The [`Expander`] recursively resolves the members of `as-set` and `route-set` objects
+//! contained in a database.
+//!
+//! # Example
+//! ```
+//! # use rpsl::{database::{IrrDatabase, ObjectKey}, parse_whois_response};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut database = IrrDatabase::new();
+//! for object in parse_whois_response("
+//! mntner:         MAINT-EXAMPLE
+//! mnt-by:         MAINT-EXAMPLE
+//! source:         RIPE
+//!
+//! route:          192.0.2.0/24
+//! origin:         AS65536
+//! mnt-by:         MAINT-EXAMPLE
+//! source:         RIPE
+//!
+//! ")? {
+//!     database.insert(object)?;
+//! }
+//!
+//! let key = ObjectKey::new("route", ["192.0.2.0/24", "as65536"]);
+//! assert!(database.get(&key).is_some());
+//! assert_eq!(database.inverse("mnt-by", "maint-example").count(), 2);
+//! assert_eq!(database.inverse("origin", "AS65536").count(), 1);
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+};
+
+use crate::{
+    schema::{AttributeTemplate, Schema},
+    AddressPrefix, Asn, DatabaseError, Object, SetName,
+};
+
+pub use expand::{Expander, Expansion, ExpansionIssue};
+
+mod expand;
+
+/// The attributes indexed as inverse keys by default.
+pub const DEFAULT_INVERSE_KEYS: [&str; 4] = ["mnt-by", "origin", "member-of", "admin-c"];
+
+/// The key uniquely identifying an object within an [`IrrDatabase`].
+///
+/// The key consists of the class of the object and the values of its primary key attributes,
+/// e.g. the prefix and origin of a `route` object. Both are normalized on creation.
+///
+/// # Example
+/// ```
+/// # use rpsl::database::ObjectKey;
+/// let key = ObjectKey::new("Route", ["192.0.2.0/24", "as1.10"]);
+/// assert_eq!(key.class(), "route");
+/// assert_eq!(key.components(), ["192.0.2.0/24", "AS65546"]);
+/// assert_eq!(key.to_string(), "route 192.0.2.0/24 AS65546");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectKey {
+    class: String,
+    components: Vec<String>,
+}
+
+impl ObjectKey {
+    /// Create a new key from the class of an object and the values of its primary key
+    /// attributes, in the order the attributes are listed in the class template.
+    #[must_use]
+    pub fn new<I, S>(class: &str, components: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            class: class.to_ascii_lowercase(),
+            components: components
+                .into_iter()
+                .map(|component| normalize(component.as_ref()))
+                .collect(),
+        }
+    }
+
+    /// The class of the object.
+    #[must_use]
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+
+    /// The normalized values of the primary key attributes.
+    #[must_use]
+    pub fn components(&self) -> &[String] {
+        &self.components
+    }
+}
+
+impl fmt::Display for ObjectKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.class)?;
+        for component in &self.components {
+            write!(f, " {component}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An in-memory database of RPSL objects, indexed by primary and inverse keys.
+#[derive(Debug, Clone)]
+pub struct IrrDatabase {
+    schema: Schema,
+    inverse_keys: Vec<String>,
+    objects: BTreeMap<ObjectKey, Object<'static>>,
+    inverse: HashMap<(String, String), BTreeSet<ObjectKey>>,
+}
+
+impl IrrDatabase {
+    /// Create an empty database using the built-in schema to determine primary keys and
+    /// indexing the [`DEFAULT_INVERSE_KEYS`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_schema(Schema::builtin())
+    }
+
+    /// Create an empty database using the given schema to determine primary keys.
+    ///
+    /// Objects of classes not contained in the schema are identified by the value of their
+    /// first attribute.
+    #[must_use]
+    pub fn with_schema(schema: Schema) -> Self {
+        Self {
+            schema,
+            inverse_keys: DEFAULT_INVERSE_KEYS.map(String::from).to_vec(),
+            objects: BTreeMap::new(),
+            inverse: HashMap::new(),
+        }
+    }
+
+    /// Index the given attributes as inverse keys instead of the [`DEFAULT_INVERSE_KEYS`].
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{database::IrrDatabase, object};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut database = IrrDatabase::new().with_inverse_keys(["tech-c"]);
+    /// database.insert(object! {
+    ///     "mntner": "MAINT-EXAMPLE";
+    ///     "tech-c": "RPSL1-RIPE";
+    ///     "mnt-by": "MAINT-EXAMPLE";
+    /// })?;
+    /// assert_eq!(database.inverse("tech-c", "RPSL1-RIPE").count(), 1);
+    /// assert_eq!(database.inverse("mnt-by", "MAINT-EXAMPLE").count(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_inverse_keys<I, S>(mut self, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.inverse_keys = attributes
+            .into_iter()
+            .map(|attribute| attribute.as_ref().to_ascii_lowercase())
+            .collect();
+        self.reindex();
+        self
+    }
+
+    /// The key identifying the object within the database.
+    ///
+    /// # Errors
+    /// Returns an error if the object does not contain any attributes or is missing a
+    /// primary key attribute.
+    pub fn key_of(&self, object: &Object) -> Result<ObjectKey, DatabaseError> {
+        let class = object.class().ok_or(DatabaseError::EmptyObject)?;
+        let attributes: Vec<&str> = match self.schema.get(class) {
+            Some(template) => template
+                .primary_key()
+                .map(AttributeTemplate::name)
+                .collect(),
+            None => vec![class],
+        };
+
+        let components = attributes
+            .into_iter()
+            .map(|attribute| {
+                object
+                    .first(attribute)
+                    .ok_or_else(|| DatabaseError::MissingPrimaryKey {
+                        class: class.to_string(),
+                        attribute: attribute.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ObjectKey::new(class, components))
+    }
+
+    /// Insert a new object into the database, returning its key.
+    ///
+    /// # Errors
+    /// Returns an error if the object has no valid key or an object with the same key is
+    /// already contained in the database.
+    pub fn insert(&mut self, object: Object) -> Result<ObjectKey, DatabaseError> {
+        let key = self.key_of(&object)?;
+        if self.objects.contains_key(&key) {
+            return Err(DatabaseError::DuplicateObject(key));
+        }
+        self.store(key.clone(), object.into_owned());
+        Ok(key)
+    }
+
+    /// Insert an object into the database, replacing and returning the object with the same
+    /// key, if any.
+    ///
+    /// # Errors
+    /// Returns an error if the object has no valid key.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{database::IrrDatabase, object};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut database = IrrDatabase::new();
+    /// database.insert(object! {
+    ///     "mntner": "MAINT-EXAMPLE";
+    ///     "mnt-by": "MAINT-EXAMPLE";
+    /// })?;
+    /// let previous = database.replace(object! {
+    ///     "mntner": "MAINT-EXAMPLE";
+    ///     "mnt-by": "MAINT-OTHER";
+    /// })?;
+    /// assert_eq!(previous.unwrap().get("mnt-by"), vec!["MAINT-EXAMPLE"]);
+    /// assert_eq!(database.inverse("mnt-by", "MAINT-EXAMPLE").count(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replace(&mut self, object: Object) -> Result<Option<Object<'static>>, DatabaseError> {
+        let key = self.key_of(&object)?;
+        let previous = self.delete(&key);
+        self.store(key, object.into_owned());
+        Ok(previous)
+    }
+
+    /// Remove the object with the given key from the database, returning it if it was
+    /// contained.
+    pub fn delete(&mut self, key: &ObjectKey) -> Option<Object<'static>> {
+        let object = self.objects.remove(key)?;
+        for index_key in self.inverse_entries(&object) {
+            if let Some(keys) = self.inverse.get_mut(&index_key) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.inverse.remove(&index_key);
+                }
+            }
+        }
+        Some(object)
+    }
+
+    /// The object with the given key.
+    #[must_use]
+    pub fn get(&self, key: &ObjectKey) -> Option<&Object<'static>> {
+        self.objects.get(key)
+    }
+
+    /// Whether an object with the given key is contained.
+    #[must_use]
+    pub fn contains(&self, key: &ObjectKey) -> bool {
+        self.objects.contains_key(key)
+    }
+
+    /// Iterate over the objects of the given class, ordered by key.
+    pub fn objects_of_class<'d>(
+        &'d self,
+        class: &str,
+    ) -> impl Iterator<Item = &'d Object<'static>> + 'd {
+        let start = ObjectKey::new(class, Vec::<&str>::new());
+        self.objects
+            .range(start.clone()..)
+            .take_while(move |(key, _)| key.class == start.class)
+            .map(|(_, object)| object)
+    }
+
+    /// Iterate over the objects that reference the given value in an inverse key attribute,
+    /// ordered by key.
+    ///
+    /// Only attributes the database was configured to index as inverse keys are considered.
+    pub fn inverse<'d>(
+        &'d self,
+        attribute: &str,
+        value: &str,
+    ) -> impl Iterator<Item = &'d Object<'static>> + 'd {
+        self.inverse
+            .get(&(attribute.to_ascii_lowercase(), normalize(value)))
+            .into_iter()
+            .flatten()
+            .filter_map(|key| self.objects.get(key))
+    }
+
+    /// The objects that reference the given value in an attribute, using the inverse index if
+    /// the attribute is indexed and scanning all objects otherwise.
+    pub(crate) fn referencing<'d>(
+        &'d self,
+        attribute: &str,
+        value: &str,
+    ) -> Vec<&'d Object<'static>> {
+        let attribute = attribute.to_ascii_lowercase();
+        if self.inverse_keys.contains(&attribute) {
+            return self.inverse(&attribute, value).collect();
+        }
+        let value = normalize(value);
+        self.objects
+            .values()
+            .filter(|object| {
+                object
+                    .list(&attribute)
+                    .into_iter()
+                    .any(|v| normalize(v) == value)
+            })
+            .collect()
+    }
+
+    /// Iterate over all objects, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&ObjectKey, &Object<'static>)> {
+        self.objects.iter()
+    }
+
+    /// The number of objects contained.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Whether the database does not contain any objects.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    fn store(&mut self, key: ObjectKey, object: Object<'static>) {
+        for index_key in self.inverse_entries(&object) {
+            self.inverse
+                .entry(index_key)
+                .or_default()
+                .insert(key.clone());
+        }
+        self.objects.insert(key, object);
+    }
+
+    /// The entries of the inverse index referencing the object.
+    fn inverse_entries(&self, object: &Object) -> Vec<(String, String)> {
+        self.inverse_keys
+            .iter()
+            .flat_map(|attribute| {
+                object
+                    .list(attribute)
+                    .into_iter()
+                    .map(|value| (attribute.clone(), normalize(value)))
+            })
+            .collect()
+    }
+
+    fn reindex(&mut self) {
+        self.inverse.clear();
+        let objects = std::mem::take(&mut self.objects);
+        for (key, object) in objects {
+            self.store(key, object);
+        }
+    }
+}
+
+impl Default for IrrDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Normalize a key value, using the canonical form of AS numbers, address prefixes and set
+/// names and ignoring ASCII case otherwise.
+fn normalize(value: &str) -> String {
+    let value = value.trim();
+    let has_as_prefix = value
+        .get(..2)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("AS"));
+    if has_as_prefix {
+        if let Ok(asn) = value.parse::<Asn>() {
+            return asn.to_string();
+        }
+    }
+    if let Ok(prefix) = value.parse::<AddressPrefix>() {
+        return prefix.to_string();
+    }
+    if let Ok(name) = value.parse::<SetName>() {
+        return name.normalized().to_string();
+    }
+    value.to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::object;
+
+    fn database() -> IrrDatabase {
+        let mut database = IrrDatabase::new();
+        for object in [
+            object! {
+                "mntner": "MAINT-EXAMPLE";
+                "admin-c": "RPSL1-RIPE";
+                "mnt-by": "MAINT-EXAMPLE";
+                "source": "RIPE";
+            },
+            object! {
+                "person": "John Doe";
+                "nic-hdl": "RPSL1-RIPE";
+                "mnt-by": "MAINT-EXAMPLE";
+                "source": "RIPE";
+            },
+            object! {
+                "route": "192.0.2.0/24";
+                "origin": "AS65536";
+                "member-of": "RS-EXAMPLE";
+                "mnt-by": "MAINT-EXAMPLE", "MAINT-OTHER";
+                "source": "RIPE";
+            },
+            object! {
+                "route": "192.0.2.0/24";
+                "origin": "AS65537";
+                "mnt-by": "MAINT-OTHER";
+                "source": "RIPE";
+            },
+            object! {
+                "route6": "2001:db8::/32";
+                "origin": "AS65536";
+                "mnt-by": "MAINT-EXAMPLE";
+                "source": "RIPE";
+            },
+        ] {
+            database.insert(object).unwrap();
+        }
+        database
+    }
+
+    #[rstest]
+    #[case(ObjectKey::new("mntner", ["maint-example"]))]
+    #[case(ObjectKey::new("PERSON", ["rpsl1-ripe"]))]
+    #[case(ObjectKey::new("route", ["192.0.2.0/24", "AS65536"]))]
+    #[case(ObjectKey::new("route", ["192.0.2.0/24", "AS1.1"]))]
+    #[case(ObjectKey::new("route6", ["2001:DB8:0::/32", "as65536"]))]
+    fn objects_are_found_by_primary_key(#[case] key: ObjectKey) {
+        let database = database();
+        let object = database.get(&key).unwrap();
+        assert_eq!(database.key_of(object).unwrap(), key);
+    }
+
+    #[rstest]
+    #[case("mnt-by", "MAINT-EXAMPLE", vec!["MAINT-EXAMPLE", "John Doe", "192.0.2.0/24", "2001:db8::/32"])]
+    #[case("mnt-by", "maint-other", vec!["192.0.2.0/24", "192.0.2.0/24"])]
+    #[case("origin", "AS65536", vec!["192.0.2.0/24", "2001:db8::/32"])]
+    #[case("member-of", "rs-example", vec!["192.0.2.0/24"])]
+    #[case("admin-c", "RPSL1-RIPE", vec!["MAINT-EXAMPLE"])]
+    #[case("tech-c", "RPSL1-RIPE", vec![])]
+    #[case("origin", "AS1", vec![])]
+    fn objects_are_found_by_inverse_key(
+        #[case] attribute: &str,
+        #[case] value: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let database = database();
+        let found: Vec<&str> = database
+            .inverse(attribute, value)
+            .map(|object| object[0].value.with_content()[0])
+            .collect();
+        assert_eq!(found.len(), expected.len());
+        assert!(expected.iter().all(|value| found.contains(value)));
+    }
+
+    #[test]
+    fn objects_of_class() {
+        let database = database();
+        assert_eq!(database.objects_of_class("route").count(), 2);
+        assert_eq!(database.objects_of_class("Route6").count(), 1);
+        assert_eq!(database.objects_of_class("aut-num").count(), 0);
+        assert_eq!(database.len(), 5);
+    }
+
+    #[test]
+    fn insert_duplicate_is_err() {
+        let mut database = database();
+        let err = database
+            .insert(object! {
+                "mntner": "maint-example";
+                "mnt-by": "MAINT-OTHER";
+            })
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DatabaseError::DuplicateObject(ObjectKey::new("mntner", ["MAINT-EXAMPLE"]))
+        );
+        assert_eq!(database.inverse("mnt-by", "MAINT-OTHER").count(), 2);
+    }
+
+    #[rstest]
+    #[case(Object::new(vec![]), DatabaseError::EmptyObject)]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "mnt-by": "MAINT-EXAMPLE";
+        },
+        DatabaseError::MissingPrimaryKey {
+            class: "route".to_string(),
+            attribute: "origin".to_string(),
+        }
+    )]
+    fn object_without_key_is_err(#[case] object: Object, #[case] expected: DatabaseError) {
+        assert_eq!(IrrDatabase::new().insert(object), Err(expected));
+    }
+
+    #[test]
+    fn unknown_class_is_keyed_by_first_attribute() {
+        let mut database = IrrDatabase::new();
+        let key = database
+            .insert(object! {
+                "peering-policy": "Example";
+                "mnt-by": "MAINT-EXAMPLE";
+            })
+            .unwrap();
+        assert_eq!(key, ObjectKey::new("peering-policy", ["EXAMPLE"]));
+    }
+
+    #[test]
+    fn delete_removes_inverse_keys() {
+        let mut database = database();
+        let key = ObjectKey::new("route", ["192.0.2.0/24", "AS65536"]);
+
+        let deleted = database.delete(&key).unwrap();
+        assert_eq!(deleted.get("origin"), vec!["AS65536"]);
+        assert!(!database.contains(&key));
+        assert!(database.delete(&key).is_none());
+        assert_eq!(database.inverse("origin", "AS65536").count(), 1);
+        assert_eq!(database.inverse("member-of", "RS-EXAMPLE").count(), 0);
+        assert_eq!(database.len(), 4);
+    }
+
+    #[test]
+    fn replace_updates_inverse_keys() {
+        let mut database = database();
+        let previous = database
+            .replace(object! {
+                "route": "192.0.2.0/24";
+                "origin": "AS65536";
+                "mnt-by": "MAINT-THIRD";
+                "source": "RIPE";
+            })
+            .unwrap();
+
+        assert!(previous.is_some());
+        assert_eq!(database.inverse("mnt-by", "MAINT-OTHER").count(), 1);
+        assert_eq!(database.inverse("mnt-by", "MAINT-THIRD").count(), 1);
+        assert_eq!(database.inverse("member-of", "RS-EXAMPLE").count(), 0);
+        assert_eq!(database.len(), 5);
+    }
+}
@@ -1,7 +1,9 @@
-use std::fmt;
+use std::{fmt, io, ops::Range};
 
 use thiserror::Error;
 
+use crate::{database::ObjectKey, generate::Platform, AddressPrefix, RangeOperator, SetName};
+
 #[derive(Error, Debug)]
 pub enum InvalidNameError {
     #[error("cannot be empty")]
@@ -20,6 +22,8 @@ pub enum InvalidValueError {
     NonExtendedAscii,
     #[error("cannot contain ASCII control characters")]
     ContainsControlChar,
+    #[error("cannot contain the \"#\" character, since it starts a comment")]
+    ContainsCommentChar,
 }
 
 #[derive(Error, Debug)]
@@ -33,11 +37,131 @@ pub enum AttributeError {
     InvalidValue(#[from] InvalidValueError),
 }
 
+/// An error that can occur when converting an [`Object`](crate::Object) into a typed RPSL class.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ClassError {
+    /// The object is of a different class.
+    #[error("expected an object of class `{expected}`, found `{found}`")]
+    WrongClass {
+        /// The class that was expected.
+        expected: &'static str,
+        /// The class of the object, empty if the object has no attributes.
+        found: String,
+    },
+    /// The object is missing attributes that are mandatory for its class.
+    #[error("missing mandatory attribute(s): {}", .0.join(", "))]
+    MissingAttributes(Vec<&'static str>),
+    /// An attribute identifying the object contains an invalid value.
+    #[error("invalid value `{value}` of attribute `{name}`")]
+    InvalidValue {
+        /// The name of the attribute.
+        name: &'static str,
+        /// The invalid value.
+        value: String,
+    },
+}
+
+/// An error that can occur when parsing or creating an address prefix, range operator or
+/// prefix range.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PrefixError {
+    /// The text is not a valid IPv4 or IPv6 address prefix.
+    #[error("invalid address prefix `{0}`")]
+    InvalidPrefix(String),
+    /// The address of the prefix has bits set that are not covered by its length.
+    #[error("address prefix `{0}` has host bits set")]
+    HostBitsSet(String),
+    /// The text is not a valid range operator.
+    #[error("invalid range operator `{0}`")]
+    InvalidRangeOperator(String),
+    /// The range operator selects no more specifics of the prefix it is applied to.
+    #[error("range operator `{operator}` cannot be applied to `{prefix}`")]
+    InvalidRange {
+        /// The prefix the operator is applied to.
+        prefix: AddressPrefix,
+        /// The range operator.
+        operator: RangeOperator,
+    },
+}
+
+/// An error that can occur when parsing an AS number.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid AS number `{0}`")]
+pub struct AsnError(pub(crate) String);
+
+/// An error that can occur when parsing the name of a set.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SetNameError {
+    /// A component of the name is neither an AS number nor a set name.
+    #[error("invalid component `{component}` in set name `{name}`")]
+    InvalidComponent {
+        /// The name of the set.
+        name: String,
+        /// The invalid component.
+        component: String,
+    },
+    /// The name only consists of AS numbers.
+    #[error("set name `{0}` does not contain a set component")]
+    MissingSetComponent(String),
+    /// The name contains set components of different classes, e.g. `AS-FOO:RS-BAR`.
+    #[error("set name `{0}` mixes components of different set classes")]
+    MixedClasses(String),
+}
+
+/// An error that can occur when storing an object in an
+/// [`IrrDatabase`](crate::database::IrrDatabase).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DatabaseError {
+    /// The object does not contain any attributes.
+    #[error("object does not contain any attributes")]
+    EmptyObject,
+    /// The object does not contain an attribute that is part of the primary key of its class.
+    #[error("object of class `{class}` is missing primary key attribute `{attribute}`")]
+    MissingPrimaryKey {
+        /// The class of the object.
+        class: String,
+        /// The name of the missing attribute.
+        attribute: String,
+    },
+    /// An object with the same key is already contained in the database.
+    #[error("object `{0}` already exists")]
+    DuplicateObject(ObjectKey),
+}
+
+/// An error that can occur when generating router configuration using the
+/// [`generate`](crate::generate) module.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+    /// The configuration cannot be generated for the platform.
+    #[error("generating this configuration is not supported for {0}")]
+    UnsupportedPlatform(Platform),
+    /// The AS path regular expression uses a construct the platform cannot express.
+    #[error("AS path regular expression `{regex}` cannot be expressed for {platform}: {reason}")]
+    UnsupportedRegex {
+        /// The AS path regular expression.
+        regex: String,
+        /// The platform the configuration is generated for.
+        platform: Platform,
+        /// The construct that cannot be expressed.
+        reason: String,
+    },
+    /// The members of a set referenced by an AS path regular expression are unknown.
+    #[error("members of set `{0}` are unknown")]
+    UnresolvedSet(SetName),
+    /// An AS path regular expression references `PeerAS`, but the peer AS number is unknown.
+    #[error("AS path regular expression references `PeerAS`, but the peer AS number is unknown")]
+    UnknownPeerAs,
+}
+
 /// An error that can occur when parsing RPSL text.
 ///
+/// The error points to the position in the text at which parsing failed, given as a line and
+/// column as well as a byte offset, and describes the problem found there by its
+/// [`ParseErrorKind`].
+///
 /// # Example
 /// ```
-/// # use rpsl::parse_object;
+/// # use rpsl::{parse_object, ParseErrorKind};
 /// let rpsl = "\
 /// role;        ACME Company
 ///
@@ -51,18 +175,314 @@ pub enum AttributeError {
 /// invalid separator
 /// expected `:`";
 /// assert_eq!(err.to_string(), message);
+/// assert_eq!(err.kind(), ParseErrorKind::InvalidSeparator);
+/// assert_eq!((err.line(), err.column(), err.offset()), (1, 5, 4));
+/// assert_eq!(err.attribute(), Some("role"));
 /// ```
-#[derive(Error, Debug)]
-pub struct ParseError(String);
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    span: Range<usize>,
+    line: usize,
+    column: usize,
+    attribute: Option<String>,
+    /// The content of the line containing the error, used for display.
+    content: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(
+        kind: ParseErrorKind,
+        input: &str,
+        span: Range<usize>,
+        attribute: Option<&str>,
+    ) -> Self {
+        let (line, column) = position(input, span.start);
+        let line_start = input[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[span.start..]
+            .find('\n')
+            .map_or(input.len(), |i| span.start + i);
+        Self {
+            kind,
+            line,
+            column,
+            content: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            span,
+            attribute: attribute.map(ToString::to_string),
+        }
+    }
+
+    /// Move the position of the error, for an error found in text that starts at the given byte
+    /// offset and line of a larger text.
+    pub(crate) fn shift(mut self, offset: usize, lines: usize) -> Self {
+        self.span = self.span.start + offset..self.span.end + offset;
+        self.line += lines;
+        self
+    }
+
+    /// The kind of problem found.
+    #[must_use]
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// The line of the error, starting at 1.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the error within its line in characters, starting at 1.
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The byte offset of the error within the text.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.span.start
+    }
+
+    /// The byte range of the text causing the error, which is empty if something is missing.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The name of the attribute containing the error, if it is known.
+    #[must_use]
+    pub fn attribute(&self) -> Option<&str> {
+        self.attribute.as_deref()
+    }
+}
 
 impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "parse error at line {}, column {}",
+            self.line, self.column
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.content)?;
+        let column_offset = self
+            .content
+            .char_indices()
+            .nth(self.column - 1)
+            .map_or(self.content.len(), |(i, _)| i);
+        // At least one marker, even for an empty span.
+        let markers = self.content[column_offset..]
+            .char_indices()
+            .take_while(|(i, _)| *i < self.span.len())
+            .count()
+            .max(1);
+        writeln!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(markers)
+        )?;
+        write!(f, "{}", self.kind)?;
+        if self.kind == ParseErrorKind::InvalidSeparator {
+            write!(f, "\nexpected `:`")?;
+        }
+        Ok(())
+    }
+}
+
+/// The kind of problem causing a [`ParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// An attribute name is not followed by a `:` separator.
+    InvalidSeparator,
+    /// An attribute name is invalid, e.g. because it does not start with a letter.
+    InvalidName,
+    /// A value contains a character that is not part of the extended ASCII set.
+    NonExtendedAsciiValue,
+    /// A value contains an ASCII control character.
+    ControlCharacter,
+    /// A line or an object is not terminated by a newline.
+    MissingNewline,
+    /// Text follows where the end of the input is expected, e.g. a second object passed to
+    /// [`parse_object`](crate::parse_object).
+    UnexpectedContent,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Self::InvalidSeparator => "invalid separator",
+            Self::InvalidName => "invalid attribute name",
+            Self::NonExtendedAsciiValue => {
+                "value contains a character that is not part of the extended ASCII set"
+            }
+            Self::ControlCharacter => "value contains an ASCII control character",
+            Self::MissingNewline => "missing terminating newline",
+            Self::UnexpectedContent => "unexpected content",
+        };
+        write!(f, "{description}")
+    }
+}
+
+/// The line and the column in characters of the byte offset within the input, starting at 1.
+fn position(input: &str, offset: usize) -> (usize, usize) {
+    let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+    (
+        input[..offset].matches('\n').count() + 1,
+        input[line_start..offset].chars().count() + 1,
+    )
+}
+
+/// A deviation from RPSL that was accepted by a lenient parser.
+///
+/// Created by parsing with [`ParserOptions::lenient`](crate::ParserOptions::lenient).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    kind: ParseWarningKind,
+    span: Range<usize>,
+    line: usize,
+    column: usize,
+}
+
+impl ParseWarning {
+    pub(crate) fn new(kind: ParseWarningKind, input: &str, span: Range<usize>) -> Self {
+        let (line, column) = position(input, span.start);
+        Self {
+            kind,
+            span,
+            line,
+            column,
+        }
+    }
+
+    /// Move the position of the warning, for a deviation found in text that starts at the given
+    /// byte offset and line of a larger text.
+    pub(crate) fn shift(mut self, offset: usize, lines: usize) -> Self {
+        self.span = self.span.start + offset..self.span.end + offset;
+        self.line += lines;
+        self
+    }
+
+    /// The kind of deviation found.
+    #[must_use]
+    pub fn kind(&self) -> ParseWarningKind {
+        self.kind
+    }
+
+    /// The line of the deviation, starting at 1.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the deviation within its line in characters, starting at 1.
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The byte offset of the deviation within the text.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.span.start
+    }
+
+    /// The byte range of the text deviating from RPSL, which is empty if something is missing.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+/// The kind of deviation from RPSL reported by a [`ParseWarning`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseWarningKind {
+    /// A line ends with `\r\n` instead of `\n`.
+    CrlfLineEnding,
+    /// A value is followed by spaces or tabs, which are not part of the parsed value.
+    TrailingWhitespace,
+    /// The separator following an attribute name contains a tab.
+    TabSeparator,
+    /// The last object is not terminated by a blank line.
+    MissingBlankLine,
+    /// A value contains a character that is not part of the extended ASCII set, e.g. UTF-8
+    /// in a `descr` attribute.
+    NonExtendedAsciiValue,
+}
+
+impl fmt::Display for ParseWarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Self::CrlfLineEnding => "line ends with CRLF",
+            Self::TrailingWhitespace => "value is followed by whitespace",
+            Self::TabSeparator => "separator contains a tab",
+            Self::MissingBlankLine => "object is not terminated by a blank line",
+            Self::NonExtendedAsciiValue => {
+                "value contains a character that is not part of the extended ASCII set"
+            }
+        };
+        write!(f, "{description}")
+    }
+}
+
+/// An error that can occur when reading RPSL from a [`BufRead`](std::io::BufRead) source.
+#[derive(Error, Debug)]
+pub enum ReadError {
+    /// Reading from the underlying source failed.
+    #[error("failed to read RPSL: {0}")]
+    Io(#[from] io::Error),
+    /// The RPSL that was read is invalid.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// An error that can occur when converting between Rust data structures and RPSL using
+/// [`to_string`](crate::to_string) or [`from_str`](crate::from_str).
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Error, Debug)]
+pub enum SerdeError {
+    /// The RPSL to deserialize from is invalid.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    /// A serialized name or value is not a valid attribute name or value.
+    #[error(transparent)]
+    Attribute(#[from] AttributeError),
+    /// The data structure cannot be represented as RPSL, e.g. a nested struct.
+    #[error("{0} cannot be represented as RPSL")]
+    Unsupported(&'static str),
+    /// A custom error reported by a `Serialize` or `Deserialize` implementation, e.g. for a
+    /// missing attribute.
+    #[error("{0}")]
+    Custom(String),
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
     }
 }
 
-impl From<winnow::error::ParseError<&str, winnow::error::ContextError>> for ParseError {
-    fn from(value: winnow::error::ParseError<&str, winnow::error::ContextError>) -> Self {
-        Self(value.to_string())
+#[cfg(feature = "serde")]
+impl serde::de::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
     }
 }
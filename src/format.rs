@@ -0,0 +1,620 @@
+//! Formatting of RPSL objects using a configurable layout.
+//!
+//! Objects and attributes are displayed in the layout used by the RIPE database, with values
+//! starting at column 16 and continuation lines starting with a space, unless they were parsed
+//! from RPSL and have not been changed since. A [`Formatter`] instead lays out every attribute
+//! according to its [`FormatOptions`], regardless of how the object was created:
+//! - The [`Alignment`] of values, either at a fixed column or after the longest attribute name.
+//! - The [`Continuation`] character starting continuation lines.
+//! - Wrapping of long `descr` and `remarks` values into continuation lines.
+//! - Ordering of attributes as listed in the template of the object's class.
+//! - Trimming of empty continuation lines.
+//!
+//! Comments are kept and follow the line they were written on.
+//!
+//! # Example
+//! ```
+//! # use rpsl::{format::{Alignment, Continuation, FormatOptions, Formatter}, parse_object};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let object = parse_object("
+//! role: ACME Company
+//! address: Packet Street 6
+//! +
+//! nic-hdl: RPSL1-RIPE  # primary handle
+//!
+//! ")?;
+//!
+//! let formatter = Formatter::new(
+//!     FormatOptions::default()
+//!         .with_alignment(Alignment::Auto)
+//!         .with_continuation(Continuation::Plus),
+//! );
+//! assert_eq!(
+//!     formatter.format(&object),
+//!     concat!(
+//!         "role:    ACME Company\n",
+//!         "address: Packet Street 6\n",
+//!         "+\n",
+//!         "nic-hdl: RPSL1-RIPE # primary handle\n",
+//!         "\n",
+//!     )
+//! );
+//! # Ok(())
+//! # }
+//! ```
+use crate::{schema::Schema, Attribute, Object};
+
+/// The attributes whose values are wrapped, if wrapping is enabled.
+const WRAPPED_ATTRIBUTES: [&str; 2] = ["descr", "remarks"];
+
+/// The column at which the values of attributes start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    /// Start values at the given column, counted from zero.
+    ///
+    /// Values of attributes whose name does not fit before the column directly follow the name,
+    /// unless [`FormatOptions::with_space_after_long_names`] is used.
+    Column(usize),
+    /// Start values one space after the longest attribute name of the object.
+    Auto,
+}
+
+impl Default for Alignment {
+    /// Start values at column 16, as done by the RIPE database.
+    fn default() -> Self {
+        Self::Column(16)
+    }
+}
+
+/// The character starting the continuation lines of a multi-line value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Continuation {
+    /// Start continuation lines with a space.
+    #[default]
+    Space,
+    /// Start continuation lines with a `+`.
+    Plus,
+    /// Start empty continuation lines with a `+` and others with a space.
+    ///
+    /// Unlike a line only containing a space, such a line cannot be mistaken for the blank
+    /// line ending an object once trailing whitespace is stripped.
+    PlusIfEmpty,
+}
+
+/// Options controlling the layout of RPSL written by a [`Formatter`].
+///
+/// The default options lay out attributes like their [`Display`](std::fmt::Display)
+/// implementation, without wrapping, reordering or trimming.
+///
+/// # Example
+/// ```
+/// # use rpsl::{format::{FormatOptions, Formatter}, object, schema::Schema};
+/// let route = object! {
+///     "route": "192.0.2.0/24";
+///     "source": "RIPE";
+///     "descr": "Documentation prefix assigned by RFC 5737 for use in examples";
+///     "origin": "AS65536";
+/// };
+///
+/// let options = FormatOptions::default()
+///     .with_wrapping(40)
+///     .with_template_order(Schema::builtin());
+/// assert_eq!(
+///     Formatter::new(options).format(&route),
+///     concat!(
+///         "route:          192.0.2.0/24\n",
+///         "descr:          Documentation prefix\n",
+///         "                assigned by RFC 5737 for\n",
+///         "                use in examples\n",
+///         "origin:         AS65536\n",
+///         "source:         RIPE\n",
+///         "\n",
+///     )
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptions {
+    alignment: Alignment,
+    continuation: Continuation,
+    wrap_width: Option<usize>,
+    template_order: Option<Schema>,
+    trim_blank_lines: bool,
+    space_after_long_names: bool,
+}
+
+impl FormatOptions {
+    /// Align values as given.
+    #[must_use]
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Start continuation lines with the given character.
+    #[must_use]
+    pub fn with_continuation(mut self, continuation: Continuation) -> Self {
+        self.continuation = continuation;
+        self
+    }
+
+    /// Wrap lines of `descr` and `remarks` values longer than the given width at spaces.
+    ///
+    /// The width is counted in characters, including the name or indentation preceding the
+    /// value but not any comment following it. Words longer than the available width are
+    /// kept on a line of their own.
+    #[must_use]
+    pub fn with_wrapping(mut self, width: usize) -> Self {
+        self.wrap_width = Some(width);
+        self
+    }
+
+    /// Order attributes as listed in the template of the object's class in the given schema.
+    ///
+    /// Attributes of the same name keep their relative order, attributes not listed in the
+    /// template follow all others and objects of classes missing from the schema are not
+    /// reordered.
+    #[must_use]
+    pub fn with_template_order(mut self, schema: Schema) -> Self {
+        self.template_order = Some(schema);
+        self
+    }
+
+    /// Separate values from names that do not fit before the column of an [`Alignment::Column`]
+    /// by a space, instead of having the value directly follow the name.
+    #[must_use]
+    pub fn with_space_after_long_names(mut self) -> Self {
+        self.space_after_long_names = true;
+        self
+    }
+
+    /// Remove empty continuation lines at the end of values and join consecutive empty
+    /// continuation lines into one.
+    ///
+    /// Lines with a comment following the empty value are kept, while comments taking up a line
+    /// of their own move to the preceding line.
+    #[must_use]
+    pub fn with_blank_line_trimming(mut self) -> Self {
+        self.trim_blank_lines = true;
+        self
+    }
+}
+
+/// Writes RPSL objects and attributes in the layout configured by [`FormatOptions`].
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone, Default)]
+pub struct Formatter {
+    options: FormatOptions,
+}
+
+impl Formatter {
+    /// Create a formatter using the given options.
+    #[must_use]
+    pub fn new(options: FormatOptions) -> Self {
+        Self { options }
+    }
+
+    /// The options used by the formatter.
+    #[must_use]
+    pub fn options(&self) -> &FormatOptions {
+        &self.options
+    }
+
+    /// Format an object as RPSL, followed by the blank line ending it.
+    #[must_use]
+    pub fn format(&self, object: &Object) -> String {
+        let mut attributes: Vec<&Attribute> = object.iter().collect();
+        let template = self
+            .options
+            .template_order
+            .as_ref()
+            .zip(object.class())
+            .and_then(|(schema, class)| schema.get(class));
+        if let Some(template) = template {
+            attributes.sort_by_key(|attribute| {
+                template
+                    .attributes()
+                    .iter()
+                    .position(|t| t.name().eq_ignore_ascii_case(&attribute.name))
+                    .unwrap_or(usize::MAX)
+            });
+        }
+
+        let column = self.column(attributes.iter().map(|attribute| attribute.name.len()));
+        let mut rpsl = String::new();
+        for attribute in attributes {
+            self.write_attribute(&mut rpsl, attribute, column);
+        }
+        rpsl.push('\n');
+        rpsl
+    }
+
+    /// Format a single attribute as RPSL.
+    ///
+    /// Using [`Alignment::Auto`], the value starts one space after the name of the attribute.
+    #[must_use]
+    pub fn format_attribute(&self, attribute: &Attribute) -> String {
+        let mut rpsl = String::new();
+        let column = self.column(std::iter::once(attribute.name.len()));
+        self.write_attribute(&mut rpsl, attribute, column);
+        rpsl
+    }
+
+    /// The column at which values start, given the length of the names of all attributes.
+    fn column(&self, name_lengths: impl Iterator<Item = usize>) -> usize {
+        match self.options.alignment {
+            Alignment::Column(column) => column,
+            Alignment::Auto => name_lengths.max().map_or(0, |length| length + 2),
+        }
+    }
+
+    fn write_attribute(&self, rpsl: &mut String, attribute: &Attribute, column: usize) {
+        let name: &str = &attribute.name;
+        let first_column = if self.options.space_after_long_names {
+            column.max(name.len() + 2)
+        } else {
+            column
+        };
+        let continuation_column = column.max(1);
+
+        let mut lines = Line::from_attribute(attribute);
+        if self.options.trim_blank_lines {
+            lines = trim(lines);
+        }
+        if let Some(width) = self.options.wrap_width {
+            if WRAPPED_ATTRIBUTES
+                .iter()
+                .any(|wrapped| wrapped.eq_ignore_ascii_case(name))
+            {
+                lines = wrap(
+                    lines,
+                    width.saturating_sub(first_column),
+                    width.saturating_sub(continuation_column),
+                );
+            }
+        }
+
+        for (i, line) in lines.iter().enumerate() {
+            let start = rpsl.len();
+            let column = if i == 0 {
+                rpsl.push_str(name);
+                rpsl.push(':');
+                first_column
+            } else {
+                rpsl.push(match (self.options.continuation, &line.value) {
+                    (Continuation::Plus, _) | (Continuation::PlusIfEmpty, None) => '+',
+                    _ => ' ',
+                });
+                continuation_column
+            };
+            match (&line.value, line.inline_comment) {
+                (Some(value), comment) => {
+                    pad(rpsl, start, column);
+                    rpsl.push_str(value);
+                    if let Some(comment) = comment {
+                        rpsl.push_str(" #");
+                        rpsl.push_str(comment);
+                    }
+                }
+                (None, Some(comment)) => {
+                    pad(rpsl, start, column);
+                    rpsl.push('#');
+                    rpsl.push_str(comment);
+                }
+                (None, None) => {}
+            }
+            rpsl.push('\n');
+
+            for comment in &line.comments {
+                rpsl.push('#');
+                rpsl.push_str(comment);
+                rpsl.push('\n');
+            }
+        }
+    }
+}
+
+/// Pad the line starting at the given position with spaces up to the column.
+fn pad(rpsl: &mut String, start: usize, column: usize) {
+    let length = rpsl[start..].chars().count();
+    rpsl.extend(std::iter::repeat(' ').take(column.saturating_sub(length)));
+}
+
+/// A line of an attribute to be written, along with its comments.
+#[derive(Debug)]
+struct Line<'a> {
+    value: Option<&'a str>,
+    inline_comment: Option<&'a str>,
+    /// The comments taking up a line of their own after this line.
+    comments: Vec<&'a str>,
+}
+
+impl<'a> Line<'a> {
+    fn from_attribute(attribute: &'a Attribute) -> Vec<Self> {
+        let mut lines: Vec<Self> = attribute
+            .value
+            .values()
+            .into_iter()
+            .map(|value| Self {
+                value,
+                inline_comment: None,
+                comments: Vec::new(),
+            })
+            .collect();
+        for comment in attribute.comments() {
+            let Some(line) = lines.get_mut(comment.line()) else {
+                continue;
+            };
+            if comment.is_own_line() {
+                line.comments.push(comment.text());
+            } else {
+                line.inline_comment = Some(comment.text());
+            }
+        }
+        lines
+    }
+
+    /// Whether the line has neither a value nor a comment following it.
+    fn is_blank(&self) -> bool {
+        self.value.is_none() && self.inline_comment.is_none()
+    }
+}
+
+/// Remove blank lines at the end and blank lines following an empty line, keeping the first line.
+fn trim(lines: Vec<Line<'_>>) -> Vec<Line<'_>> {
+    let mut kept: Vec<Line> = Vec::with_capacity(lines.len());
+    for line in lines {
+        match kept.last_mut() {
+            Some(last) if line.is_blank() && last.value.is_none() => {
+                last.comments.extend(line.comments);
+            }
+            _ => kept.push(line),
+        }
+    }
+    while kept.len() > 1 && kept.last().is_some_and(Line::is_blank) {
+        if let Some(line) = kept.pop() {
+            if let Some(last) = kept.last_mut() {
+                last.comments.extend(line.comments);
+            }
+        }
+    }
+    kept
+}
+
+/// Wrap values longer than the available width, given for the first and all other lines.
+///
+/// Lines are only wrapped at spaces, keeping any other spaces within the value as they are.
+/// The comments of a wrapped line follow the last line it is wrapped into.
+fn wrap(lines: Vec<Line<'_>>, first_width: usize, width: usize) -> Vec<Line<'_>> {
+    let mut wrapped: Vec<Line> = Vec::with_capacity(lines.len());
+    for line in lines {
+        let available = if wrapped.is_empty() {
+            first_width
+        } else {
+            width
+        };
+        let Some(value) = line.value.filter(|value| value.chars().count() > available) else {
+            wrapped.push(line);
+            continue;
+        };
+
+        let mut pieces: Vec<&str> = Vec::new();
+        // The start of the current piece and the end of its last word.
+        let mut start = 0;
+        let mut end = None;
+        let mut word_start = 0;
+        for word in value.split(' ') {
+            let word_end = word_start + word.len();
+            if !word.is_empty() {
+                let available = if wrapped.is_empty() && pieces.is_empty() {
+                    first_width
+                } else {
+                    width
+                };
+                if let Some(end) =
+                    end.filter(|_| value[start..word_end].chars().count() > available)
+                {
+                    pieces.push(&value[start..end]);
+                    start = word_start;
+                }
+                end = Some(word_end);
+            }
+            word_start = word_end + 1;
+        }
+        pieces.push(&value[start..]);
+
+        let last = pieces.len() - 1;
+        let mut comments = Some((line.inline_comment, line.comments));
+        for (i, piece) in pieces.into_iter().enumerate() {
+            let (inline_comment, comments) = if i == last {
+                comments.take().unwrap_or_default()
+            } else {
+                (None, Vec::new())
+            };
+            wrapped.push(Line {
+                value: Some(piece),
+                inline_comment,
+                comments,
+            });
+        }
+    }
+    wrapped
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::{object, parse_object};
+
+    #[rstest]
+    #[case(FormatOptions::default(), "remarks:        A\n                B\n \n")]
+    #[case(
+        FormatOptions::default().with_alignment(Alignment::Column(10)),
+        "remarks:  A\n          B\n \n"
+    )]
+    #[case(
+        FormatOptions::default().with_alignment(Alignment::Column(0)),
+        "remarks:A\n B\n \n"
+    )]
+    #[case(
+        FormatOptions::default()
+            .with_alignment(Alignment::Column(0))
+            .with_space_after_long_names(),
+        "remarks: A\n B\n \n"
+    )]
+    #[case(
+        FormatOptions::default().with_alignment(Alignment::Auto),
+        "remarks: A\n         B\n \n"
+    )]
+    #[case(
+        FormatOptions::default().with_continuation(Continuation::Plus),
+        "remarks:        A\n+               B\n+\n"
+    )]
+    #[case(
+        FormatOptions::default().with_continuation(Continuation::PlusIfEmpty),
+        "remarks:        A\n                B\n+\n"
+    )]
+    #[case(
+        FormatOptions::default().with_blank_line_trimming(),
+        "remarks:        A\n                B\n"
+    )]
+    fn format_attribute_with_options(#[case] options: FormatOptions, #[case] expected: &str) {
+        let attribute = Attribute::unchecked_multi("remarks", vec!["A", "B", ""]);
+        assert_eq!(
+            Formatter::new(options).format_attribute(&attribute),
+            expected
+        );
+    }
+
+    #[test]
+    fn default_formatter_formats_like_display() {
+        let attribute =
+            Attribute::unchecked_multi("address", vec!["Packet Street 6", "", "Internet"]);
+        assert_eq!(
+            Formatter::default().format_attribute(&attribute),
+            attribute.to_string()
+        );
+    }
+
+    #[rstest]
+    #[case(FormatOptions::default(), "administrative-contact:A\n")]
+    #[case(
+        FormatOptions::default().with_space_after_long_names(),
+        "administrative-contact: A\n"
+    )]
+    fn long_names_are_followed_by_the_value(
+        #[case] options: FormatOptions,
+        #[case] expected: &str,
+    ) {
+        let attribute = Attribute::unchecked_single("administrative-contact", "A");
+        assert_eq!(
+            Formatter::new(options).format_attribute(&attribute),
+            expected
+        );
+    }
+
+    #[test]
+    fn auto_alignment_uses_longest_name() {
+        let object = object! {
+            "mntner": "MAINT-EXAMPLE";
+            "remarks": "A", "B";
+            "source": "RIPE";
+        };
+        let formatter = Formatter::new(FormatOptions::default().with_alignment(Alignment::Auto));
+        assert_eq!(
+            formatter.format(&object),
+            "mntner:  MAINT-EXAMPLE\nremarks: A\n         B\nsource:  RIPE\n\n"
+        );
+    }
+
+    #[rstest]
+    #[case("remarks: A\n \n \n+\n\n", "remarks:        A\n")]
+    #[case(
+        "remarks: A\n \n \n B\n \n\n",
+        "remarks:        A\n \n                B\n"
+    )]
+    #[case("remarks:\n \n B\n\n", "remarks:\n                B\n")]
+    #[case("remarks: A\n# C\n \n# D\n \n\n", "remarks:        A\n# C\n# D\n")]
+    #[case("remarks: A\n+ # C\n \n\n", "remarks:        A\n                # C\n")]
+    fn blank_lines_are_trimmed(#[case] rpsl: &str, #[case] expected: &str) {
+        let object = parse_object(rpsl).unwrap();
+        let formatter = Formatter::new(FormatOptions::default().with_blank_line_trimming());
+        assert_eq!(formatter.format_attribute(&object[0]), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "descr: A B C D E F\n\n",
+        24,
+        "descr:          A B C D\n                E F\n"
+    )]
+    #[case(
+        "descr: A B C D E F\n\n",
+        20,
+        "descr:          A B\n                C D\n                E F\n"
+    )]
+    #[case("remarks: A  B\n\n", 10, "remarks:        A\n                B\n")]
+    #[case(
+        "remarks: ABCDEF GH\n\n",
+        20,
+        "remarks:        ABCDEF\n                GH\n"
+    )]
+    #[case("remarks: A B\n\n", 19, "remarks:        A B\n")]
+    #[case(
+        "remarks: A  B   C D  \n\n",
+        24,
+        "remarks:        A  B   C\n                D  \n"
+    )]
+    #[case("address: A B C D E F\n\n", 20, "address:        A B C D E F\n")]
+    #[case(
+        "descr: A B C # D\n# E\n\n",
+        20,
+        "descr:          A B\n                C # D\n# E\n"
+    )]
+    fn long_values_are_wrapped(#[case] rpsl: &str, #[case] width: usize, #[case] expected: &str) {
+        let object = parse_object(rpsl).unwrap();
+        let formatter = Formatter::new(FormatOptions::default().with_wrapping(width));
+        assert_eq!(formatter.format_attribute(&object[0]), expected);
+    }
+
+    #[rstest]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "source": "RIPE";
+            "remarks": "A";
+            "x-custom": "B";
+            "origin": "AS65536";
+            "remarks": "C";
+        },
+        &["route", "origin", "remarks", "remarks", "source", "x-custom"]
+    )]
+    #[case(
+        object! {
+            "x-class": "A";
+            "source": "RIPE";
+            "descr": "B";
+        },
+        &["x-class", "source", "descr"]
+    )]
+    fn attributes_are_ordered_by_template(#[case] object: Object, #[case] expected: &[&str]) {
+        let formatter =
+            Formatter::new(FormatOptions::default().with_template_order(Schema::builtin()));
+        let formatted = formatter.format(&object);
+        let reordered = parse_object(&formatted).unwrap();
+        let names: Vec<&str> = reordered.iter().map(|a| &*a.name).collect();
+        assert_eq!(names, expected);
+        assert_eq!(reordered.get("remarks"), object.get("remarks"));
+    }
+
+    #[test]
+    fn parsed_objects_are_formatted_anew() {
+        let object = parse_object("role:   ACME\n+       Company # C\nsource: RIPE\n\n").unwrap();
+        assert_eq!(
+            Formatter::default().format(&object),
+            "role:           ACME\n                Company # C\nsource:         RIPE\n\n"
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use std::{cmp::Reverse, collections::HashSet};
+
+use crate::{AddressPrefix, AddressPrefixRange};
+
+/// Aggregate prefixes and prefix ranges into fewer ranges containing exactly the same prefixes.
+///
+/// Ranges contained in another range are left out, ranges of the same prefix with overlapping
+/// or adjacent lengths are joined and the more specifics of both halves of a prefix are merged
+/// into a range of that prefix, e.g. `192.0.2.0/25` and `192.0.2.128/25` become
+/// `192.0.2.0/24^25`. The returned ranges are sorted.
+///
+/// # Example
+/// ```
+/// # use rpsl::{generate::aggregate, AddressPrefix};
+/// let prefixes: Vec<AddressPrefix> = vec![
+///     "192.0.2.0/24".parse()?,
+///     "192.0.2.0/25".parse()?,
+///     "192.0.2.128/25".parse()?,
+///     "198.51.100.0/24".parse()?,
+///     "198.51.100.0/26".parse()?,
+/// ];
+///
+/// let aggregated: Vec<String> = aggregate(prefixes).iter().map(ToString::to_string).collect();
+/// assert_eq!(
+///     aggregated,
+///     ["192.0.2.0/24^24-25", "198.51.100.0/24", "198.51.100.0/26"]
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[must_use]
+pub fn aggregate<I>(ranges: I) -> Vec<AddressPrefixRange>
+where
+    I: IntoIterator,
+    I::Item: Into<AddressPrefixRange>,
+{
+    let mut entries: Vec<Entry> = ranges
+        .into_iter()
+        .map(|range| {
+            let range = range.into();
+            let (min, max) = range.length_bounds();
+            Entry {
+                prefix: *range.prefix(),
+                min,
+                max,
+            }
+        })
+        .collect();
+    loop {
+        entries = compact(entries);
+        match merge_siblings(&entries) {
+            Some(merged) => entries = merged,
+            None => break,
+        }
+    }
+    entries
+        .into_iter()
+        .map(|entry| AddressPrefixRange::with_bounds(entry.prefix, entry.min, entry.max))
+        .collect()
+}
+
+/// The more specifics of a prefix with a length between `min` and `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Entry {
+    prefix: AddressPrefix,
+    min: u8,
+    max: u8,
+}
+
+impl Entry {
+    /// Whether all prefixes contained in the other entry are contained in this entry.
+    fn covers(&self, other: &Entry) -> bool {
+        self.prefix.contains(&other.prefix) && self.min <= other.min && other.max <= self.max
+    }
+}
+
+/// Sort the entries, join the entries of the same prefix with overlapping or adjacent lengths
+/// and leave out entries covered by another entry.
+fn compact(mut entries: Vec<Entry>) -> Vec<Entry> {
+    entries.sort_unstable_by_key(|entry| (entry.prefix, entry.min, Reverse(entry.max)));
+
+    let mut joined: Vec<Entry> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        match joined.last_mut() {
+            Some(last) if last.prefix == entry.prefix && entry.min <= last.max + 1 => {
+                last.max = last.max.max(entry.max);
+            }
+            _ => joined.push(entry),
+        }
+    }
+
+    let mut kept: Vec<Entry> = Vec::with_capacity(joined.len());
+    // The kept entries whose prefix contains the prefix of the current entry. As the entries are
+    // sorted, an entry whose prefix does not contain the current one cannot contain later ones.
+    let mut enclosing: Vec<Entry> = Vec::new();
+    for entry in joined {
+        while enclosing
+            .last()
+            .is_some_and(|last| !last.prefix.contains(&entry.prefix))
+        {
+            enclosing.pop();
+        }
+        if enclosing.iter().any(|other| other.covers(&entry)) {
+            continue;
+        }
+        enclosing.push(entry);
+        kept.push(entry);
+    }
+    kept
+}
+
+/// Merge the entries of both halves of a prefix with the same lengths into an entry of that
+/// prefix, starting with the longest prefixes so that merged entries can be merged again.
+///
+/// Returns `None` if no entries were merged.
+fn merge_siblings(entries: &[Entry]) -> Option<Vec<Entry>> {
+    let mut remaining: HashSet<Entry> = entries.iter().copied().collect();
+    let mut by_length: Vec<Vec<Entry>> = vec![Vec::new(); 129];
+    for entry in entries {
+        by_length[usize::from(entry.prefix.length())].push(*entry);
+    }
+
+    let mut merged = false;
+    for length in (1..by_length.len()).rev() {
+        for entry in std::mem::take(&mut by_length[length]) {
+            let (Some(sibling), Some(parent)) = (entry.prefix.sibling(), entry.prefix.parent())
+            else {
+                continue;
+            };
+            let sibling = Entry {
+                prefix: sibling,
+                ..entry
+            };
+            if !remaining.contains(&entry) || !remaining.remove(&sibling) {
+                continue;
+            }
+            remaining.remove(&entry);
+            // Both halves only contain prefixes at least as long as themselves, so the merged
+            // entry contains exactly their union.
+            let parent = Entry {
+                prefix: parent,
+                ..entry
+            };
+            remaining.insert(parent);
+            by_length[length - 1].push(parent);
+            merged = true;
+        }
+    }
+    merged.then(|| remaining.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case(&["192.0.2.0/25", "192.0.2.128/25"], &["192.0.2.0/24^25"])]
+    #[case(
+        &["192.0.2.0/26", "192.0.2.64/26", "192.0.2.128/26", "192.0.2.192/26"],
+        &["192.0.2.0/24^26"]
+    )]
+    #[case(
+        &["192.0.2.0/24", "192.0.2.0/25", "192.0.2.128/25^+"],
+        &["192.0.2.0/24", "192.0.2.0/25", "192.0.2.128/25^+"]
+    )]
+    #[case(
+        &["192.0.2.0/24", "192.0.2.0/25^+", "192.0.2.128/25^+"],
+        &["192.0.2.0/24^+"]
+    )]
+    #[case(&["192.0.2.0/24^24-26", "192.0.2.0/24^27-28"], &["192.0.2.0/24^24-28"])]
+    #[case(&["192.0.2.0/24^25", "192.0.2.0/24^27"], &["192.0.2.0/24^25", "192.0.2.0/24^27"])]
+    #[case(&["192.0.2.0/24^+", "192.0.2.0/26", "192.0.2.0/24"], &["192.0.2.0/24^+"])]
+    #[case(&["192.0.2.0/25", "192.0.3.0/25"], &["192.0.2.0/25", "192.0.3.0/25"])]
+    #[case(&["192.0.2.128/25", "192.0.3.0/25"], &["192.0.2.128/25", "192.0.3.0/25"])]
+    #[case(&["192.0.2.0/25", "192.0.2.128/25^26"], &["192.0.2.0/25", "192.0.2.128/25^26"])]
+    #[case(&["0.0.0.0/1", "128.0.0.0/1", "::/1", "8000::/1"], &["0.0.0.0/0^1", "::/0^1"])]
+    #[case(
+        &["2001:db8::/33^48", "2001:db8:8000::/33^48", "2001:db8::/32"],
+        &["2001:db8::/32", "2001:db8::/32^48"]
+    )]
+    #[case(&[], &[])]
+    fn aggregate_ranges(#[case] ranges: &[&str], #[case] expected: &[&str]) {
+        let ranges: Vec<AddressPrefixRange> = ranges.iter().map(|r| r.parse().unwrap()).collect();
+        let aggregated: Vec<String> = aggregate(ranges).iter().map(ToString::to_string).collect();
+        assert_eq!(aggregated, expected);
+    }
+}
@@ -0,0 +1,686 @@
+use std::collections::BTreeSet;
+
+use super::Platform;
+use crate::{
+    policy::{AsPathRegex, ClassMember},
+    Asn, GenerateError, SetName,
+};
+
+/// The largest range of AS numbers expanded into an alternation for platforms without
+/// support for ranges in AS path regular expressions.
+const MAX_EXPANDED_RANGE: u32 = 256;
+
+/// The configuration of an AS path filter to generate.
+///
+/// An AS path filter is generated either from a set of origin AS numbers, e.g. the result of
+/// expanding an `as-set`, or from an [`AsPathRegex`] of a filter. Filters can be generated for
+/// Juniper, Cisco and BIRD.
+///
+/// # Example
+/// ```
+/// # use rpsl::{generate::{AsPathList, Platform}, Asn};
+/// let origins = [Asn::new(65536), Asn::new(65537)];
+///
+/// let as_path_list = AsPathList::new("AS-CUSTOMERS");
+/// assert_eq!(
+///     as_path_list.render_origins(Platform::Cisco, origins)?,
+///     "\
+/// no ip as-path access-list AS-CUSTOMERS
+/// ip as-path access-list AS-CUSTOMERS permit _65536$
+/// ip as-path access-list AS-CUSTOMERS permit _65537$
+/// "
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsPathList {
+    name: String,
+    peer_as: Option<Asn>,
+}
+
+impl AsPathList {
+    /// Create the configuration of an AS path filter with the given name.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            peer_as: None,
+        }
+    }
+
+    /// Use the AS number of the peer for `PeerAS` in AS path regular expressions.
+    #[must_use]
+    pub fn with_peer_as(mut self, peer_as: Asn) -> Self {
+        self.peer_as = Some(peer_as);
+        self
+    }
+
+    /// The name of the AS path filter.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Render a filter accepting AS paths originated by one of the AS numbers.
+    ///
+    /// An empty filter is rendered in a way that rejects all AS paths.
+    ///
+    /// # Errors
+    /// Returns a [`GenerateError`] if AS path filters cannot be generated for the platform.
+    pub fn render_origins<I>(&self, platform: Platform, origins: I) -> Result<String, GenerateError>
+    where
+        I: IntoIterator<Item = Asn>,
+    {
+        let origins: BTreeSet<u32> = origins.into_iter().map(u32::from).collect();
+        let name = &self.name;
+        let lines = match platform {
+            Platform::Juniper => {
+                let mut lines = vec![
+                    "policy-options {".to_string(),
+                    "replace:".to_string(),
+                    format!(" as-path-group {name} {{"),
+                ];
+                lines.extend(
+                    origins
+                        .iter()
+                        .enumerate()
+                        .map(|(i, asn)| format!("  as-path a{i} \".* {asn}\";")),
+                );
+                lines.extend([" }".to_string(), "}".to_string()]);
+                lines
+            }
+            Platform::Cisco => {
+                let mut lines = vec![format!("no ip as-path access-list {name}")];
+                if origins.is_empty() {
+                    lines.push(format!("ip as-path access-list {name} deny .*"));
+                }
+                lines.extend(
+                    origins
+                        .iter()
+                        .map(|asn| format!("ip as-path access-list {name} permit _{asn}$")),
+                );
+                lines
+            }
+            Platform::Bird => {
+                let condition = if origins.is_empty() {
+                    "false".to_string()
+                } else {
+                    let origins: Vec<String> = origins.iter().map(u32::to_string).collect();
+                    format!("bgp_path.last ~ [{}]", origins.join(", "))
+                };
+                bird_function(name, &condition)
+            }
+            platform => return Err(GenerateError::UnsupportedPlatform(platform)),
+        };
+        Ok(join_lines(&lines))
+    }
+
+    /// Render a filter accepting AS paths matched by the regular expression.
+    ///
+    /// Sets contained in the expression cannot be rendered, use
+    /// [`render_regex_with`](Self::render_regex_with) to resolve their members.
+    ///
+    /// # Errors
+    /// Returns a [`GenerateError`] if AS path filters cannot be generated for the platform or
+    /// the expression cannot be expressed in its syntax.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{generate::{AsPathList, Platform}, policy::AsPathRegex};
+    /// let regex: AsPathRegex = "<^AS65537 AS65538* $>".parse()?;
+    ///
+    /// let as_path_list = AsPathList::new("NN");
+    /// assert_eq!(
+    ///     as_path_list.render_regex(Platform::Juniper, &regex)?,
+    ///     "\
+    /// policy-options {
+    /// replace:
+    ///  as-path NN \"65537 65538*\";
+    /// }
+    /// "
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn render_regex(
+        &self,
+        platform: Platform,
+        regex: &AsPathRegex,
+    ) -> Result<String, GenerateError> {
+        self.render_regex_with(platform, regex, |_| None)
+    }
+
+    /// Render a filter accepting AS paths matched by the regular expression, using `resolve`
+    /// to determine the AS numbers contained in a set, e.g. using an
+    /// [`Expander`](crate::database::Expander).
+    ///
+    /// # Errors
+    /// Returns a [`GenerateError`] if AS path filters cannot be generated for the platform,
+    /// the expression cannot be expressed in its syntax or a set cannot be resolved.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::collections::BTreeSet;
+    /// # use rpsl::{generate::{AsPathList, Platform}, policy::AsPathRegex, Asn, SetName};
+    /// let regex: AsPathRegex = "<AS-CUSTOMERS$>".parse()?;
+    ///
+    /// let resolve = |_: &SetName| Some(BTreeSet::from([Asn::new(65536), Asn::new(65537)]));
+    /// let as_path_list = AsPathList::new("NN");
+    /// assert_eq!(
+    ///     as_path_list.render_regex_with(Platform::Bird, &regex, resolve)?,
+    ///     "\
+    /// function NN()
+    /// {
+    ///     return bgp_path ~ [= * [65536, 65537] =];
+    /// }
+    /// "
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn render_regex_with<F>(
+        &self,
+        platform: Platform,
+        regex: &AsPathRegex,
+        resolve: F,
+    ) -> Result<String, GenerateError>
+    where
+        F: Fn(&SetName) -> Option<BTreeSet<Asn>>,
+    {
+        let translator = Translator {
+            platform,
+            regex,
+            peer_as: self.peer_as,
+            resolve: &resolve,
+        };
+        let name = &self.name;
+        let lines = match platform {
+            Platform::Juniper => vec![
+                "policy-options {".to_string(),
+                "replace:".to_string(),
+                format!(" as-path {name} \"{}\";", translator.juniper()?),
+                "}".to_string(),
+            ],
+            Platform::Cisco => vec![
+                format!("no ip as-path access-list {name}"),
+                format!(
+                    "ip as-path access-list {name} permit {}",
+                    translator.cisco()?
+                ),
+            ],
+            Platform::Bird => {
+                bird_function(name, &format!("bgp_path ~ [= {} =]", translator.bird()?))
+            }
+            platform => return Err(GenerateError::UnsupportedPlatform(platform)),
+        };
+        Ok(join_lines(&lines))
+    }
+}
+
+fn bird_function(name: &str, condition: &str) -> Vec<String> {
+    vec![
+        format!("function {name}()"),
+        "{".to_string(),
+        format!("    return {condition};"),
+        "}".to_string(),
+    ]
+}
+
+fn join_lines(lines: &[String]) -> String {
+    let mut config = lines.join("\n");
+    config.push('\n');
+    config
+}
+
+/// Translates an AS path regular expression into the syntax of a platform.
+struct Translator<'r, F> {
+    platform: Platform,
+    regex: &'r AsPathRegex,
+    peer_as: Option<Asn>,
+    resolve: &'r F,
+}
+
+impl<F> Translator<'_, F>
+where
+    F: Fn(&SetName) -> Option<BTreeSet<Asn>>,
+{
+    fn unsupported(&self, reason: impl Into<String>) -> GenerateError {
+        GenerateError::UnsupportedRegex {
+            regex: self.regex.to_string(),
+            platform: self.platform,
+            reason: reason.into(),
+        }
+    }
+
+    /// The expressions of the regular expression, without the anchors at its start and end,
+    /// and whether it is anchored at the start and end.
+    fn unanchored(&self) -> Result<(&[AsPathRegex], bool, bool), GenerateError> {
+        let mut regexes = match self.regex {
+            AsPathRegex::Sequence(regexes) => regexes.as_slice(),
+            regex => std::slice::from_ref(regex),
+        };
+        let start = regexes.first() == Some(&AsPathRegex::Start);
+        if start {
+            regexes = &regexes[1..];
+        }
+        let end = regexes.last() == Some(&AsPathRegex::End);
+        if end {
+            regexes = &regexes[..regexes.len() - 1];
+        }
+        if regexes.iter().any(contains_anchor) {
+            return Err(self.unsupported("anchors are only supported at the start and end"));
+        }
+        Ok((regexes, start, end))
+    }
+
+    /// The ranges of AS numbers matched by a single AS expression.
+    fn members(&self, regex: &AsPathRegex) -> Result<Vec<(u32, u32)>, GenerateError> {
+        let asn = |asn: Asn| (asn.number(), asn.number());
+        let peer_as = || self.peer_as.ok_or(GenerateError::UnknownPeerAs);
+        let set = |name: &SetName| {
+            (self.resolve)(name)
+                .map(|members| members.into_iter().map(asn).collect::<Vec<_>>())
+                .ok_or_else(|| GenerateError::UnresolvedSet(name.clone()))
+        };
+        let members = match regex {
+            AsPathRegex::Asn(number) => vec![asn(*number)],
+            AsPathRegex::PeerAs => vec![asn(peer_as()?)],
+            AsPathRegex::Set(name) => set(name)?,
+            AsPathRegex::Class { negated: true, .. } => {
+                return Err(self.unsupported("negated classes are not supported"))
+            }
+            AsPathRegex::Class { members, .. } => {
+                let mut ranges = Vec::new();
+                for member in members {
+                    match member {
+                        ClassMember::Asn(number) => ranges.push(asn(*number)),
+                        ClassMember::Range(first, last) => {
+                            ranges.push((first.number(), last.number()));
+                        }
+                        ClassMember::Set(name) => ranges.extend(set(name)?),
+                        ClassMember::PeerAs => ranges.push(asn(peer_as()?)),
+                    }
+                }
+                ranges
+            }
+            _ => unreachable!("only called for expressions matching a single AS number"),
+        };
+        let mut members = members;
+        members.sort_unstable();
+        members.dedup();
+        if members.is_empty() {
+            return Err(self.unsupported("a set without members"));
+        }
+        Ok(members)
+    }
+
+    /// The regular expression in Junos syntax, which always matches the whole AS path.
+    fn juniper(&self) -> Result<String, GenerateError> {
+        let (regexes, start, end) = self.unanchored()?;
+        let mut terms = Vec::new();
+        if !start {
+            terms.push(".*".to_string());
+        }
+        for regex in regexes {
+            terms.push(self.juniper_term(regex)?);
+        }
+        if !end {
+            terms.push(".*".to_string());
+        }
+        if terms.is_empty() {
+            return Ok("()".to_string());
+        }
+        Ok(terms.join(" "))
+    }
+
+    fn juniper_term(&self, regex: &AsPathRegex) -> Result<String, GenerateError> {
+        Ok(match regex {
+            AsPathRegex::Any => ".".to_string(),
+            AsPathRegex::Sequence(regexes) => {
+                let terms: Result<Vec<String>, _> =
+                    regexes.iter().map(|r| self.juniper_term(r)).collect();
+                format!("({})", terms?.join(" "))
+            }
+            AsPathRegex::Alternation(regexes) => {
+                let terms: Result<Vec<String>, _> =
+                    regexes.iter().map(|r| self.juniper_term(r)).collect();
+                format!("({})", terms?.join("|"))
+            }
+            AsPathRegex::Repeat { same_as: true, .. } => {
+                return Err(self.unsupported("`~` repetitions are not supported"))
+            }
+            AsPathRegex::Repeat {
+                regex, min, max, ..
+            } => {
+                let term = self.juniper_term(regex)?;
+                // Other terms are either atomic or already enclosed in parentheses.
+                let term = if matches!(**regex, AsPathRegex::Repeat { .. }) {
+                    format!("({term})")
+                } else {
+                    term
+                };
+                format!("{term}{}", repetition(*min, *max))
+            }
+            regex => {
+                let members: Vec<String> = self
+                    .members(regex)?
+                    .into_iter()
+                    .map(|(first, last)| {
+                        if first == last {
+                            first.to_string()
+                        } else {
+                            format!("{first}-{last}")
+                        }
+                    })
+                    .collect();
+                alternation(&members)
+            }
+        })
+    }
+
+    /// The regular expression in Cisco syntax, matched against the AS path as a string of AS
+    /// numbers separated by spaces.
+    ///
+    /// Every AS number is followed by `_`, matching the following separator or the end of the
+    /// path.
+    fn cisco(&self) -> Result<String, GenerateError> {
+        let (regexes, start, end) = self.unanchored()?;
+        let mut pattern = if start { "^" } else { "_" }.to_string();
+        for regex in regexes {
+            pattern.push_str(&self.cisco_term(regex)?);
+        }
+        if end {
+            if pattern.ends_with('_') && pattern.len() > 1 {
+                pattern.pop();
+            }
+            pattern.push('$');
+        }
+        Ok(pattern)
+    }
+
+    fn cisco_term(&self, regex: &AsPathRegex) -> Result<String, GenerateError> {
+        Ok(match regex {
+            AsPathRegex::Any => "[0-9]+_".to_string(),
+            AsPathRegex::Sequence(regexes) => {
+                let terms: Result<Vec<String>, _> =
+                    regexes.iter().map(|r| self.cisco_term(r)).collect();
+                terms?.concat()
+            }
+            AsPathRegex::Alternation(regexes) => {
+                let terms: Result<Vec<String>, _> =
+                    regexes.iter().map(|r| self.cisco_term(r)).collect();
+                format!("({})", terms?.join("|"))
+            }
+            AsPathRegex::Repeat { same_as: true, .. } => {
+                return Err(self.unsupported("`~` repetitions are not supported"))
+            }
+            AsPathRegex::Repeat {
+                regex, min, max, ..
+            } => {
+                let term = self.cisco_term(regex)?;
+                let term = if matches!(**regex, AsPathRegex::Alternation(_)) {
+                    term
+                } else {
+                    format!("({term})")
+                };
+                format!("{term}{}", repetition(*min, *max))
+            }
+            regex => {
+                let mut numbers = BTreeSet::new();
+                for (first, last) in self.members(regex)? {
+                    if last - first >= MAX_EXPANDED_RANGE {
+                        return Err(self.unsupported(format!(
+                            "the range AS{first}-AS{last} is too large to be expanded"
+                        )));
+                    }
+                    numbers.extend(first..=last);
+                }
+                let members: Vec<String> = numbers.iter().map(u32::to_string).collect();
+                format!("{}_", alternation(&members))
+            }
+        })
+    }
+
+    /// The regular expression as a BIRD path mask, which always matches the whole AS path.
+    fn bird(&self) -> Result<String, GenerateError> {
+        let (regexes, start, end) = self.unanchored()?;
+        let mut terms = Vec::new();
+        if !start {
+            terms.push("*".to_string());
+        }
+        self.bird_terms(regexes, &mut terms)?;
+        if !end {
+            terms.push("*".to_string());
+        }
+        Ok(terms.join(" "))
+    }
+
+    fn bird_terms(
+        &self,
+        regexes: &[AsPathRegex],
+        terms: &mut Vec<String>,
+    ) -> Result<(), GenerateError> {
+        for regex in regexes {
+            match regex {
+                AsPathRegex::Any => terms.push("?".to_string()),
+                AsPathRegex::Sequence(regexes) => self.bird_terms(regexes, terms)?,
+                AsPathRegex::Repeat {
+                    regex,
+                    min: 0,
+                    max: None,
+                    same_as: false,
+                } if **regex == AsPathRegex::Any => terms.push("*".to_string()),
+                AsPathRegex::Alternation(_) => {
+                    return Err(self.unsupported("alternations are not supported"))
+                }
+                AsPathRegex::Repeat { .. } => {
+                    return Err(self.unsupported("only `.*` repetitions are supported"))
+                }
+                regex => {
+                    let members: Vec<String> = self
+                        .members(regex)?
+                        .into_iter()
+                        .map(|(first, last)| {
+                            if first == last {
+                                first.to_string()
+                            } else {
+                                format!("{first}..{last}")
+                            }
+                        })
+                        .collect();
+                    match members.as_slice() {
+                        [member] if !member.contains('.') => terms.push(member.clone()),
+                        members => terms.push(format!("[{}]", members.join(", "))),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether the expression contains a start or end anchor.
+fn contains_anchor(regex: &AsPathRegex) -> bool {
+    match regex {
+        AsPathRegex::Start | AsPathRegex::End => true,
+        AsPathRegex::Sequence(regexes) | AsPathRegex::Alternation(regexes) => {
+            regexes.iter().any(contains_anchor)
+        }
+        AsPathRegex::Repeat { regex, .. } => contains_anchor(regex),
+        _ => false,
+    }
+}
+
+/// The alternation of the terms, without parentheses for a single term.
+fn alternation(terms: &[String]) -> String {
+    match terms {
+        [term] => term.clone(),
+        terms => format!("({})", terms.join("|")),
+    }
+}
+
+/// A repetition operator in POSIX syntax.
+fn repetition(min: usize, max: Option<usize>) -> String {
+    match (min, max) {
+        (0, None) => "*".to_string(),
+        (1, None) => "+".to_string(),
+        (0, Some(1)) => "?".to_string(),
+        (min, None) => format!("{{{min},}}"),
+        (min, Some(max)) if min == max => format!("{{{min}}}"),
+        (min, Some(max)) => format!("{{{min},{max}}}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn resolve(name: &SetName) -> Option<BTreeSet<Asn>> {
+        (name.normalized() == "AS-CUSTOMERS")
+            .then(|| BTreeSet::from([Asn::new(65540), Asn::new(65541)]))
+    }
+
+    #[rstest]
+    #[case(
+        Platform::Juniper,
+        "\
+policy-options {
+replace:
+ as-path-group NN {
+  as-path a0 \".* 65536\";
+  as-path a1 \".* 4200000000\";
+ }
+}
+"
+    )]
+    #[case(
+        Platform::Cisco,
+        "\
+no ip as-path access-list NN
+ip as-path access-list NN permit _65536$
+ip as-path access-list NN permit _4200000000$
+"
+    )]
+    #[case(
+        Platform::Bird,
+        "\
+function NN()
+{
+    return bgp_path.last ~ [65536, 4200000000];
+}
+"
+    )]
+    fn render_origins(#[case] platform: Platform, #[case] expected: &str) {
+        let origins = [Asn::new(4_200_000_000), Asn::new(65536), Asn::new(65536)];
+        let rendered = AsPathList::new("NN").render_origins(platform, origins);
+        assert_eq!(rendered.unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(Platform::Cisco, "ip as-path access-list NN deny .*")]
+    #[case(Platform::Bird, "    return false;")]
+    fn render_no_origins(#[case] platform: Platform, #[case] expected_line: &str) {
+        let rendered = AsPathList::new("NN").render_origins(platform, []).unwrap();
+        assert!(rendered.lines().any(|line| line == expected_line));
+    }
+
+    #[rstest]
+    #[case(Platform::CiscoXr)]
+    #[case(Platform::Arista)]
+    #[case(Platform::Frr)]
+    #[case(Platform::OpenBgpd)]
+    fn unsupported_platform_is_err(#[case] platform: Platform) {
+        let as_path_list = AsPathList::new("NN");
+        let regex: AsPathRegex = "<AS65536$>".parse().unwrap();
+        assert_eq!(
+            as_path_list.render_origins(platform, [Asn::new(65536)]),
+            Err(GenerateError::UnsupportedPlatform(platform))
+        );
+        assert_eq!(
+            as_path_list.render_regex(platform, &regex),
+            Err(GenerateError::UnsupportedPlatform(platform))
+        );
+    }
+
+    #[rstest]
+    #[case("<^AS65537 AS65538* $>", "65537 65538*", "^65537_(65538_)*$", "")]
+    #[case(
+        "<AS-CUSTOMERS$>",
+        ".* (65540|65541)",
+        "_(65540|65541)$",
+        "* [65540, 65541]"
+    )]
+    #[case(
+        "<^PeerAS .* AS65536$>",
+        "65537 .* 65536",
+        "^65537_([0-9]+_)*65536$",
+        "65537 * 65536"
+    )]
+    #[case("<AS65536>", ".* 65536 .*", "_65536_", "* 65536 *")]
+    #[case(
+        "<^[AS65536 AS65540-AS65542 AS-CUSTOMERS]$>",
+        "(65536|65540|65540-65542|65541)",
+        "^(65536|65540|65541|65542)$",
+        "[65536, 65540, 65540..65542, 65541]"
+    )]
+    #[case(
+        "<^AS65536 . AS65537?$>",
+        "65536 . 65537?",
+        "^65536_[0-9]+_(65537_)?$",
+        ""
+    )]
+    #[case(
+        "<^(AS65536 AS65537)+ (AS1 | AS2){2,3}$>",
+        "(65536 65537)+ (1|2){2,3}",
+        "^(65536_65537_)+(1_|2_){2,3}$",
+        ""
+    )]
+    fn render_regex(
+        #[case] regex: &str,
+        #[case] juniper: &str,
+        #[case] cisco: &str,
+        #[case] bird: &str,
+    ) {
+        let regex: AsPathRegex = regex.parse().unwrap();
+        let as_path_list = AsPathList::new("NN").with_peer_as(Asn::new(65537));
+
+        let rendered = as_path_list.render_regex_with(Platform::Juniper, &regex, resolve);
+        assert!(rendered
+            .unwrap()
+            .contains(&format!(" as-path NN \"{juniper}\";")));
+        let rendered = as_path_list.render_regex_with(Platform::Cisco, &regex, resolve);
+        assert!(rendered.unwrap().ends_with(&format!(" permit {cisco}\n")));
+        let rendered = as_path_list.render_regex_with(Platform::Bird, &regex, resolve);
+        if bird.is_empty() {
+            assert!(rendered.is_err());
+        } else {
+            assert!(rendered
+                .unwrap()
+                .contains(&format!("bgp_path ~ [= {bird} =];")));
+        }
+    }
+
+    #[rstest]
+    #[case("<AS-UNKNOWN$>", GenerateError::UnresolvedSet("AS-UNKNOWN".parse().unwrap()))]
+    #[case("<^PeerAS>", GenerateError::UnknownPeerAs)]
+    fn unresolved_regex_is_err(#[case] regex: &str, #[case] expected: GenerateError) {
+        let regex: AsPathRegex = regex.parse().unwrap();
+        let rendered = AsPathList::new("NN").render_regex_with(Platform::Cisco, &regex, resolve);
+        assert_eq!(rendered, Err(expected));
+    }
+
+    #[rstest]
+    #[case("<^AS65536~*$>", Platform::Juniper)]
+    #[case("<^[^AS65536]$>", Platform::Cisco)]
+    #[case("<^[AS1-AS4000000]$>", Platform::Cisco)]
+    #[case("<^AS1 (AS2 | AS3)$>", Platform::Bird)]
+    #[case("<^AS1 AS2+$>", Platform::Bird)]
+    fn unsupported_regex_is_err(#[case] regex: &str, #[case] platform: Platform) {
+        let regex: AsPathRegex = regex.parse().unwrap();
+        let rendered = AsPathList::new("NN").render_regex(platform, &regex);
+        assert!(matches!(
+            rendered,
+            Err(GenerateError::UnsupportedRegex { .. })
+        ));
+    }
+}
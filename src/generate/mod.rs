@@ -0,0 +1,80 @@
+//! Generation of router configuration from expanded RPSL data.
+//!
+//! A [`PrefixList`] renders the prefix ranges resulting from the expansion of an `as-set` or
+//! `route-set` into the filter syntax of a [`Platform`]. The output follows the one emitted by
+//! [bgpq4](https://github.com/bgp/bgpq4) for the same input, so that results can be compared.
+//! The ranges can be reduced using [`aggregate`], without changing the prefixes they contain.
+//!
+//! An [`AsPathList`] renders a filter on the AS path of routes, either accepting the origin AS
+//! numbers resulting from the expansion of an `as-set` or the paths matched by an
+//! [`AsPathRegex`](crate::policy::AsPathRegex).
+//!
+//! # Example
+//! ```
+//! # use rpsl::{generate::{Platform, PrefixList}, AddressPrefixRange};
+//! let ranges: Vec<AddressPrefixRange> = vec!["192.0.2.0/24".parse()?, "198.51.100.0/24^+".parse()?];
+//!
+//! let config = PrefixList::new("AS65536").render(Platform::Cisco, ranges);
+//! assert_eq!(
+//!     config,
+//!     "\
+//! no ip prefix-list AS65536
+//! ip prefix-list AS65536 permit 192.0.2.0/24
+//! ip prefix-list AS65536 permit 198.51.100.0/24 le 32
+//! "
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+use std::fmt;
+
+pub use aggregate::aggregate;
+pub use as_path::AsPathList;
+pub use prefix_list::PrefixList;
+
+mod aggregate;
+mod as_path;
+mod prefix_list;
+
+/// A router platform for which configuration can be generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    /// Juniper Junos.
+    Juniper,
+    /// Cisco IOS and IOS-XE.
+    Cisco,
+    /// Cisco IOS-XR.
+    CiscoXr,
+    /// Arista EOS.
+    Arista,
+    /// The BIRD Internet Routing Daemon.
+    Bird,
+    /// FRR, which accepts the Cisco IOS syntax.
+    Frr,
+    /// `OpenBGPD`.
+    OpenBgpd,
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Juniper => "Juniper",
+            Self::Cisco => "Cisco IOS",
+            Self::CiscoXr => "Cisco IOS-XR",
+            Self::Arista => "Arista EOS",
+            Self::Bird => "BIRD",
+            Self::Frr => "FRR",
+            Self::OpenBgpd => "OpenBGPD",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The IP version of the generated configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IpVersion {
+    /// IPv4.
+    #[default]
+    V4,
+    /// IPv6.
+    V6,
+}
@@ -0,0 +1,520 @@
+use super::{aggregate, IpVersion, Platform};
+use crate::{AddressPrefix, AddressPrefixRange};
+
+/// The configuration of a prefix list to generate.
+///
+/// A prefix list is generated from prefix ranges of a single IP version, ranges of the other
+/// version are ignored. Ranges containing more specifics, e.g. `192.0.2.0/24^+` or
+/// `2001:db8::/32^48-56`, are rendered using the length matching of the platform.
+///
+/// # Example
+/// ```
+/// # use rpsl::{generate::{IpVersion, Platform, PrefixList}, AddressPrefixRange};
+/// let ranges: Vec<AddressPrefixRange> = vec![
+///     "192.0.2.0/24".parse()?,
+///     "2001:db8::/32^48-56".parse()?,
+/// ];
+///
+/// let prefix_list = PrefixList::new("AS-EXAMPLE").with_ip_version(IpVersion::V6);
+/// assert_eq!(
+///     prefix_list.render(Platform::Bird, ranges),
+///     "\
+/// AS-EXAMPLE = [
+///     2001:db8::/32{48,56}
+/// ];
+/// "
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixList {
+    name: String,
+    ip_version: IpVersion,
+    aggregate: bool,
+    max_length: Option<u8>,
+}
+
+impl PrefixList {
+    /// Create the configuration of an IPv4 prefix list with the given name.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ip_version: IpVersion::V4,
+            aggregate: false,
+            max_length: None,
+        }
+    }
+
+    /// Generate the prefix list for the given IP version.
+    #[must_use]
+    pub fn with_ip_version(mut self, ip_version: IpVersion) -> Self {
+        self.ip_version = ip_version;
+        self
+    }
+
+    /// Reduce the number of entries using [`aggregate`], accepting the same prefixes.
+    #[must_use]
+    pub fn with_aggregation(mut self) -> Self {
+        self.aggregate = true;
+        self
+    }
+
+    /// Limit the length of the accepted prefixes.
+    ///
+    /// The upper bound of ranges such as `192.0.2.0/24^+` is lowered to the maximum length,
+    /// ranges only containing prefixes that are longer are left out.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{generate::{Platform, PrefixList}, AddressPrefixRange};
+    /// let ranges: Vec<AddressPrefixRange> = vec![
+    ///     "192.0.2.0/24^+".parse()?,
+    ///     "198.51.100.0/25".parse()?,
+    /// ];
+    ///
+    /// let prefix_list = PrefixList::new("NN").with_max_length(24);
+    /// assert_eq!(
+    ///     prefix_list.render(Platform::Cisco, ranges),
+    ///     "\
+    /// no ip prefix-list NN
+    /// ip prefix-list NN permit 192.0.2.0/24
+    /// "
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_max_length(mut self, max_length: u8) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// The name of the prefix list.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Render the prefix list in the syntax of the platform.
+    ///
+    /// Entries are sorted by prefix. An empty prefix list is rendered in a way that rejects
+    /// all prefixes on platforms that would otherwise accept everything.
+    #[must_use]
+    pub fn render<I>(&self, platform: Platform, ranges: I) -> String
+    where
+        I: IntoIterator<Item = AddressPrefixRange>,
+    {
+        let entries = self.entries(ranges);
+        let lines = match platform {
+            Platform::Juniper if entries.iter().all(Entry::is_exact) => self.juniper(&entries),
+            Platform::Juniper => self.juniper_route_filter(&entries),
+            Platform::Cisco | Platform::Frr => self.cisco(&entries),
+            Platform::CiscoXr => self.cisco_xr(&entries),
+            Platform::Arista => self.arista(&entries),
+            Platform::Bird => self.bird(&entries),
+            Platform::OpenBgpd => self.openbgpd(&entries),
+        };
+        let mut config = lines.join("\n");
+        config.push('\n');
+        config
+    }
+
+    /// The sorted entries of the prefix list.
+    fn entries<I>(&self, ranges: I) -> Vec<Entry>
+    where
+        I: IntoIterator<Item = AddressPrefixRange>,
+    {
+        let is_v4 = self.ip_version == IpVersion::V4;
+        let ranges = ranges
+            .into_iter()
+            .filter(|range| range.prefix().is_ipv4() == is_v4)
+            .filter_map(|range| {
+                let (min, max) = range.length_bounds();
+                let max = self.max_length.map_or(max, |limit| max.min(limit));
+                (min <= max).then(|| AddressPrefixRange::with_bounds(*range.prefix(), min, max))
+            });
+        let ranges: Vec<AddressPrefixRange> = if self.aggregate {
+            aggregate(ranges)
+        } else {
+            ranges.collect()
+        };
+        let mut entries: Vec<Entry> = ranges
+            .into_iter()
+            .map(|range| {
+                let (min, max) = range.length_bounds();
+                Entry {
+                    prefix: *range.prefix(),
+                    min,
+                    max,
+                }
+            })
+            .collect();
+        entries.sort_unstable();
+        entries.dedup();
+        entries
+    }
+
+    fn default_route(&self) -> &'static str {
+        match self.ip_version {
+            IpVersion::V4 => "0.0.0.0/0",
+            IpVersion::V6 => "::/0",
+        }
+    }
+
+    fn ip_keyword(&self) -> &'static str {
+        match self.ip_version {
+            IpVersion::V4 => "ip",
+            IpVersion::V6 => "ipv6",
+        }
+    }
+
+    fn juniper(&self, entries: &[Entry]) -> Vec<String> {
+        let mut lines = vec![
+            "policy-options {".to_string(),
+            "replace:".to_string(),
+            format!(" prefix-list {} {{", self.name),
+        ];
+        lines.extend(entries.iter().map(|entry| format!("    {};", entry.prefix)));
+        lines.extend([" }".to_string(), "}".to_string()]);
+        lines
+    }
+
+    fn juniper_route_filter(&self, entries: &[Entry]) -> Vec<String> {
+        let mut lines = vec![
+            "policy-options {".to_string(),
+            format!(" policy-statement {} {{", self.name),
+            "replace:".to_string(),
+            "  from {".to_string(),
+        ];
+        lines.extend(entries.iter().map(|entry| {
+            let length = entry.prefix.length();
+            let matching = match (entry.min, entry.max) {
+                (min, max) if min == length && max == length => "exact".to_string(),
+                (min, max) if min == length => format!("upto /{max}"),
+                (min, max) => format!("prefix-length-range /{min}-/{max}"),
+            };
+            format!("    route-filter {} {matching};", entry.prefix)
+        }));
+        lines.extend(["  }".to_string(), " }".to_string(), "}".to_string()]);
+        lines
+    }
+
+    fn cisco(&self, entries: &[Entry]) -> Vec<String> {
+        let (keyword, name) = (self.ip_keyword(), &self.name);
+        let mut lines = vec![format!("no {keyword} prefix-list {name}")];
+        if entries.is_empty() {
+            lines.push(format!("! generated prefix-list {name} is empty"));
+            lines.push(format!(
+                "{keyword} prefix-list {name} deny {}",
+                self.default_route()
+            ));
+        }
+        lines.extend(entries.iter().map(|entry| {
+            format!(
+                "{keyword} prefix-list {name} permit {}{}",
+                entry.prefix,
+                entry.cisco_lengths()
+            )
+        }));
+        lines
+    }
+
+    fn cisco_xr(&self, entries: &[Entry]) -> Vec<String> {
+        let mut lines = vec![
+            format!("no prefix-set {}", self.name),
+            format!("prefix-set {}", self.name),
+        ];
+        let last = entries.len().saturating_sub(1);
+        lines.extend(entries.iter().enumerate().map(|(i, entry)| {
+            let separator = if i == last { "" } else { "," };
+            format!(" {}{}{separator}", entry.prefix, entry.cisco_lengths())
+        }));
+        lines.push("end-set".to_string());
+        lines
+    }
+
+    fn arista(&self, entries: &[Entry]) -> Vec<String> {
+        let (keyword, name) = (self.ip_keyword(), &self.name);
+        let mut lines = vec![
+            format!("no {keyword} prefix-list {name}"),
+            format!("{keyword} prefix-list {name}"),
+        ];
+        if entries.is_empty() {
+            lines.push(format!("    deny {}", self.default_route()));
+        }
+        lines.extend(
+            entries
+                .iter()
+                .map(|entry| format!("    permit {}{}", entry.prefix, entry.cisco_lengths())),
+        );
+        lines
+    }
+
+    fn bird(&self, entries: &[Entry]) -> Vec<String> {
+        if entries.is_empty() {
+            return vec![format!("{} = [];", self.name)];
+        }
+        let mut lines = vec![format!("{} = [", self.name)];
+        let last = entries.len() - 1;
+        lines.extend(entries.iter().enumerate().map(|(i, entry)| {
+            let separator = if i == last { "" } else { "," };
+            if entry.is_exact() {
+                format!("    {}{separator}", entry.prefix)
+            } else {
+                format!(
+                    "    {}{{{},{}}}{separator}",
+                    entry.prefix, entry.min, entry.max
+                )
+            }
+        }));
+        lines.push("];".to_string());
+        lines
+    }
+
+    fn openbgpd(&self, entries: &[Entry]) -> Vec<String> {
+        let mut lines = vec![format!("prefix-set {} {{", self.name)];
+        lines.extend(entries.iter().map(|entry| match (entry.min, entry.max) {
+            _ if entry.is_exact() => format!("\t{}", entry.prefix),
+            (min, max) if min == max => format!("\t{} prefixlen = {min}", entry.prefix),
+            (min, max) => format!("\t{} prefixlen {min} - {max}", entry.prefix),
+        }));
+        lines.push("}".to_string());
+        lines
+    }
+}
+
+/// An entry of a prefix list, accepting the more specifics of a prefix with a length between
+/// `min` and `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+    prefix: AddressPrefix,
+    min: u8,
+    max: u8,
+}
+
+impl Entry {
+    /// Whether only the prefix itself is accepted.
+    fn is_exact(&self) -> bool {
+        self.min == self.prefix.length() && self.max == self.prefix.length()
+    }
+
+    /// The length matching in Cisco syntax, e.g. ` ge 25 le 32`.
+    fn cisco_lengths(&self) -> String {
+        match (self.min, self.max) {
+            _ if self.is_exact() => String::new(),
+            (min, max) if min == self.prefix.length() => format!(" le {max}"),
+            (min, max) => format!(" ge {min} le {max}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn ranges(ranges: &[&str]) -> Vec<AddressPrefixRange> {
+        ranges.iter().map(|range| range.parse().unwrap()).collect()
+    }
+
+    #[fixture]
+    fn mixed() -> Vec<AddressPrefixRange> {
+        ranges(&[
+            "198.51.100.0/24^+",
+            "192.0.2.0/24",
+            "203.0.113.0/24^25-26",
+            "2001:db8::/32",
+        ])
+    }
+
+    #[rstest]
+    #[case(
+        Platform::Cisco,
+        "\
+no ip prefix-list NN
+ip prefix-list NN permit 192.0.2.0/24
+ip prefix-list NN permit 198.51.100.0/24 le 32
+ip prefix-list NN permit 203.0.113.0/24 ge 25 le 26
+"
+    )]
+    #[case(
+        Platform::Frr,
+        "\
+no ip prefix-list NN
+ip prefix-list NN permit 192.0.2.0/24
+ip prefix-list NN permit 198.51.100.0/24 le 32
+ip prefix-list NN permit 203.0.113.0/24 ge 25 le 26
+"
+    )]
+    #[case(
+        Platform::CiscoXr,
+        "\
+no prefix-set NN
+prefix-set NN
+ 192.0.2.0/24,
+ 198.51.100.0/24 le 32,
+ 203.0.113.0/24 ge 25 le 26
+end-set
+"
+    )]
+    #[case(
+        Platform::Arista,
+        "\
+no ip prefix-list NN
+ip prefix-list NN
+    permit 192.0.2.0/24
+    permit 198.51.100.0/24 le 32
+    permit 203.0.113.0/24 ge 25 le 26
+"
+    )]
+    #[case(
+        Platform::Juniper,
+        "\
+policy-options {
+ policy-statement NN {
+replace:
+  from {
+    route-filter 192.0.2.0/24 exact;
+    route-filter 198.51.100.0/24 upto /32;
+    route-filter 203.0.113.0/24 prefix-length-range /25-/26;
+  }
+ }
+}
+"
+    )]
+    #[case(
+        Platform::Bird,
+        "\
+NN = [
+    192.0.2.0/24,
+    198.51.100.0/24{24,32},
+    203.0.113.0/24{25,26}
+];
+"
+    )]
+    #[case(
+        Platform::OpenBgpd,
+        "\
+prefix-set NN {
+\t192.0.2.0/24
+\t198.51.100.0/24 prefixlen 24 - 32
+\t203.0.113.0/24 prefixlen 25 - 26
+}
+"
+    )]
+    fn render_ipv4(
+        mixed: Vec<AddressPrefixRange>,
+        #[case] platform: Platform,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(PrefixList::new("NN").render(platform, mixed), expected);
+    }
+
+    #[rstest]
+    #[case(
+        Platform::Cisco,
+        "\
+no ipv6 prefix-list NN
+ipv6 prefix-list NN permit 2001:db8::/32
+"
+    )]
+    #[case(
+        Platform::Juniper,
+        "\
+policy-options {
+replace:
+ prefix-list NN {
+    2001:db8::/32;
+ }
+}
+"
+    )]
+    #[case(
+        Platform::OpenBgpd,
+        "\
+prefix-set NN {
+\t2001:db8::/32
+}
+"
+    )]
+    fn render_ipv6(
+        mixed: Vec<AddressPrefixRange>,
+        #[case] platform: Platform,
+        #[case] expected: &str,
+    ) {
+        let prefix_list = PrefixList::new("NN").with_ip_version(IpVersion::V6);
+        assert_eq!(prefix_list.render(platform, mixed), expected);
+    }
+
+    #[rstest]
+    #[case(
+        Platform::Cisco,
+        "\
+no ipv6 prefix-list NN
+! generated prefix-list NN is empty
+ipv6 prefix-list NN deny ::/0
+"
+    )]
+    #[case(
+        Platform::Arista,
+        "\
+no ipv6 prefix-list NN
+ipv6 prefix-list NN
+    deny ::/0
+"
+    )]
+    #[case(
+        Platform::CiscoXr,
+        "\
+no prefix-set NN
+prefix-set NN
+end-set
+"
+    )]
+    #[case(Platform::Bird, "NN = [];\n")]
+    fn render_empty(#[case] platform: Platform, #[case] expected: &str) {
+        let prefix_list = PrefixList::new("NN").with_ip_version(IpVersion::V6);
+        assert_eq!(
+            prefix_list.render(platform, ranges(&["192.0.2.0/24"])),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(
+        &["192.0.2.0/24^+", "192.0.2.0/25", "192.0.2.128/26^27-28", "192.0.2.0/24"],
+        &["192.0.2.0/24^+"]
+    )]
+    #[case(
+        &["10.0.0.0/8^16-24", "10.1.0.0/16^16-24", "10.1.0.0/16^+", "10.2.0.0/16"],
+        &["10.0.0.0/8^16-24", "10.1.0.0/16^+"]
+    )]
+    #[case(
+        &["10.0.0.0/8^16", "10.1.0.0/24", "11.0.0.0/8"],
+        &["10.0.0.0/8^16", "10.1.0.0/24", "11.0.0.0/8"]
+    )]
+    #[case(
+        &["192.0.2.0/25", "192.0.2.128/25", "192.0.2.0/24"],
+        &["192.0.2.0/24^24-25"]
+    )]
+    fn aggregation_reduces_entries(#[case] input: &[&str], #[case] expected: &[&str]) {
+        let prefix_list = PrefixList::new("NN").with_aggregation();
+        let expected: Vec<Entry> = PrefixList::new("NN").entries(ranges(expected));
+        assert_eq!(prefix_list.entries(ranges(input)), expected);
+    }
+
+    #[rstest]
+    #[case(&["192.0.2.0/24^+"], 28, &["192.0.2.0/24^24-28"])]
+    #[case(&["192.0.2.0/24^26"], 24, &[])]
+    #[case(&["192.0.2.0/24^-", "198.51.100.0/24"], 25, &["192.0.2.0/24^25", "198.51.100.0/24"])]
+    fn max_length_limits_ranges(
+        #[case] input: &[&str],
+        #[case] max_length: u8,
+        #[case] expected: &[&str],
+    ) {
+        let prefix_list = PrefixList::new("NN").with_max_length(max_length);
+        let expected: Vec<Entry> = PrefixList::new("NN").entries(ranges(expected));
+        assert_eq!(prefix_list.entries(ranges(input)), expected);
+    }
+}
@@ -5,13 +5,39 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-pub use attribute::{Attribute, Name, Value};
-pub use error::{AttributeError, ParseError};
+pub use asn::{AsTerm, Asn};
+pub use attribute::{Attribute, Comment, Name, Value};
+#[cfg(feature = "serde")]
+pub use error::SerdeError;
+pub use error::{
+    AsnError, AttributeError, ClassError, DatabaseError, GenerateError, ParseError, ParseErrorKind,
+    ParseWarning, ParseWarningKind, PrefixError, ReadError, SetNameError,
+};
+#[cfg(feature = "json")]
+pub use object::JsonLayout;
 pub use object::Object;
-pub use parser::{parse_object, parse_whois_response};
+pub use parser::{
+    iter_objects, parse_object, parse_whois_response, parse_whois_response_recovering,
+    read_objects, ObjectIter, ObjectReader, Parsed, ParserOptions, Recovered,
+};
+pub use prefix::{AddressPrefix, AddressPrefixRange, RangeOperator};
+#[cfg(feature = "serde")]
+pub use serialization::{from_object, from_str, to_object, to_string};
+pub use set_name::{SetClass, SetName};
 
+mod asn;
 mod attribute;
+pub mod class;
+pub mod database;
 #[allow(clippy::module_name_repetitions)]
 mod error;
+pub mod format;
+pub mod generate;
 mod object;
 mod parser;
+pub mod policy;
+mod prefix;
+pub mod schema;
+#[cfg(feature = "serde")]
+mod serialization;
+mod set_name;
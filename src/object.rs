@@ -1,10 +1,11 @@
 use std::{
+    borrow::Cow,
     fmt,
     ops::{Deref, Index},
 };
 
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use super::Attribute;
 
@@ -175,14 +176,32 @@ use super::Attribute;
 /// );
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
+///
+/// Objects can be deserialized from the same representation, validating names and values like
+/// their `FromStr` implementations and borrowing from the input where possible.
+/// ```
+/// # use rpsl::Object;
+/// # #[cfg(feature = "json")]
+/// # {
+/// let json = r#"{"attributes": [{"name": "role", "values": ["ACME Company"]}]}"#;
+/// let role_acme: Object = serde_json::from_str(json)?;
+/// assert_eq!(role_acme.get("role"), vec!["ACME Company"]);
+/// # }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(clippy::len_without_is_empty)]
 pub struct Object<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
     attributes: Vec<Attribute<'a>>,
-    /// Contains the source if the object was created by parsing RPSL.
+    /// Contains the source if the object was created by parsing RPSL and has not been changed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    source: Option<Cow<'a, str>>,
+    /// Contains the text following the attributes if the object was created by parsing RPSL,
+    /// usually the blank line terminating it.
     #[cfg_attr(feature = "serde", serde(skip))]
-    source: Option<&'a str>,
+    tail: Option<Cow<'a, str>>,
 }
 
 impl Object<'_> {
@@ -209,14 +228,59 @@ impl Object<'_> {
         Object {
             attributes,
             source: None,
+            tail: None,
         }
     }
 
     /// Create a new RPSL object from a text source and it's corresponding parsed attributes.
     pub(crate) fn from_parsed<'a>(source: &'a str, attributes: Vec<Attribute<'a>>) -> Object<'a> {
+        let attributes_length: Option<usize> = attributes
+            .iter()
+            .map(|attribute| attribute.source().map(str::len))
+            .sum();
         Object {
+            tail: attributes_length
+                .and_then(|length| source.get(length..))
+                .map(Cow::Borrowed),
             attributes,
-            source: Some(source),
+            source: Some(Cow::Borrowed(source)),
+        }
+    }
+
+    /// Convert the object into one that owns all of its data and no longer borrows from the
+    /// RPSL it was parsed from.
+    ///
+    /// If the object was created by parsing RPSL, each attribute keeps a copy of the lines it was
+    /// parsed from, so the owned object still displays exactly like the text it was parsed from.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{parse_object, Object};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let owned: Object<'static> = {
+    ///     let rpsl = String::from("
+    /// mntner:  MAINT-AS3257
+    /// source:  RIPE
+    ///
+    /// ");
+    ///     parse_object(&rpsl)?.into_owned()
+    /// };
+    /// assert_eq!(owned.get("mntner"), vec!["MAINT-AS3257"]);
+    /// assert_eq!(owned.to_string(), "mntner:  MAINT-AS3257\nsource:  RIPE\n\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn into_owned(self) -> Object<'static> {
+        Object {
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(Attribute::into_owned)
+                .collect(),
+            // The attributes and the tail together make up the source of the whole object.
+            source: None,
+            tail: self.tail.map(|tail| Cow::Owned(tail.into_owned())),
         }
     }
 
@@ -226,6 +290,22 @@ impl Object<'_> {
         self.attributes.len()
     }
 
+    /// The class of the object, as determined by the name of its first attribute.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::object;
+    /// let mntner = object! {
+    ///     "mntner": "MAINT-AS3257";
+    ///     "source": "RIPE";
+    /// };
+    /// assert_eq!(mntner.class(), Some("mntner"));
+    /// ```
+    #[must_use]
+    pub fn class(&self) -> Option<&str> {
+        self.attributes.first().map(|a| &*a.name)
+    }
+
     /// Get the value(s) of specific attribute(s).
     #[must_use]
     pub fn get(&self, name: &str) -> Vec<&str> {
@@ -241,14 +321,197 @@ impl Object<'_> {
     #[allow(clippy::missing_panics_doc)]
     #[must_use]
     /// Serialize the object into a JSON value.
+    ///
+    /// This is the lossless [`JsonLayout::Attributes`] layout, which can be deserialized into
+    /// an object again.
     pub fn json(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap()
     }
 
+    /// Serialize the object into a JSON value using the given layout.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{object, JsonLayout};
+    /// # use serde_json::json;
+    /// let as_set = object! {
+    ///     "as-set": "AS-EXAMPLE";
+    ///     "members": "AS1, AS2";
+    ///     "members": "AS3";
+    ///     "remarks": "Line 1", "", "Line 3";
+    /// };
+    /// assert_eq!(
+    ///     as_set.json_with(JsonLayout::Map),
+    ///     json!({
+    ///         "as-set": ["AS-EXAMPLE"],
+    ///         "members": ["AS1, AS2", "AS3"],
+    ///         "remarks": ["Line 1", "Line 3"],
+    ///     })
+    /// );
+    /// assert_eq!(
+    ///     as_set.json_with(JsonLayout::Joined),
+    ///     json!({
+    ///         "as-set": "AS-EXAMPLE",
+    ///         "members": ["AS1, AS2", "AS3"],
+    ///         "remarks": "Line 1\n\nLine 3",
+    ///     })
+    /// );
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    #[must_use]
+    pub fn json_with(&self, layout: JsonLayout) -> serde_json::Value {
+        match layout {
+            JsonLayout::Attributes => self.json(),
+            JsonLayout::Map => self.json_by_name(|attributes| {
+                attributes
+                    .iter()
+                    .flat_map(|attribute| attribute.value.with_content())
+                    .collect::<Vec<&str>>()
+                    .into()
+            }),
+            JsonLayout::Joined => self.json_by_name(|attributes| {
+                let mut values: Vec<serde_json::Value> = attributes
+                    .iter()
+                    .map(|attribute| {
+                        attribute
+                            .value
+                            .values()
+                            .into_iter()
+                            .map(Option::unwrap_or_default)
+                            .collect::<Vec<&str>>()
+                            .join("\n")
+                            .into()
+                    })
+                    .collect();
+                if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    values.into()
+                }
+            }),
+        }
+    }
+
+    /// A JSON object mapping the lowercase name of each attribute to a value built from all
+    /// attributes of that name, in the order the names first appear.
+    #[cfg(feature = "json")]
+    fn json_by_name(
+        &self,
+        value: impl Fn(&[&Attribute]) -> serde_json::Value,
+    ) -> serde_json::Value {
+        let mut groups: Vec<(String, Vec<&Attribute>)> = Vec::new();
+        for attribute in &self.attributes {
+            let name = attribute.name.to_ascii_lowercase();
+            match groups.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, group)) => group.push(attribute),
+                None => groups.push((name, vec![attribute])),
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(name, attributes)| (name, value(&attributes)))
+            .collect::<serde_json::Map<String, serde_json::Value>>()
+            .into()
+    }
+
     /// Access the source field for use in tests.
     #[cfg(test)]
     pub(crate) fn source(&self) -> Option<&str> {
-        self.source
+        self.source.as_deref()
+    }
+}
+
+impl<'a> Object<'a> {
+    /// Mutable access to the attributes of the object, e.g. to change, add or remove attributes.
+    ///
+    /// If the object was created by parsing RPSL, the attributes that remain unchanged are
+    /// still displayed exactly like the lines they were parsed from, including their spacing,
+    /// continuation characters and comments, while changed attributes are formatted anew,
+    /// keeping their comments.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::parse_object;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut object = parse_object("
+    /// aut-num:   AS65536
+    /// as-name:   EXAMPLE   # to be renamed
+    /// remarks:   Peering
+    /// +          Policy
+    /// source:    RIPE
+    ///
+    /// ")?;
+    /// object.attributes_mut()[1].value = "EXAMPLE-NET".parse()?;
+    /// object.attributes_mut().pop();
+    /// assert_eq!(
+    ///     object.to_string(),
+    ///     concat!(
+    ///         "aut-num:   AS65536\n",
+    ///         "as-name:        EXAMPLE-NET # to be renamed\n",
+    ///         "remarks:   Peering\n",
+    ///         "+          Policy\n",
+    ///         "\n",
+    ///     )
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute<'a>> {
+        // The source of the whole object no longer describes it once it is changed.
+        self.source = None;
+        &mut self.attributes
+    }
+}
+
+impl<'a> Object<'a> {
+    /// Iterate over the attributes with the given name, ignoring ASCII case.
+    pub(crate) fn attributes_named<'s, 'n>(
+        &'s self,
+        name: &'n str,
+    ) -> impl Iterator<Item = &'s Attribute<'a>> + 'n
+    where
+        's: 'n,
+    {
+        self.attributes
+            .iter()
+            .filter(move |a| a.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The first line with content of the first attribute with the given name.
+    pub(crate) fn first(&self, name: &str) -> Option<&str> {
+        self.attributes_named(name)
+            .flat_map(|a| a.value.with_content())
+            .next()
+    }
+
+    /// The lines with content of all attributes with the given name.
+    pub(crate) fn lines(&self, name: &str) -> Vec<&str> {
+        self.attributes_named(name)
+            .flat_map(|a| a.value.with_content())
+            .collect()
+    }
+
+    /// The comma separated list items contained in all attributes with the given name.
+    pub(crate) fn list(&self, name: &str) -> Vec<&str> {
+        self.attributes_named(name)
+            .flat_map(|a| a.value.with_content())
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+
+    /// The values of all attributes with the given name, with the lines of each value joined by a
+    /// single space.
+    pub(crate) fn joined(&self, name: &str) -> Vec<Cow<'_, str>> {
+        self.attributes_named(name)
+            .filter_map(|a| match a.value.with_content()[..] {
+                [] => None,
+                [line] => Some(Cow::Borrowed(line)),
+                ref lines => Some(Cow::Owned(lines.join(" "))),
+            })
+            .collect()
     }
 }
 
@@ -288,17 +551,66 @@ impl PartialEq for Object<'_> {
 impl fmt::Display for Object<'_> {
     /// Display the object as RPSL.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(source) = self.source {
-            write!(f, "{source}")
-        } else {
-            for attribute in &self.attributes {
-                write!(f, "{attribute}")?;
+        if let Some(source) = &self.source {
+            return write!(f, "{source}");
+        }
+
+        for (i, attribute) in self.attributes.iter().enumerate() {
+            match attribute.intact_source() {
+                Some(source) => {
+                    f.write_str(source)?;
+                    // The last line of a leniently parsed object may lack a newline.
+                    if !source.ends_with('\n') && i + 1 < self.attributes.len() {
+                        writeln!(f)?;
+                    }
+                }
+                None => write!(f, "{attribute}")?,
             }
-            writeln!(f)
+        }
+        match &self.tail {
+            Some(tail) => write!(f, "{tail}"),
+            None => writeln!(f),
         }
     }
 }
 
+/// The layout of an object serialized into JSON using [`Object::json_with`].
+///
+/// Except for [`JsonLayout::Attributes`], attributes are mapped by their name in lowercase, so
+/// that all attributes whose names only differ in case share a key. Keys are in the order their
+/// names first appear in the object. Comments are never included.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum JsonLayout {
+    /// A list of attributes in their original order, each with its name and the lines of its
+    /// value, using `null` for empty lines.
+    ///
+    /// Nothing but comments is lost, so that objects can be deserialized from this layout.
+    /// ```json
+    /// {"attributes": [{"name": "remarks", "values": ["Line 1", null, "Line 3"]}]}
+    /// ```
+    #[default]
+    Attributes,
+    /// Each attribute name mapped to a list of the lines of all attributes of that name.
+    ///
+    /// Lines of repeated attributes follow each other in order, while empty lines are left out,
+    /// like with [`Object::get`]. An attribute with only empty lines maps to an empty list.
+    /// ```json
+    /// {"remarks": ["Line 1", "Line 3"]}
+    /// ```
+    Map,
+    /// Each attribute name mapped to its value, with the lines of multi-line values joined by
+    /// `\n`, like in the JSON output of `IRRd`.
+    ///
+    /// Empty lines are kept as empty strings between the other lines, so that an empty value is
+    /// an empty string. Repeated attributes map to a list of their values instead.
+    /// ```json
+    /// {"remarks": "Line 1\n\nLine 3"}
+    /// ```
+    Joined,
+}
+
 /// Creates an [`Object`] containing the given attributes.
 ///
 /// - Create an [`Object`] containing only single value attributes:
@@ -589,6 +901,106 @@ mod tests {
         assert_eq!(json, expected);
     }
 
+    #[test]
+    #[cfg(feature = "json")]
+    fn object_json_round_trip() {
+        let object = crate::parse_object(concat!(
+            "aut-num:  AS65536\n",
+            "remarks:  Peering\n",
+            "+\n",
+            "          Policy # comments are not serialized\n",
+            "\n",
+        ))
+        .unwrap();
+        let json = object.json().to_string();
+
+        let deserialized: Object = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, object);
+        assert!(matches!(
+            deserialized[0].value,
+            crate::Value::SingleLine(Some(Cow::Borrowed(_)))
+        ));
+        assert_eq!(
+            deserialized.to_string(),
+            "aut-num:        AS65536\nremarks:        Peering\n \n                Policy\n\n"
+        );
+    }
+
+    #[rstest]
+    #[case(
+        JsonLayout::Attributes,
+        json!({
+            "attributes": [
+                { "name": "route", "values": ["192.0.2.0/24"] },
+                { "name": "descr", "values": ["A", null, "B"] },
+                { "name": "DESCR", "values": ["C"] },
+                { "name": "remarks", "values": [null] },
+            ]
+        })
+    )]
+    #[case(
+        JsonLayout::Map,
+        json!({
+            "route": ["192.0.2.0/24"],
+            "descr": ["A", "B", "C"],
+            "remarks": [],
+        })
+    )]
+    #[case(
+        JsonLayout::Joined,
+        json!({
+            "route": "192.0.2.0/24",
+            "descr": ["A\n\nB", "C"],
+            "remarks": "",
+        })
+    )]
+    #[cfg(feature = "json")]
+    fn object_json_layouts(#[case] layout: JsonLayout, #[case] expected: serde_json::Value) {
+        let object = object! {
+            "route": "192.0.2.0/24";
+            "descr": "A", "", "B";
+            "DESCR": "C";
+            "remarks": "";
+        };
+        assert_eq!(object.json_with(layout), expected);
+    }
+
+    #[rstest]
+    #[case(
+        JsonLayout::Map,
+        r#"{"route":["192.0.2.0/24"],"descr":["A","B","C"],"remarks":[]}"#
+    )]
+    #[case(
+        JsonLayout::Joined,
+        r#"{"route":"192.0.2.0/24","descr":["A\n\nB","C"],"remarks":""}"#
+    )]
+    #[cfg(feature = "json")]
+    fn object_json_layouts_keep_attribute_order(
+        #[case] layout: JsonLayout,
+        #[case] expected: &str,
+    ) {
+        let object = object! {
+            "route": "192.0.2.0/24";
+            "descr": "A", "", "B";
+            "DESCR": "C";
+            "remarks": "";
+        };
+        assert_eq!(
+            serde_json::to_string(&object.json_with(layout)).unwrap(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(r#"{"attributes": [{"name": "1role", "values": ["ACME"]}]}"#)]
+    #[case(r#"{"attributes": [{"name": "role", "values": []}]}"#)]
+    #[case(r#"{"attributes": [{"name": "role"}]}"#)]
+    #[case(r#"{"attributes": {"role": ["ACME"]}}"#)]
+    #[cfg(feature = "json")]
+    fn object_json_invalid(#[case] json: &str) {
+        assert!(serde_json::from_str::<Object>(json).is_err());
+    }
+
     #[rstest]
     #[case(
         Object::from_parsed(
@@ -733,6 +1145,95 @@ mod tests {
         assert_eq!(object.to_string(), expected);
     }
 
+    #[test]
+    /// Owned objects display like the source they were parsed from without keeping a copy of
+    /// the whole source in addition to the one of each attribute.
+    fn object_into_owned_displays_like_source() {
+        let source = String::from(concat!(
+            "role:    ACME Company\n",
+            "address: Packet Street 6 # old\n",
+            "\n"
+        ));
+        let borrowed = crate::parse_object(&source).unwrap();
+        let expected = borrowed.clone();
+
+        let owned = borrowed.into_owned();
+        assert_eq!(owned.source(), None);
+        assert_eq!(owned.to_string(), source);
+        assert_eq!(owned, expected);
+    }
+
+    #[rstest]
+    #[case(
+        concat!(
+            "aut-num:\tAS65536 # example\r\n",
+            "AS-Name:   EXAMPLE\r\n",
+            "# renamed in 2024\r\n",
+            "remarks:   Peering\r\n",
+            "\t          Policy\r\n",
+            "source:    RIPE\r\n",
+            "\r\n",
+        ),
+        concat!(
+            "aut-num:\tAS65536 # example\r\n",
+            "AS-Name:   EXAMPLE\r\n",
+            "# renamed in 2024\r\n",
+            "remarks:        Changed\n",
+            "source:    RIPE\r\n",
+            "\r\n",
+        )
+    )]
+    #[case(
+        "aut-num: AS65536\nremarks:  Peering  \n Policy",
+        "aut-num: AS65536\nremarks:        Changed\n"
+    )]
+    #[case(
+        "remarks: Peering\naut-num: AS65536",
+        "remarks:        Changed\naut-num: AS65536"
+    )]
+    /// Changing an attribute of a parsed object leaves the other attributes unchanged.
+    fn changed_objects_keep_unchanged_attributes(#[case] rpsl: &str, #[case] expected: &str) {
+        let mut object = crate::ParserOptions::lenient()
+            .parse_object(rpsl)
+            .unwrap()
+            .value;
+        assert_eq!(object.to_string(), rpsl);
+
+        let remarks = object
+            .attributes_mut()
+            .iter_mut()
+            .find(|attribute| attribute.name == "remarks")
+            .unwrap();
+        remarks.value = "Changed".parse().unwrap();
+        assert_eq!(object.source(), None);
+        assert_eq!(object.to_string(), expected);
+        assert_eq!(object.into_owned().to_string(), expected);
+    }
+
+    #[test]
+    /// Attributes following one without a newline start on a line of their own.
+    fn added_attributes_start_on_new_line() {
+        let mut object = crate::ParserOptions::lenient()
+            .parse_object("aut-num: AS65536")
+            .unwrap()
+            .value;
+        object
+            .attributes_mut()
+            .push(Attribute::unchecked_single("source", "RIPE"));
+        assert_eq!(
+            object.to_string(),
+            "aut-num: AS65536\nsource:         RIPE\n"
+        );
+    }
+
+    #[test]
+    fn object_into_owned_without_source() {
+        let object = Object::new(vec![Attribute::unchecked_single("role", "ACME Company")]);
+        let owned = object.clone().into_owned();
+        assert!(owned.source.is_none());
+        assert_eq!(owned, object);
+    }
+
     #[rstest]
     #[case(
         object! {
@@ -1,10 +1,6 @@
-use winnow::{
-    ascii::multispace0,
-    combinator::{delimited, repeat},
-    Parser,
-};
+use std::io::BufRead;
 
-use super::core::{object_block, object_block_padded};
+use super::{iter::ObjectIter, reader::ObjectReader, recover::Recovered, ParserOptions};
 use crate::{Object, ParseError};
 
 /// Parse RPSL into an [`Object`], borrowing from the source.
@@ -125,10 +121,9 @@ use crate::{Object, ParseError};
 /// # Ok(())
 /// # }
 /// ```
-pub fn parse_object(rpsl: &str) -> Result<Object, ParseError> {
-    let block_parser = object_block();
-    let object = delimited(multispace0, block_parser, multispace0).parse(rpsl)?;
-    Ok(object)
+pub fn parse_object(rpsl: &str) -> Result<Object<'_>, ParseError> {
+    let parsed = ParserOptions::strict().parse_object(rpsl)?;
+    Ok(parsed.value)
 }
 
 /// Parse a WHOIS server response into [`Object`]s contained within.
@@ -191,8 +186,183 @@ pub fn parse_object(rpsl: &str) -> Result<Object, ParseError> {
 /// );
 /// # Ok(())
 /// # }
-pub fn parse_whois_response(response: &str) -> Result<Vec<Object>, ParseError> {
-    let block_parser = object_block_padded(object_block());
-    let objects = repeat(1.., block_parser).parse(response)?;
-    Ok(objects)
+pub fn parse_whois_response(response: &str) -> Result<Vec<Object<'_>>, ParseError> {
+    let parsed = ParserOptions::strict().parse_whois_response(response)?;
+    Ok(parsed.value)
+}
+
+/// Parse a WHOIS server response into the [`Object`]s contained within, collecting an error
+/// for every object that cannot be parsed instead of failing on the first one.
+///
+/// After an error, parsing continues after the next blank line, so that a single broken object
+/// does not hide the problems of the objects following it. Positions of the errors refer to
+/// the whole response.
+///
+/// # Examples
+/// ```
+/// # use rpsl::{parse_whois_response_recovering, object, ParseErrorKind};
+/// let update = "
+/// mntner:         MAINT-AS3257
+/// source:         RIPE
+///
+/// mntner;         INVALID
+/// source:         RIPE
+///
+/// mntner:         MAINT-AS1299
+/// source:         RIPE
+/// remarks-:       INVALID
+///
+/// ";
+/// let recovered = parse_whois_response_recovering(update);
+/// assert_eq!(
+///     recovered.objects,
+///     vec![object! {
+///         "mntner": "MAINT-AS3257";
+///         "source": "RIPE";
+///     }]
+/// );
+/// let errors: Vec<_> = recovered.errors.iter().map(|err| (err.line(), err.kind())).collect();
+/// assert_eq!(
+///     errors,
+///     [(5, ParseErrorKind::InvalidSeparator), (10, ParseErrorKind::InvalidName)]
+/// );
+/// ```
+#[must_use]
+pub fn parse_whois_response_recovering(response: &str) -> Recovered<'_> {
+    iter_objects(response).collect()
+}
+
+/// Lazily parse RPSL containing any number of objects, such as a WHOIS server response
+/// or an entire database dump.
+///
+/// In contrast to [`parse_whois_response`], objects are parsed one at a time as the returned
+/// iterator is advanced, keeping memory usage bounded regardless of the size of the input.
+/// An object that fails to parse results in a [`ParseError`] being yielded in its place,
+/// after which parsing continues with the next object.
+///
+/// # Examples
+/// ```
+/// # use rpsl::{iter_objects, object};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let dump = "
+/// mntner:         MAINT-AS3257
+/// source:         RIPE
+///
+/// mntner;         INVALID
+/// source:         RIPE
+///
+/// mntner:         MAINT-AS1299
+/// source:         RIPE
+///
+/// ";
+/// let mut objects = iter_objects(dump);
+/// assert_eq!(
+///     objects.next().unwrap()?,
+///     object! {
+///         "mntner": "MAINT-AS3257";
+///         "source": "RIPE";
+///     }
+/// );
+/// assert!(objects.next().unwrap().is_err());
+/// assert_eq!(
+///     objects.next().unwrap()?,
+///     object! {
+///         "mntner": "MAINT-AS1299";
+///         "source": "RIPE";
+///     }
+/// );
+/// assert!(objects.next().is_none());
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn iter_objects(rpsl: &str) -> ObjectIter<'_> {
+    ParserOptions::strict().iter_objects(rpsl)
+}
+
+/// Read RPSL containing any number of objects from a [`BufRead`] source, such as a file,
+/// a pipe or a decompressor.
+///
+/// The returned iterator reads one object at a time and yields owned [`Object`]s that are
+/// independent of the source they were read from. An object that fails to parse results in a
+/// [`ReadError::Parse`](crate::ReadError::Parse) being yielded in its place, after which reading
+/// continues with the next object. Objects that are not valid UTF-8 are decoded as Latin-1.
+///
+/// # Examples
+/// ```
+/// # use std::io::Cursor;
+/// # use rpsl::{read_objects, object, Object};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let source = Cursor::new("
+/// mntner:         MAINT-AS3257
+/// source:         RIPE
+///
+/// mntner:         MAINT-AS1299
+/// source:         RIPE
+///
+/// ");
+/// let objects: Vec<Object<'static>> = read_objects(source).collect::<Result<_, _>>()?;
+/// assert_eq!(
+///     objects,
+///     vec![
+///         object! {
+///             "mntner": "MAINT-AS3257";
+///             "source": "RIPE";
+///         },
+///         object! {
+///             "mntner": "MAINT-AS1299";
+///             "source": "RIPE";
+///         },
+///     ]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn read_objects<R: BufRead>(reader: R) -> ObjectReader<R> {
+    ParserOptions::strict().read_objects(reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::ParseErrorKind;
+
+    #[rstest]
+    #[case("role: A\nnic;hdl: B\n\n", ParseErrorKind::InvalidSeparator, (2, 4))]
+    #[case("role: A\n  nic-hdl: B\n  \u{2603}\n\n", ParseErrorKind::NonExtendedAsciiValue, (3, 3))]
+    #[case("role: A\nnic-hdl: B\n", ParseErrorKind::MissingNewline, (3, 1))]
+    #[case("role: A\n\nrole: B\n\n", ParseErrorKind::UnexpectedContent, (3, 1))]
+    #[case("\n\n", ParseErrorKind::MissingNewline, (3, 1))]
+    fn parse_object_error_position(
+        #[case] rpsl: &str,
+        #[case] kind: ParseErrorKind,
+        #[case] position: (usize, usize),
+    ) {
+        let err = parse_object(rpsl).unwrap_err();
+        assert_eq!(
+            (err.kind(), err.line(), err.column()),
+            (kind, position.0, position.1)
+        );
+    }
+
+    #[rstest]
+    #[case("% note\n\nrole: A\n\nrole: B\nnic-hdl: \x01\n\n", ParseErrorKind::ControlCharacter, (6, 10))]
+    #[case("role: A\n\nrole: B\n+\u{2603}\n\n", ParseErrorKind::NonExtendedAsciiValue, (4, 2))]
+    #[case("role: A\n\n-role: B\n\n", ParseErrorKind::InvalidName, (3, 1))]
+    #[case("role: A\r\n\r\nrole: B\r\nnic-hdl: \x01\r\n\r\n", ParseErrorKind::ControlCharacter, (4, 10))]
+    #[case("role: A\nnic-hdl: B\n\nrole: C\nnic;hdl: D\n\n", ParseErrorKind::InvalidSeparator, (5, 4))]
+    fn parse_whois_response_error_position(
+        #[case] rpsl: &str,
+        #[case] kind: ParseErrorKind,
+        #[case] position: (usize, usize),
+    ) {
+        let err = parse_whois_response(rpsl).unwrap_err();
+        assert_eq!(
+            (err.kind(), err.line(), err.column()),
+            (kind, position.0, position.1)
+        );
+    }
 }
@@ -1,31 +1,178 @@
+use std::mem;
+
 use winnow::{
-    ascii::{newline, space0},
-    combinator::{alt, delimited, peek, preceded, repeat, separated_pair, terminated},
-    error::{AddContext, ContextError, ParserError, StrContext, StrContextValue},
+    ascii::{line_ending, space0},
+    combinator::{alt, cut_err, delimited, eof, opt, peek, preceded, repeat, terminated},
+    error::{AddContext, ContextError, ErrMode, ErrorKind, ParserError},
+    stream::{Offset, Stateful, Stream},
     token::{one_of, take_while},
     Parser,
 };
 
-use crate::{Attribute, Name, Object, Value};
+use super::ParserOptions;
+use crate::{
+    error::InvalidValueError, Attribute, Comment, Name, Object, ParseError, ParseErrorKind,
+    ParseWarning, ParseWarningKind, Value,
+};
+
+/// The input of the parsers in this module, carrying the [`State`] of a parse.
+pub(crate) type Input<'s> = Stateful<&'s str, State<'s>>;
+
+/// The state of a parse, containing the options it is done with and the deviations from RPSL
+/// accepted so far.
+#[derive(Debug)]
+pub(crate) struct State<'s> {
+    lenient: bool,
+    /// The accepted deviations, along with the text they were found in.
+    deviations: Vec<(ParseWarningKind, &'s str)>,
+}
+
+impl<'s> State<'s> {
+    pub(crate) fn new(options: ParserOptions) -> Self {
+        Self {
+            lenient: options.is_lenient(),
+            deviations: Vec::new(),
+        }
+    }
+
+    /// Record a deviation found in the given text, if deviations are accepted.
+    fn warn(&mut self, kind: ParseWarningKind, text: &'s str) {
+        if self.lenient {
+            self.deviations.push((kind, text));
+        }
+    }
+
+    /// Record a deviation for the first character of the text that is not part of the extended
+    /// ASCII set, if any.
+    fn warn_non_extended_ascii(&mut self, text: &'s str) {
+        let found = text.char_indices().find(|(_, c)| {
+            matches!(
+                Value::validate_char(*c),
+                Err(InvalidValueError::NonExtendedAscii)
+            )
+        });
+        if let Some((i, c)) = found {
+            self.warn(
+                ParseWarningKind::NonExtendedAsciiValue,
+                &text[i..i + c.len_utf8()],
+            );
+        }
+    }
+
+    /// Take the deviations recorded so far as warnings, in order of their appearance within
+    /// the given input.
+    fn take_warnings(&mut self, input: &'s str) -> Vec<ParseWarning> {
+        let mut warnings: Vec<ParseWarning> = mem::take(&mut self.deviations)
+            .into_iter()
+            .map(|(kind, text)| {
+                let offset = text.offset_from(&input);
+                ParseWarning::new(kind, input, offset..offset + text.len())
+            })
+            .collect();
+        warnings.sort_by_key(ParseWarning::offset);
+        warnings
+    }
+}
+
+/// Run the given parser on the remaining input, which has to be consumed entirely.
+///
+/// The positions of errors and warnings refer to the given text, which the remaining input is
+/// a suffix of.
+pub(crate) fn parse_to_end<'s, O, P>(
+    mut parser: P,
+    input: &mut Input<'s>,
+    text: &'s str,
+) -> Result<(O, Vec<ParseWarning>), ParseError>
+where
+    P: Parser<Input<'s>, O, ContextError<Cause<'s>>>,
+{
+    match terminated(parser.by_ref(), eof).parse_next(input) {
+        Ok(output) => Ok((output, input.state.take_warnings(text))),
+        Err(err) => {
+            let err = err
+                .into_inner()
+                .expect("complete parsers should not report `ErrMode::Incomplete(_)`");
+            Err(parse_error(text, text.len() - input.input.len(), &err))
+        }
+    }
+}
+
+/// Run the given parser on the entire text, parsing it with the given options.
+pub(crate) fn parse<'s, O, P>(
+    parser: P,
+    text: &'s str,
+    options: ParserOptions,
+) -> Result<(O, Vec<ParseWarning>), ParseError>
+where
+    P: Parser<Input<'s>, O, ContextError<Cause<'s>>>,
+{
+    let mut input = Input {
+        input: text,
+        state: State::new(options),
+    };
+    parse_to_end(parser, &mut input, text)
+}
+
+/// The context attached to errors of the parsers in this module, from which a [`ParseError`]
+/// is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Cause<'s> {
+    /// The kind of problem found at the position the parser failed.
+    Kind(ParseErrorKind),
+    /// The name of the attribute that contains the problem.
+    Attribute(&'s str),
+}
 
 /// Generate an object block parser.
 /// As per [RFC 2622](https://datatracker.ietf.org/doc/html/rfc2622#section-2), an RPSL object
 /// is textually represented as a list of attribute-value pairs that ends when a blank line is encountered.
-pub fn object_block<'s, E>() -> impl Parser<&'s str, Object<'s>, E>
+/// Lines may end with either `\n` or `\r\n`, which is never part of a value.
+///
+/// Once a line has been found to start an object, the object cannot be backtracked from, so
+/// that errors point to the position of the problem rather than the start of the object.
+/// When parsing leniently, the blank line may be missing at the end of the input.
+pub fn object_block<'s, E>() -> impl Parser<Input<'s>, Object<'s>, E>
 where
-    E: ParserError<&'s str> + AddContext<&'s str, StrContext>,
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
 {
-    terminated(repeat(1.., attribute()), newline)
-        .with_taken()
-        .map(|(attributes, source)| Object::from_parsed(source, attributes))
+    move |input: &mut Input<'s>| {
+        let source = input.input;
+        let mut attributes = Vec::new();
+        loop {
+            if !attributes.is_empty() && opt(newline()).parse_next(input)?.is_some() {
+                break;
+            }
+            if input.is_empty() {
+                if !attributes.is_empty() && input.state.lenient {
+                    let end = input.input;
+                    input.state.warn(ParseWarningKind::MissingBlankLine, end);
+                    break;
+                }
+                let err = ErrMode::from_error_kind(input, ErrorKind::Eof).add_context(
+                    input,
+                    &input.checkpoint(),
+                    Cause::Kind(ParseErrorKind::MissingNewline),
+                );
+                // Without any attributes, there is no object to commit to yet.
+                return Err(if attributes.is_empty() {
+                    err
+                } else {
+                    err.cut()
+                });
+            }
+            attributes.push(cut_err(attribute()).parse_next(input)?);
+        }
+        let source = &source[..source.len() - input.input.len()];
+        Ok(Object::from_parsed(source, attributes))
+    }
 }
 
 /// Generate a parser that extends the given object block parser to consume optional padding
 /// server messages or newlines.
-pub fn object_block_padded<'s, P, E>(block_parser: P) -> impl Parser<&'s str, Object<'s>, E>
+pub fn object_block_padded<'s, P, E>(block_parser: P) -> impl Parser<Input<'s>, Object<'s>, E>
 where
-    P: Parser<&'s str, Object<'s>, E>,
-    E: ParserError<&'s str>,
+    P: Parser<Input<'s>, Object<'s>, E>,
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
 {
     delimited(
         consume_opt_messages_or_newlines(),
@@ -35,115 +182,322 @@ where
 }
 
 /// Generate a parser that consumes optional messages or newlines.
-fn consume_opt_messages_or_newlines<'s, E>() -> impl Parser<&'s str, (), E>
+/// Comment lines preceding an object are consumed as well, since they are not part of it.
+pub fn consume_opt_messages_or_newlines<'s, E>() -> impl Parser<Input<'s>, (), E>
 where
-    E: ParserError<&'s str>,
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
 {
-    repeat(0.., alt((newline.void(), server_message().void())))
+    repeat(
+        0..,
+        alt((
+            newline().void(),
+            server_message().void(),
+            comment_line().void(),
+        )),
+    )
 }
 
 // A response code or message sent by the whois server.
 // Starts with the "%" character and extends until the end of the line.
 // In contrast to RPSL, characters are not limited to ASCII.
-fn server_message<'s, E>() -> impl Parser<&'s str, &'s str, E>
+fn server_message<'s, E>() -> impl Parser<Input<'s>, &'s str, E>
 where
-    E: ParserError<&'s str>,
+    E: ParserError<Input<'s>>,
 {
     delimited(
         ('%', space0),
         take_while(0.., |c: char| !c.is_control()),
-        newline,
+        newline(),
     )
 }
 
 // Generate an attribute parser.
 // The attributes name and value are separated by a colon and optional spaces.
-fn attribute<'s, E>() -> impl Parser<&'s str, Attribute<'s>, E>
+// The parsed attribute keeps the lines it was parsed from, including comment lines.
+fn attribute<'s, E>() -> impl Parser<Input<'s>, Attribute<'s>, E>
 where
-    E: ParserError<&'s str> + AddContext<&'s str, StrContext>,
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
 {
-    separated_pair(
-        attribute_name(),
-        (
-            ':'.context(StrContext::Label("separator"))
-                .context(StrContext::Expected(StrContextValue::StringLiteral(":"))),
-            space0,
-        ),
-        attribute_value(),
-    )
-    .map(|(name, value)| Attribute::new(name, value))
+    move |input: &mut Input<'s>| {
+        let source = input.input;
+        let name = attribute_name().parse_next(input)?;
+        let (value, comments) = preceded(separator(), attribute_value())
+            .context(Cause::Attribute(name))
+            .parse_next(input)?;
+        let source = &source[..source.len() - input.input.len()];
+        Ok(Attribute::from_parsed(Name::unchecked(name), value, comments).with_source(source))
+    }
 }
 
 /// Generate an attribute value parser that parses an ASCII sequence of letters,
 /// digits and the characters "-", "_". The first character must be a letter,
 /// while the last character may be a letter or a digit.
-fn attribute_name<'s, E>() -> impl Parser<&'s str, Name<'s>, E>
+fn attribute_name<'s, E>() -> impl Parser<Input<'s>, &'s str, E>
 where
-    E: ParserError<&'s str>,
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
 {
-    take_while(2.., ('A'..='Z', 'a'..='z', '0'..='9', '-', '_'))
+    take_while(2.., is_name_char)
         .verify(|s: &str| {
             s.starts_with(|c: char| c.is_ascii_alphabetic())
                 && s.ends_with(|c: char| c.is_ascii_alphanumeric())
         })
-        .map(Name::unchecked)
+        .context(Cause::Kind(ParseErrorKind::InvalidName))
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
 }
 
-/// Generate an attribute value parser that includes continuation lines.
-fn attribute_value<'s, E>() -> impl Parser<&'s str, Value<'s>, E>
+/// Generate a parser for the colon separating an attribute name from its value, followed by
+/// optional spaces or tabs.
+fn separator<'s, E>() -> impl Parser<Input<'s>, (), E>
 where
-    E: ParserError<&'s str>,
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
 {
-    move |input: &mut &'s str| {
-        let first_value = single_attribute_value().parse_next(input)?;
+    move |input: &mut Input<'s>| {
+        ':'.context(Cause::Kind(ParseErrorKind::InvalidSeparator))
+            .parse_next(input)?;
+        let blanks = space0.parse_next(input)?;
+        if let Some(i) = blanks.find('\t') {
+            input
+                .state
+                .warn(ParseWarningKind::TabSeparator, &blanks[i..=i]);
+        }
+        Ok(())
+    }
+}
 
-        if peek(continuation_char::<ContextError>())
-            .parse_next(input)
-            .is_ok()
-        {
-            let mut continuation_values: Vec<&str> = repeat(
-                1..,
-                preceded(
+/// Generate an attribute value parser that includes continuation lines, as well as the
+/// comments following values and comment lines in between them.
+fn attribute_value<'s, E>() -> impl Parser<Input<'s>, (Value<'s>, Vec<Comment<'s>>), E>
+where
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
+{
+    move |input: &mut Input<'s>| {
+        let (first_value, comment) = single_attribute_value().parse_next(input)?;
+        let mut comments: Vec<Comment> = comment
+            .map(|text| Comment::inline(text, 0))
+            .into_iter()
+            .collect();
+
+        // Only filled if the value continues, so that single line values do not allocate.
+        let mut values: Vec<&str> = Vec::new();
+        loop {
+            if input.starts_with('#') {
+                let text = comment_line().parse_next(input)?;
+                comments.push(Comment::own_line(text, values.len().saturating_sub(1)));
+            } else if peek(continuation_char::<ContextError>())
+                .parse_next(input)
+                .is_ok()
+            {
+                if values.is_empty() {
+                    values.push(first_value);
+                }
+                let (value, comment) = preceded(
                     continuation_char(),
                     preceded(space0, single_attribute_value()),
-                ),
-            )
+                )
+                .parse_next(input)?;
+                if let Some(text) = comment {
+                    comments.push(Comment::inline(text, values.len()));
+                }
+                values.push(value);
+            } else {
+                break;
+            }
+        }
+
+        let value = if values.is_empty() {
+            Value::unchecked_single(first_value)
+        } else {
+            Value::unchecked_multi(values)
+        };
+        Ok((value, comments))
+    }
+}
+
+/// Generate a parser for a singular attribute value without continuation, returning the value
+/// and the text of the comment following it, if any.
+///
+/// When parsing leniently, characters that are not part of the extended ASCII set are accepted
+/// and whitespace following a value is not part of it.
+fn single_attribute_value<'s, E>() -> impl Parser<Input<'s>, (&'s str, Option<&'s str>), E>
+where
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
+{
+    move |input: &mut Input<'s>| {
+        let lenient = input.state.lenient;
+        let line = input.input;
+        let value = take_while(0.., |c| c != '#' && is_value_char(c, lenient)).parse_next(input)?;
+        // Tabs are not valid within a value, but may follow it when parsing leniently.
+        if lenient {
+            opt(terminated(
+                take_while(0.., [' ', '\t']),
+                peek(alt(("#", line_ending, eof))),
+            ))
             .parse_next(input)?;
-            continuation_values.insert(0, first_value);
-            return Ok(Value::unchecked_multi(continuation_values));
         }
+        let comment = opt(preceded('#', comment_text())).parse_next(input)?;
+        let line = &line[..line.len() - input.input.len()];
+        line_end().parse_next(input)?;
+
+        input.state.warn_non_extended_ascii(line);
+        if lenient {
+            let trimmed = value.trim_end_matches(' ');
+            if comment.is_none() && !trimmed.is_empty() && trimmed.len() < line.len() {
+                input
+                    .state
+                    .warn(ParseWarningKind::TrailingWhitespace, &line[trimmed.len()..]);
+            }
+            return Ok((trimmed, comment));
+        }
+        Ok(match comment {
+            // The spaces separating a value from its comment are not part of the value.
+            Some(_) => (value.trim_end_matches(' '), comment),
+            None => (value, None),
+        })
+    }
+}
 
-        Ok(Value::unchecked_single(first_value))
+/// Generate a parser for a line containing only a comment, returning the text of the comment.
+fn comment_line<'s, E>() -> impl Parser<Input<'s>, &'s str, E>
+where
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
+{
+    move |input: &mut Input<'s>| {
+        let text = delimited('#', comment_text(), line_end()).parse_next(input)?;
+        input.state.warn_non_extended_ascii(text);
+        Ok(text)
     }
 }
 
-/// Generate a parser for a singular attribute value without continuation.
-fn single_attribute_value<'s, E>() -> impl Parser<&'s str, &'s str, E>
+/// Generate a parser for a line ending, which is either `\n` or `\r\n`.
+fn newline<'s, E>() -> impl Parser<Input<'s>, &'s str, E>
 where
-    E: ParserError<&'s str>,
+    E: ParserError<Input<'s>>,
 {
-    terminated(
-        take_while(0.., |c| Value::validate_char(c).is_ok()),
-        newline,
-    )
+    move |input: &mut Input<'s>| {
+        let ending = line_ending.parse_next(input)?;
+        if ending == "\r\n" {
+            input.state.warn(ParseWarningKind::CrlfLineEnding, ending);
+        }
+        Ok(ending)
+    }
+}
+
+/// Generate a parser for the line ending following a value or comment.
+/// If it is missing, the error describes the character found in its place.
+///
+/// When parsing leniently, the line ending may be missing at the end of the input.
+fn line_end<'s, E>() -> impl Parser<Input<'s>, (), E>
+where
+    E: ParserError<Input<'s>> + AddContext<Input<'s>, Cause<'s>>,
+{
+    move |input: &mut Input<'s>| {
+        if input.is_empty() && input.state.lenient {
+            return Ok(());
+        }
+        newline()
+            .void()
+            .parse_next(input)
+            .map_err(|err: ErrMode<E>| {
+                let kind = match input.chars().next() {
+                    None => ParseErrorKind::MissingNewline,
+                    Some(c) => match Value::validate_char(c) {
+                        Err(InvalidValueError::NonExtendedAscii) => {
+                            ParseErrorKind::NonExtendedAsciiValue
+                        }
+                        _ => ParseErrorKind::ControlCharacter,
+                    },
+                };
+                err.add_context(input, &input.checkpoint(), Cause::Kind(kind))
+            })
+    }
+}
+
+/// Generate a parser for the text of a comment following the `#` character.
+/// The text is limited to the same characters as values.
+fn comment_text<'s, E>() -> impl Parser<Input<'s>, &'s str, E>
+where
+    E: ParserError<Input<'s>>,
+{
+    move |input: &mut Input<'s>| {
+        let lenient = input.state.lenient;
+        take_while(0.., move |c| is_value_char(c, lenient)).parse_next(input)
+    }
+}
+
+/// Whether the character is valid within a value. When parsing leniently, characters that are
+/// not part of the extended ASCII set are valid as well.
+fn is_value_char(c: char, lenient: bool) -> bool {
+    match Value::validate_char(c) {
+        Ok(()) => true,
+        Err(InvalidValueError::NonExtendedAscii) => lenient,
+        Err(InvalidValueError::ContainsControlChar | InvalidValueError::ContainsCommentChar) => {
+            false
+        }
+    }
 }
 
 /// Generate a parser for a single continuation character.
-fn continuation_char<'s, E>() -> impl Parser<&'s str, char, E>
+fn continuation_char<'s, E>() -> impl Parser<Input<'s>, char, E>
 where
-    E: ParserError<&'s str>,
+    E: ParserError<Input<'s>>,
 {
     one_of([' ', '\t', '+'])
 }
 
+/// Create a [`ParseError`] for a parser of this module that failed at the given byte offset
+/// within the input, pointing to the text that caused it.
+fn parse_error(input: &str, offset: usize, err: &ContextError<Cause>) -> ParseError {
+    let kind = err.context().find_map(|cause| match cause {
+        Cause::Kind(kind) => Some(*kind),
+        Cause::Attribute(_) => None,
+    });
+    let attribute = err.context().find_map(|cause| match cause {
+        Cause::Attribute(name) => Some(*name),
+        Cause::Kind(_) => None,
+    });
+
+    let rest = &input[offset..];
+    let line = &rest[..rest.find(['\r', '\n']).unwrap_or(rest.len())];
+    let char_length = line.chars().next().map_or(0, char::len_utf8);
+    // Input following a complete object is the only failure without a known cause.
+    let kind = kind.unwrap_or(ParseErrorKind::UnexpectedContent);
+    let length = match kind {
+        ParseErrorKind::MissingNewline => 0,
+        ParseErrorKind::UnexpectedContent => line.len(),
+        ParseErrorKind::InvalidName => match line.find(|c| !is_name_char(c)) {
+            Some(0) => char_length,
+            Some(name_length) => name_length,
+            None => line.len(),
+        },
+        _ => char_length,
+    };
+    ParseError::new(kind, input, offset..offset + length, attribute)
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
     use rstest::*;
-    use winnow::error::ContextError;
 
     use super::*;
 
+    /// Parse the start of the given input strictly, leaving the remaining input in place.
+    fn parse_next<'s, O>(
+        mut parser: impl Parser<Input<'s>, O, ContextError<Cause<'s>>>,
+        given: &mut &'s str,
+    ) -> winnow::PResult<O, ContextError<Cause<'s>>> {
+        let mut input = Input {
+            input: given,
+            state: State::new(ParserOptions::strict()),
+        };
+        let result = parser.parse_next(&mut input);
+        *given = input.input;
+        result
+    }
+
     #[rstest]
     #[case(
         &mut concat!(
@@ -156,11 +510,21 @@ mod tests {
                 Attribute::unchecked_single("nic-hdl", "RPSL1-RIPE")
         ]
     )]
+    #[case(
+        &mut concat!(
+            "email:       rpsl-rs@github.com\r\n",
+            "nic-hdl:     RPSL1-RIPE\n",
+            "\r\n"
+        ),
+        vec![
+                Attribute::unchecked_single("email", "rpsl-rs@github.com"),
+                Attribute::unchecked_single("nic-hdl", "RPSL1-RIPE")
+        ]
+    )]
     fn object_block_valid(#[case] given: &mut &str, #[case] attributes: Vec<Attribute>) {
         let expected = Object::from_parsed(given, attributes);
 
-        let mut parser = object_block::<ContextError>();
-        let parsed = parser.parse_next(given).unwrap();
+        let parsed = parse_next(object_block::<ContextError<Cause>>(), given).unwrap();
 
         assert_eq!(parsed, expected);
     }
@@ -175,8 +539,7 @@ mod tests {
         );
         let source = *rpsl;
 
-        let mut parser = object_block::<ContextError>();
-        let parsed = parser.parse_next(rpsl).unwrap();
+        let parsed = parse_next(object_block::<ContextError<Cause>>(), rpsl).unwrap();
 
         assert_eq!(parsed.source().unwrap(), source);
     }
@@ -187,8 +550,7 @@ mod tests {
             "email:       rpsl-rs@github.com\n",
             "nic-hdl:     RPSL1-RIPE\n",
         );
-        let mut parser = object_block::<ContextError>();
-        assert!(parser.parse_next(object).is_err());
+        assert!(parse_next(object_block::<ContextError<Cause>>(), object).is_err());
     }
 
     #[rstest]
@@ -208,8 +570,11 @@ mod tests {
     fn object_block_padded_valid(#[case] given: &mut &str, #[case] attributes: Vec<Attribute>) {
         let expected = Object::from_parsed(given, attributes);
 
-        let mut parser = object_block_padded::<_, ContextError>(object_block());
-        let parsed = parser.parse_next(given).unwrap();
+        let parsed = parse_next(
+            object_block_padded::<_, ContextError<Cause>>(object_block()),
+            given,
+        )
+        .unwrap();
 
         assert_eq!(parsed, expected);
     }
@@ -231,16 +596,32 @@ mod tests {
             "\n",
         )
     )]
+    #[case(
+        &mut concat!(
+            "#\n",
+            "# ARIN WHOIS data and services are subject to the Terms of Use\n",
+            "#\n",
+            "\n",
+        )
+    )]
     fn optional_comment_or_newlines_consumed(#[case] given: &mut &str) {
-        let mut parser = consume_opt_messages_or_newlines::<ContextError>();
-        parser.parse_next(given).unwrap();
+        parse_next(
+            consume_opt_messages_or_newlines::<ContextError<Cause>>(),
+            given,
+        )
+        .unwrap();
         assert_eq!(*given, "");
     }
 
     #[test]
     fn optional_comment_or_newlines_optional() {
-        let mut parser = consume_opt_messages_or_newlines::<ContextError>();
-        assert_eq!(parser.parse_next(&mut ""), Ok(()));
+        assert_eq!(
+            parse_next(
+                consume_opt_messages_or_newlines::<ContextError<Cause>>(),
+                &mut ""
+            ),
+            Ok(())
+        );
     }
 
     #[rstest]
@@ -259,13 +640,17 @@ mod tests {
         "This query was served by the RIPE Database Query Service version 1.106.1 (BUSA)",
         ""
     )]
+    #[case(
+        &mut "% Note: this output has been filtered.\r\n",
+        "Note: this output has been filtered.",
+        ""
+    )]
     fn server_message_valid(
         #[case] given: &mut &str,
         #[case] expected: &str,
         #[case] remaining: &str,
     ) {
-        let mut parser = server_message::<ContextError>();
-        let parsed = parser.parse_next(given).unwrap();
+        let parsed = parse_next(server_message::<ContextError<Cause>>(), given).unwrap();
         assert_eq!(parsed, expected);
         assert_eq!(*given, remaining);
     }
@@ -281,8 +666,7 @@ mod tests {
         #[case] expected: Attribute,
         #[case] remaining: &str,
     ) {
-        let mut parser = attribute::<ContextError>();
-        let parsed = parser.parse_next(given).unwrap();
+        let parsed = parse_next(attribute::<ContextError<Cause>>(), given).unwrap();
         assert_eq!(parsed, expected);
         assert_eq!(*given, remaining);
     }
@@ -323,13 +707,25 @@ mod tests {
         ),
         ""
     )]
+    #[case(
+        &mut concat!(
+            "remarks:        Test\r\n",
+            "                continuation value followed by CRLF\r\n",
+            "+\r\n",
+            "remarks:        Peering Policy\r\n",
+        ),
+        Attribute::unchecked_multi(
+            "remarks",
+            vec![Some("Test"), Some("continuation value followed by CRLF"), None]
+        ),
+        "remarks:        Peering Policy\r\n"
+    )]
     fn attribute_valid_multi_value(
         #[case] given: &mut &str,
         #[case] expected: Attribute,
         #[case] remaining: &str,
     ) {
-        let mut parser = attribute::<ContextError>();
-        let parsed = parser.parse_next(given).unwrap();
+        let parsed = parse_next(attribute::<ContextError<Cause>>(), given).unwrap();
         assert_eq!(parsed, expected);
         assert_eq!(*given, remaining);
     }
@@ -344,8 +740,7 @@ mod tests {
         #[case] expected: &str,
         #[case] remaining: &str,
     ) {
-        let mut parser = attribute_name::<ContextError>();
-        let parsed = parser.parse_next(given).unwrap();
+        let parsed = parse_next(attribute_name::<ContextError<Cause>>(), given).unwrap();
         assert_eq!(parsed, expected);
         assert_eq!(*given, remaining);
     }
@@ -355,22 +750,19 @@ mod tests {
     #[case(&mut "-remarks:")]
     #[case(&mut "_remarks:")]
     fn attribute_name_non_letter_first_char_is_error(#[case] given: &mut &str) {
-        let mut parser = attribute_name::<ContextError>();
-        assert!(parser.parse_next(given).is_err());
+        assert!(parse_next(attribute_name::<ContextError<Cause>>(), given).is_err());
     }
 
     #[rstest]
     #[case(&mut "remarks-:")]
     #[case(&mut "remarks_:")]
     fn attribute_name_non_letter_or_digit_last_char_is_error(#[case] given: &mut &str) {
-        let mut parser = attribute_name::<ContextError>();
-        assert!(parser.parse_next(given).is_err());
+        assert!(parse_next(attribute_name::<ContextError<Cause>>(), given).is_err());
     }
 
     #[test]
     fn attribute_name_single_letter_is_error() {
-        let mut parser = attribute_name::<ContextError>();
-        assert!(parser.parse_next(&mut "a").is_err());
+        assert!(parse_next(attribute_name::<ContextError<Cause>>(), &mut "a").is_err());
     }
 
     #[rstest]
@@ -389,6 +781,11 @@ mod tests {
             "+49 176 07071964",
             ""
         )]
+    #[case(
+            &mut "value followed by CRLF\r\n",
+            "value followed by CRLF",
+            ""
+        )]
     #[case(
             &mut "* Equinix FR5, Kleyerstr, Frankfurt am Main\n",
             "* Equinix FR5, Kleyerstr, Frankfurt am Main",
@@ -399,18 +796,101 @@ mod tests {
         #[case] expected: &str,
         #[case] remaining: &str,
     ) {
-        let mut parser = single_attribute_value::<ContextError>();
-        let parsed = parser.parse_next(given).unwrap();
-        assert_eq!(parsed, expected);
+        let parsed = parse_next(single_attribute_value::<ContextError<Cause>>(), given).unwrap();
+        assert_eq!(parsed, (expected, None));
         assert_eq!(*given, remaining);
     }
 
+    #[rstest]
+    #[case(&mut "AS65536 # transit\n", "AS65536", " transit")]
+    #[case(&mut "AS65536#\n", "AS65536", "")]
+    #[case(&mut "# only a comment\r\n", "", " only a comment")]
+    #[case(&mut "a # b # c\n", "a", " b # c")]
+    fn attribute_value_with_comment(
+        #[case] given: &mut &str,
+        #[case] expected: &str,
+        #[case] comment: &str,
+    ) {
+        let parsed = parse_next(single_attribute_value::<ContextError<Cause>>(), given).unwrap();
+        assert_eq!(parsed, (expected, Some(comment)));
+        assert_eq!(*given, "");
+    }
+
+    #[test]
+    fn attribute_comments_are_kept() {
+        let given = &mut concat!(
+            "members:        AS65536, # transit\n",
+            "# peers\n",
+            "+               AS65537 #peer\n",
+            "#\n",
+            "source:         RIPE\n",
+        );
+        let parsed = parse_next(attribute::<ContextError<Cause>>(), given).unwrap();
+        assert_eq!(parsed.value, vec!["AS65536,", "AS65537"]);
+        assert_eq!(
+            parsed.comments(),
+            [
+                Comment::inline(" transit", 0),
+                Comment::own_line(" peers", 0),
+                Comment::inline("peer", 1),
+                Comment::own_line("", 1),
+            ]
+        );
+        assert_eq!(*given, "source:         RIPE\n");
+    }
+
+    #[rstest]
+    #[case("role;  ACME\n\n", ParseErrorKind::InvalidSeparator, (1, 5), 4..5, Some("role"))]
+    #[case("role ACME\n\n", ParseErrorKind::InvalidSeparator, (1, 5), 4..5, Some("role"))]
+    #[case("role\n\n", ParseErrorKind::InvalidSeparator, (1, 5), 4..4, Some("role"))]
+    #[case("1role: ACME\n\n", ParseErrorKind::InvalidName, (1, 1), 0..5, None)]
+    #[case("r: ACME\n\n", ParseErrorKind::InvalidName, (1, 1), 0..1, None)]
+    #[case(" role: ACME\n\n", ParseErrorKind::InvalidName, (1, 1), 0..1, None)]
+    #[case("% message\n\n", ParseErrorKind::InvalidName, (1, 1), 0..1, None)]
+    #[case(
+        "role: ACME\naddress: Straße ☃\n\n",
+        ParseErrorKind::NonExtendedAsciiValue,
+        (2, 17),
+        28..31,
+        Some("address")
+    )]
+    #[case(
+        "role: ACME\naddress: Street\n +   6\x07\n\n",
+        ParseErrorKind::ControlCharacter,
+        (3, 7),
+        33..34,
+        Some("address")
+    )]
+    #[case("role: ACME\n\taddress\tA\n\n", ParseErrorKind::ControlCharacter, (2, 9), 19..20, Some("role"))]
+    #[case("role: ACME", ParseErrorKind::MissingNewline, (1, 11), 10..10, Some("role"))]
+    #[case("role: ACME\n", ParseErrorKind::MissingNewline, (2, 1), 11..11, None)]
+    #[case("role: ACME\n\nrole: ACME\n\n", ParseErrorKind::UnexpectedContent, (3, 1), 12..22, None)]
+    #[case("role\r\n\r\n", ParseErrorKind::InvalidSeparator, (1, 5), 4..4, Some("role"))]
+    #[case("role: A\r\n+\x01\r\n\r\n", ParseErrorKind::ControlCharacter, (2, 2), 10..11, Some("role"))]
+    #[case("role: A\r\n\r\nrole: B\r\n\r\n", ParseErrorKind::UnexpectedContent, (3, 1), 11..18, None)]
+    #[case("role: A # B\n# C\n\x01\n\n", ParseErrorKind::InvalidName, (3, 1), 16..17, None)]
+    #[case("role: A\n#\n# C\x01\n\n", ParseErrorKind::ControlCharacter, (3, 4), 13..14, Some("role"))]
+    #[case("role: B\nnic-hdl # C\n\n", ParseErrorKind::InvalidSeparator, (2, 8), 15..16, Some("nic-hdl"))]
+    /// Errors point to the text causing them and describe the problem found there.
+    fn object_block_error_cause(
+        #[case] input: &str,
+        #[case] kind: ParseErrorKind,
+        #[case] position: (usize, usize),
+        #[case] span: std::ops::Range<usize>,
+        #[case] attribute: Option<&str>,
+    ) {
+        let err = parse(object_block(), input, ParserOptions::strict()).unwrap_err();
+        assert_eq!(err.kind(), kind);
+        assert_eq!((err.line(), err.column()), position);
+        assert_eq!(err.span(), span);
+        assert_eq!(err.attribute(), attribute);
+    }
+
     proptest! {
         /// Parsing any non extended ASCII returns an error.
         #[test]
         fn attribute_value_non_extended_ascii_is_err(s in r"[^\x00-\xFF]+") {
-            let mut parser = single_attribute_value::<ContextError>();
-            assert!(parser.parse_next(&mut s.as_str()).is_err());
+            assert!(parse_next(single_attribute_value::<ContextError<Cause>>(), &mut s.as_str()).is_err());
         }
     }
 }
@@ -0,0 +1,222 @@
+use std::iter::FusedIterator;
+
+use winnow::{error::ContextError, Parser};
+
+use super::{
+    core::{consume_opt_messages_or_newlines, object_block, parse_to_end, Cause, Input, State},
+    ParserOptions,
+};
+use crate::{Object, ParseError, ParseWarning};
+
+/// A lazy iterator over the [`Object`]s contained within RPSL text.
+///
+/// Created by [`iter_objects`](crate::iter_objects) or
+/// [`ParserOptions::iter_objects`]. Each object block is only parsed once the iterator is
+/// advanced, so memory usage does not grow with the size of the input. If a block cannot be
+/// parsed, an error is yielded for that block and iteration resumes with the next one.
+#[derive(Debug, Clone)]
+pub struct ObjectIter<'a> {
+    rpsl: &'a str,
+    remaining: &'a str,
+    options: ParserOptions,
+    /// The number of lines preceding the remaining input.
+    lines: usize,
+    warnings: Vec<ParseWarning>,
+}
+
+impl<'a> ObjectIter<'a> {
+    pub(crate) fn with_options(rpsl: &'a str, options: ParserOptions) -> Self {
+        Self {
+            rpsl,
+            remaining: rpsl,
+            options,
+            lines: 0,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// The deviations from RPSL accepted in the object yielded last, including the server
+    /// messages and comments preceding it. Only parsing leniently accepts deviations.
+    #[must_use]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+}
+
+impl<'a> Iterator for ObjectIter<'a> {
+    type Item = Result<Object<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.warnings.clear();
+        let mut input = Input {
+            input: self.remaining,
+            state: State::new(self.options),
+        };
+        consume_opt_messages_or_newlines::<ContextError<Cause>>()
+            .parse_next(&mut input)
+            .ok()?;
+        if input.is_empty() {
+            self.remaining = input.input;
+            return None;
+        }
+
+        // The block extends up to and including the blank line that terminates it. If no blank
+        // line is found, the remaining input is treated as a single block.
+        let start = self.remaining.len() - input.input.len();
+        let end = blank_line_end(input.input).map_or(self.remaining.len(), |end| start + end);
+        let (text, remaining) = self.remaining.split_at(end);
+        input.input = &text[start..];
+
+        let offset = self.rpsl.len() - self.remaining.len();
+        let lines = self.lines;
+        self.remaining = remaining;
+        self.lines += text.matches('\n').count();
+
+        match parse_to_end(object_block(), &mut input, text) {
+            Ok((object, warnings)) => {
+                self.warnings = warnings
+                    .into_iter()
+                    .map(|warning| warning.shift(offset, lines))
+                    .collect();
+                Some(Ok(object))
+            }
+            Err(err) => Some(Err(err.shift(offset, lines))),
+        }
+    }
+}
+
+impl FusedIterator for ObjectIter<'_> {}
+
+/// The position following the first blank line, ending with either `\n` or `\r\n`.
+fn blank_line_end(s: &str) -> Option<usize> {
+    s.match_indices('\n').find_map(|(i, _)| {
+        let next = &s[i + 1..];
+        if next.starts_with('\n') {
+            Some(i + 2)
+        } else if next.starts_with("\r\n") {
+            Some(i + 3)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::Attribute;
+
+    #[rstest]
+    #[case("", 0)]
+    #[case("\n\n\n", 0)]
+    #[case("% This query was served by the RIPE Database Query Service\n\n", 0)]
+    #[case(
+        concat!(
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL1-RIPE\n",
+            "\n",
+        ),
+        1
+    )]
+    #[case(
+        concat!(
+            "% Note: this output has been filtered.\n",
+            "\n",
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL1-RIPE\n",
+            "\n",
+            "\n",
+            "% Information related to 'RPSL2-RIPE'\n",
+            "\n",
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL2-RIPE\n",
+            "\n",
+        ),
+        2
+    )]
+    #[case(
+        concat!(
+            "% Note: this output has been filtered.\r\n",
+            "\r\n",
+            "email:       rpsl-rs@github.com\r\n",
+            "nic-hdl:     RPSL1-RIPE\r\n",
+            "\r\n",
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL2-RIPE\n",
+            "\r\n",
+        ),
+        2
+    )]
+    fn object_iter_yields_each_object(#[case] given: &str, #[case] expected: usize) {
+        let objects: Vec<Object> = ObjectIter::with_options(given, ParserOptions::strict())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(objects.len(), expected);
+    }
+
+    #[test]
+    /// An invalid block yields an error, while the blocks around it are still parsed.
+    fn object_iter_resumes_after_invalid_block() {
+        let rpsl = concat!(
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL1-RIPE\n",
+            "\n",
+            "email;       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL2-RIPE\n",
+            "\n",
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL3-RIPE\n",
+            "\n",
+        );
+        let mut iter = ObjectIter::with_options(rpsl, ParserOptions::strict());
+
+        assert_eq!(
+            iter.next().unwrap().unwrap(),
+            Object::new(vec![
+                Attribute::unchecked_single("email", "rpsl-rs@github.com"),
+                Attribute::unchecked_single("nic-hdl", "RPSL1-RIPE"),
+            ])
+        );
+        let err = iter.next().unwrap().unwrap_err();
+        assert_eq!((err.line(), err.column(), err.offset()), (4, 6, 62));
+        assert_eq!(
+            iter.next().unwrap().unwrap(),
+            Object::new(vec![
+                Attribute::unchecked_single("email", "rpsl-rs@github.com"),
+                Attribute::unchecked_single("nic-hdl", "RPSL3-RIPE"),
+            ])
+        );
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn object_iter_without_newline_termination_yields_err() {
+        let rpsl = concat!(
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL1-RIPE\n",
+        );
+        let mut iter = ObjectIter::with_options(rpsl, ParserOptions::strict());
+
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    /// Objects yielded by the iterator contain the source of their block.
+    fn object_iter_objects_contain_source() {
+        let block = concat!(
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL1-RIPE\n",
+            "\n",
+        );
+        let rpsl = format!("\n\n{block}\n\n");
+        let object = ObjectIter::with_options(&rpsl, ParserOptions::strict())
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(object.source().unwrap(), block);
+    }
+}
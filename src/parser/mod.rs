@@ -1,4 +1,14 @@
-pub use api::{parse_object, parse_whois_response};
+pub use api::{
+    iter_objects, parse_object, parse_whois_response, parse_whois_response_recovering, read_objects,
+};
+pub use iter::ObjectIter;
+pub use options::{Parsed, ParserOptions};
+pub use reader::ObjectReader;
+pub use recover::Recovered;
 
 mod api;
 mod core;
+mod iter;
+mod options;
+mod reader;
+mod recover;
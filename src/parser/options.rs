@@ -0,0 +1,428 @@
+use std::io::BufRead;
+
+use winnow::{
+    ascii::multispace0,
+    combinator::{delimited, repeat},
+};
+
+use super::{
+    core::{object_block, object_block_padded, parse},
+    iter::ObjectIter,
+    reader::ObjectReader,
+};
+use crate::{Object, ParseError, ParseWarning};
+
+/// Options controlling how RPSL is parsed.
+///
+/// In the strict mode, which is the default, RPSL is parsed as by
+/// [`parse_object`](crate::parse_object) and [`parse_whois_response`](crate::parse_whois_response).
+/// The lenient mode additionally accepts deviations commonly found in real world IRR data and
+/// reports each of them as a [`ParseWarning`], including those the strict mode accepts as well:
+/// - `\r\n` line endings.
+/// - Whitespace following a value, which is not part of the parsed value.
+/// - Tabs in the separator following an attribute name.
+/// - A missing blank line after the last object.
+/// - Characters that are not part of the extended ASCII set, e.g. UTF-8 in a `descr` value.
+///
+/// Besides parsing a single object or a WHOIS server response at once, objects can be parsed
+/// one at a time using [`iter_objects`](Self::iter_objects) and
+/// [`read_objects`](Self::read_objects), which continue with the next object after an error.
+///
+/// # Example
+/// ```
+/// # use rpsl::{ParserOptions, ParseWarningKind};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let rpsl = "role:\tACME Company\r\naddress: Łódź\r\n";
+///
+/// let parsed = ParserOptions::lenient().parse_object(rpsl)?;
+/// assert_eq!(parsed.value[0].value, "ACME Company");
+/// assert_eq!(parsed.value[1].value, "Łódź");
+/// assert_eq!(parsed.warnings[0].kind(), ParseWarningKind::TabSeparator);
+/// assert_eq!(parsed.warnings.len(), 5);
+///
+/// assert!(ParserOptions::strict().parse_object(rpsl).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ParserOptions {
+    lenient: bool,
+}
+
+impl ParserOptions {
+    /// Options for parsing RPSL as specified by RFC 2622.
+    #[must_use]
+    pub fn strict() -> Self {
+        Self { lenient: false }
+    }
+
+    /// Options for parsing RPSL while accepting deviations commonly found in real world data.
+    #[must_use]
+    pub fn lenient() -> Self {
+        Self { lenient: true }
+    }
+
+    /// Whether deviations from RPSL are accepted.
+    #[must_use]
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Parse RPSL into an [`Object`], borrowing from the source.
+    ///
+    /// # Errors
+    /// Returns a [`ParseError`] if the input is not valid RPSL, taking accepted deviations
+    /// into account.
+    pub fn parse_object<'a>(&self, rpsl: &'a str) -> Result<Parsed<Object<'a>>, ParseError> {
+        let parser = delimited(multispace0, object_block(), multispace0);
+        let (value, warnings) = parse(parser, rpsl, *self)?;
+        Ok(Parsed { value, warnings })
+    }
+
+    /// Parse a WHOIS server response into the [`Object`]s contained within.
+    ///
+    /// # Errors
+    /// Returns a [`ParseError`] if the input is not valid RPSL, taking accepted deviations
+    /// into account.
+    pub fn parse_whois_response<'a>(
+        &self,
+        response: &'a str,
+    ) -> Result<Parsed<Vec<Object<'a>>>, ParseError> {
+        let parser = repeat(1.., object_block_padded(object_block()));
+        let (value, warnings) = parse(parser, response, *self)?;
+        Ok(Parsed { value, warnings })
+    }
+
+    /// Lazily parse RPSL containing any number of objects, like
+    /// [`iter_objects`](crate::iter_objects).
+    ///
+    /// The deviations accepted in the object yielded last are available using
+    /// [`ObjectIter::warnings`].
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{ParserOptions, ParseWarningKind};
+    /// let dump = "mntner: MAINT-AS3257\r\n\r\nmntner;  INVALID\n\nmntner:  MAINT-AS1299";
+    ///
+    /// let mut objects = ParserOptions::lenient().iter_objects(dump);
+    /// assert!(objects.next().unwrap().is_ok());
+    /// assert_eq!(objects.warnings()[0].kind(), ParseWarningKind::CrlfLineEnding);
+    /// assert!(objects.next().unwrap().is_err());
+    /// assert!(objects.next().unwrap().is_ok());
+    /// assert_eq!(objects.warnings()[0].kind(), ParseWarningKind::MissingBlankLine);
+    /// assert!(objects.next().is_none());
+    /// ```
+    #[must_use]
+    pub fn iter_objects<'a>(&self, rpsl: &'a str) -> ObjectIter<'a> {
+        ObjectIter::with_options(rpsl, *self)
+    }
+
+    /// Read RPSL containing any number of objects from a [`BufRead`] source, like
+    /// [`read_objects`](crate::read_objects).
+    ///
+    /// The deviations accepted in the object yielded last are available using
+    /// [`ObjectReader::warnings`].
+    #[must_use]
+    pub fn read_objects<R: BufRead>(&self, reader: R) -> ObjectReader<R> {
+        ObjectReader::with_options(reader, *self)
+    }
+}
+
+/// A parsed value, along with a warning for every deviation from RPSL that was accepted.
+///
+/// Created by parsing with [`ParserOptions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parsed<T> {
+    /// The parsed value.
+    pub value: T,
+    /// The accepted deviations, in order of their appearance.
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl<T> Parsed<T> {
+    /// Whether the input was parsed without deviations from RPSL.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+impl<T> From<T> for Parsed<T> {
+    fn from(value: T) -> Self {
+        Self {
+            value,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use rstest::*;
+
+    use super::*;
+    use crate::{object, Comment, ParseErrorKind, ParseWarningKind, ReadError};
+
+    #[rstest]
+    #[case(ParserOptions::strict(), "role: ACME\n\n", true)]
+    #[case(ParserOptions::lenient(), "role: ACME\n\n", true)]
+    #[case(ParserOptions::strict(), "role: ACME\r\n\r\n", true)]
+    #[case(ParserOptions::lenient(), "role: ACME\r\n\r\n", true)]
+    #[case(ParserOptions::strict(), "descr: \u{1F980}\n\n", false)]
+    #[case(ParserOptions::lenient(), "descr: \u{1F980}\n\n", true)]
+    #[case(ParserOptions::strict(), "role: ACME\n", false)]
+    #[case(ParserOptions::lenient(), "role: ACME\n", true)]
+    fn parse_object_with_options(
+        #[case] options: ParserOptions,
+        #[case] rpsl: &str,
+        #[case] is_ok: bool,
+    ) {
+        assert_eq!(options.parse_object(rpsl).is_ok(), is_ok);
+    }
+
+    #[test]
+    fn strict_parse_has_no_warnings() {
+        let parsed = ParserOptions::default()
+            .parse_whois_response("role: ACME  \n\nrole:\tACME\n\n")
+            .unwrap();
+        assert_eq!(parsed.value.len(), 2);
+        assert!(parsed.is_clean());
+    }
+
+    #[rstest]
+    #[case("role: ACME\nnic-hdl: RPSL1-RIPE\n\n", &[])]
+    #[case(
+        "role: ACME\r\nnic-hdl: RPSL1-RIPE\r\n\r\n",
+        &[
+            (ParseWarningKind::CrlfLineEnding, 1, 11),
+            (ParseWarningKind::CrlfLineEnding, 2, 20),
+            (ParseWarningKind::CrlfLineEnding, 3, 1),
+        ]
+    )]
+    #[case(
+        "role: ACME  \nnic-hdl: RPSL1-RIPE\t\n\n",
+        &[
+            (ParseWarningKind::TrailingWhitespace, 1, 11),
+            (ParseWarningKind::TrailingWhitespace, 2, 20),
+        ]
+    )]
+    #[case(
+        "role:\tACME\nnic-hdl: \t RPSL1-RIPE\n\n",
+        &[
+            (ParseWarningKind::TabSeparator, 1, 6),
+            (ParseWarningKind::TabSeparator, 2, 10),
+        ]
+    )]
+    #[case("role: ACME\nnic-hdl: RPSL1-RIPE\n", &[(ParseWarningKind::MissingBlankLine, 3, 1)])]
+    #[case("role: ACME\nnic-hdl: RPSL1-RIPE", &[(ParseWarningKind::MissingBlankLine, 2, 20)])]
+    fn lenient_object_warnings(
+        #[case] rpsl: &str,
+        #[case] expected: &[(ParseWarningKind, usize, usize)],
+    ) {
+        let parsed = ParserOptions::lenient().parse_object(rpsl).unwrap();
+        assert_eq!(
+            parsed.value,
+            object! {
+                "role": "ACME";
+                "nic-hdl": "RPSL1-RIPE";
+            }
+        );
+        let warnings: Vec<_> = parsed
+            .warnings
+            .iter()
+            .map(|w| (w.kind(), w.line(), w.column()))
+            .collect();
+        assert_eq!(warnings, expected);
+    }
+
+    #[test]
+    fn lenient_values_borrow_from_source() {
+        let rpsl = "descr:  Zürich ☃ \r\n        Genève\r\n\r\n";
+        let parsed = ParserOptions::lenient().parse_object(rpsl).unwrap();
+        assert_eq!(parsed.value[0].value, vec!["Zürich ☃", "Genève"]);
+        assert_eq!(parsed.value.source(), Some(rpsl));
+        let warnings: Vec<_> = parsed.warnings.iter().map(ParseWarning::kind).collect();
+        assert_eq!(
+            warnings,
+            [
+                ParseWarningKind::NonExtendedAsciiValue,
+                ParseWarningKind::TrailingWhitespace,
+                ParseWarningKind::CrlfLineEnding,
+                ParseWarningKind::CrlfLineEnding,
+                ParseWarningKind::CrlfLineEnding,
+            ]
+        );
+    }
+
+    #[test]
+    fn lenient_whois_response() {
+        let response = concat!(
+            "% Note: this output has been filtered.\r\n",
+            "\r\n",
+            "aut-num:  AS65536\r\n",
+            "\r\n",
+            "as-set:   AS-EXAMPLE\r\n",
+            "members:  AS65536\r\n",
+        );
+        let parsed = ParserOptions::lenient()
+            .parse_whois_response(response)
+            .unwrap();
+        assert_eq!(
+            parsed.value,
+            vec![
+                object! { "aut-num": "AS65536"; },
+                object! {
+                    "as-set": "AS-EXAMPLE";
+                    "members": "AS65536";
+                },
+            ]
+        );
+        assert_eq!(parsed.warnings.len(), 7);
+        assert_eq!(
+            parsed.warnings.last().map(ParseWarning::kind),
+            Some(ParseWarningKind::MissingBlankLine)
+        );
+    }
+
+    #[test]
+    fn lenient_comments() {
+        let rpsl = concat!(
+            "# ARIN WHOIS data\r\n",
+            "\r\n",
+            "remarks: Łódź # Poland \r\n",
+            "# note\r\n",
+            "  Kraków\r\n",
+            "\r\n",
+        );
+        let parsed = ParserOptions::lenient().parse_whois_response(rpsl).unwrap();
+        let attribute = &parsed.value[0][0];
+        assert_eq!(attribute.value, vec!["Łódź", "Kraków"]);
+        assert_eq!(
+            attribute.comments(),
+            [
+                Comment::inline(" Poland ", 0),
+                Comment::own_line(" note", 0)
+            ]
+        );
+        assert!(parsed
+            .warnings
+            .iter()
+            .all(|w| w.kind() != ParseWarningKind::TrailingWhitespace));
+    }
+
+    #[rstest]
+    #[case("role: ACME\r\n+\x01\r\n\r\n", ParseErrorKind::ControlCharacter, (2, 2))]
+    #[case("role: AC\rME\r\n\r\n", ParseErrorKind::ControlCharacter, (1, 9))]
+    #[case("role: A \t B\r\n\r\n", ParseErrorKind::ControlCharacter, (1, 9))]
+    #[case("role ACME\r\n\r\n", ParseErrorKind::InvalidSeparator, (1, 5))]
+    #[case("role: ACME\r\n% note\r\n\r\n", ParseErrorKind::InvalidName, (2, 1))]
+    #[case("role: ACME\r\n\r\nrole: ACME\r\n", ParseErrorKind::UnexpectedContent, (3, 1))]
+    #[case("\r\n\r\n", ParseErrorKind::MissingNewline, (3, 1))]
+    fn lenient_object_errors(
+        #[case] rpsl: &str,
+        #[case] kind: ParseErrorKind,
+        #[case] position: (usize, usize),
+    ) {
+        let err = ParserOptions::lenient().parse_object(rpsl).unwrap_err();
+        assert_eq!(
+            (err.kind(), err.line(), err.column()),
+            (kind, position.0, position.1)
+        );
+    }
+
+    const DUMP: &str = concat!(
+        "role:  A\r\n",
+        "\r\n",
+        "role;  B\n",
+        "\n",
+        "role:  C\n",
+        "\n",
+        "role:  D\x01\n",
+        "\n",
+        "role:\tE",
+    );
+
+    #[test]
+    /// Lenient iteration reports the deviations of each object and every error.
+    fn lenient_iter_objects() {
+        let mut objects = ParserOptions::lenient().iter_objects(DUMP);
+        let mut found = Vec::new();
+        while let Some(result) = objects.next() {
+            let warnings: Vec<_> = objects
+                .warnings()
+                .iter()
+                .map(|w| (w.kind(), w.line()))
+                .collect();
+            found.push((result.map_err(|err| (err.kind(), err.line())), warnings));
+        }
+
+        assert_eq!(
+            found,
+            vec![
+                (
+                    Ok(object! { "role": "A"; }),
+                    vec![
+                        (ParseWarningKind::CrlfLineEnding, 1),
+                        (ParseWarningKind::CrlfLineEnding, 2)
+                    ]
+                ),
+                (Err((ParseErrorKind::InvalidSeparator, 3)), vec![]),
+                (Ok(object! { "role": "C"; }), vec![]),
+                (Err((ParseErrorKind::ControlCharacter, 7)), vec![]),
+                (
+                    Ok(object! { "role": "E"; }),
+                    vec![
+                        (ParseWarningKind::TabSeparator, 9),
+                        (ParseWarningKind::MissingBlankLine, 9)
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    /// Lenient reading reports the deviations of each object and every error.
+    fn lenient_read_objects() {
+        let mut objects = ParserOptions::lenient().read_objects(Cursor::new(DUMP));
+        let mut found = Vec::new();
+        while let Some(result) = objects.next() {
+            let warnings: Vec<_> = objects
+                .warnings()
+                .iter()
+                .map(|w| (w.kind(), w.line()))
+                .collect();
+            let result = result.map(|object| object.to_string()).map_err(|err| {
+                let ReadError::Parse(err) = err else {
+                    unreachable!("expected a parse error")
+                };
+                (err.kind(), err.line())
+            });
+            found.push((result, warnings));
+        }
+
+        assert_eq!(
+            found,
+            vec![
+                (
+                    Ok("role:  A\r\n\r\n".to_string()),
+                    vec![
+                        (ParseWarningKind::CrlfLineEnding, 1),
+                        (ParseWarningKind::CrlfLineEnding, 2)
+                    ]
+                ),
+                (Err((ParseErrorKind::InvalidSeparator, 3)), vec![]),
+                (Ok("role:  C\n\n".to_string()), vec![]),
+                (Err((ParseErrorKind::ControlCharacter, 7)), vec![]),
+                (
+                    Ok("role:\tE".to_string()),
+                    vec![
+                        (ParseWarningKind::TabSeparator, 9),
+                        (ParseWarningKind::MissingBlankLine, 9)
+                    ]
+                ),
+            ]
+        );
+    }
+}
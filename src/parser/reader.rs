@@ -0,0 +1,283 @@
+use std::{borrow::Cow, io::BufRead, iter::FusedIterator};
+
+use super::{iter::ObjectIter, ParserOptions};
+use crate::{error::ReadError, Object, ParseWarning};
+
+/// An iterator over the [`Object`]s read from a [`BufRead`] source.
+///
+/// Created by [`read_objects`](crate::read_objects) or [`ParserOptions::read_objects`]. Lines are
+/// read from the source until a complete object block has been buffered, which is then parsed and
+/// converted into an owned [`Object`] that keeps a copy of its source. Only a single object is
+/// held in memory at a time.
+///
+/// Blocks that are not valid UTF-8 are decoded as Latin-1, the extended ASCII allowed in values,
+/// so that an object in a legacy encoding does not keep the objects following it from being read.
+///
+/// An object that cannot be parsed yields a [`ReadError::Parse`] and reading continues with the
+/// next object. Since the state of the source is unknown after a failed read, iteration ends after
+/// yielding a [`ReadError::Io`].
+#[derive(Debug)]
+pub struct ObjectReader<R> {
+    reader: R,
+    options: ParserOptions,
+    buf: Vec<u8>,
+    done: bool,
+    /// The number of bytes and lines read before the buffered block.
+    offset: usize,
+    lines: usize,
+    /// The number of lines in the buffered block.
+    block_lines: usize,
+    warnings: Vec<ParseWarning>,
+}
+
+impl<R: BufRead> ObjectReader<R> {
+    pub(crate) fn with_options(reader: R, options: ParserOptions) -> Self {
+        Self {
+            reader,
+            options,
+            buf: Vec::new(),
+            done: false,
+            offset: 0,
+            lines: 0,
+            block_lines: 0,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// The deviations from RPSL accepted in the object yielded last, including the server
+    /// messages and comments preceding it. Only parsing leniently accepts deviations.
+    #[must_use]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Read lines into the buffer until an object block terminated by a blank line, or the end
+    /// of the source has been reached. Empty lines, server messages and comment lines preceding
+    /// the block are read as well. Returns whether the buffer contains any content besides those.
+    fn fill_buf(&mut self) -> Result<bool, ReadError> {
+        self.offset += self.buf.len();
+        self.lines += self.block_lines;
+        self.buf.clear();
+        self.block_lines = 0;
+        let mut has_content = false;
+
+        loop {
+            let start = self.buf.len();
+            if self.reader.read_until(b'\n', &mut self.buf)? == 0 {
+                return Ok(has_content);
+            }
+            self.block_lines += 1;
+
+            let line = &self.buf[start..];
+            if line == b"\n" || line == b"\r\n" {
+                if has_content {
+                    return Ok(true);
+                }
+            } else if !line.starts_with(b"%") && !line.starts_with(b"#") {
+                has_content = true;
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ObjectReader<R> {
+    type Item = Result<Object<'static>, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.warnings.clear();
+        if self.done {
+            return None;
+        }
+
+        match self.fill_buf() {
+            Ok(true) => {}
+            Ok(false) => {
+                self.done = true;
+                return None;
+            }
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+
+        let block = decode(&self.buf);
+        let mut objects = ObjectIter::with_options(&block, self.options);
+        let parsed = objects.next()?;
+        self.warnings = objects
+            .warnings()
+            .iter()
+            .map(|warning| warning.clone().shift(self.offset, self.lines))
+            .collect();
+        Some(
+            parsed
+                .map(Object::into_owned)
+                .map_err(|err| ReadError::from(err.shift(self.offset, self.lines))),
+        )
+    }
+}
+
+impl<R: BufRead> FusedIterator for ObjectReader<R> {}
+
+/// Decode a block as UTF-8, falling back to Latin-1 for the extended ASCII used by RPSL.
+fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Cow::Borrowed(text),
+        Err(_) => Cow::Owned(bytes.iter().copied().map(char::from).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor, Read};
+
+    use rstest::*;
+
+    use super::*;
+    use crate::Attribute;
+
+    #[rstest]
+    #[case("", 0)]
+    #[case("\n\n\n", 0)]
+    #[case("% This query was served by the RIPE Database Query Service\n\n", 0)]
+    #[case(
+        concat!(
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL1-RIPE\n",
+            "\n",
+        ),
+        1
+    )]
+    #[case(
+        concat!(
+            "% Note: this output has been filtered.\n",
+            "\n",
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL1-RIPE\n",
+            "\n",
+            "\n",
+            "% Information related to 'RPSL2-RIPE'\n",
+            "\n",
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL2-RIPE\n",
+            "\n",
+        ),
+        2
+    )]
+    #[case(
+        concat!(
+            "% Note: this output has been filtered.\r\n",
+            "\r\n",
+            "email:       rpsl-rs@github.com\r\n",
+            "\r\n",
+            "email:       rpsl-rs@github.com\r\n",
+            "\r\n",
+        ),
+        2
+    )]
+    fn object_reader_yields_each_object(#[case] given: &str, #[case] expected: usize) {
+        let objects: Vec<Object> =
+            ObjectReader::with_options(Cursor::new(given), ParserOptions::strict())
+                .collect::<Result<_, _>>()
+                .unwrap();
+        assert_eq!(objects.len(), expected);
+    }
+
+    #[test]
+    /// An invalid object yields a parse error, while the objects around it are still read.
+    fn object_reader_resumes_after_invalid_object() {
+        let rpsl = concat!(
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL1-RIPE\n",
+            "\n",
+            "email;       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL2-RIPE\n",
+            "\n",
+            "email:       rpsl-rs@github.com\n",
+            "nic-hdl:     RPSL3-RIPE\n",
+            "\n",
+        );
+        let mut reader = ObjectReader::with_options(Cursor::new(rpsl), ParserOptions::strict());
+
+        assert_eq!(
+            reader.next().unwrap().unwrap(),
+            Object::new(vec![
+                Attribute::unchecked_single("email", "rpsl-rs@github.com"),
+                Attribute::unchecked_single("nic-hdl", "RPSL1-RIPE"),
+            ])
+        );
+        let Some(Err(ReadError::Parse(err))) = reader.next() else {
+            unreachable!("expected a parse error");
+        };
+        assert_eq!((err.line(), err.column(), err.offset()), (4, 6, 62));
+        assert_eq!(
+            reader.next().unwrap().unwrap(),
+            Object::new(vec![
+                Attribute::unchecked_single("email", "rpsl-rs@github.com"),
+                Attribute::unchecked_single("nic-hdl", "RPSL3-RIPE"),
+            ])
+        );
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    /// A block that is not valid UTF-8 is decoded as Latin-1, without affecting the blocks
+    /// following it.
+    fn object_reader_decodes_latin1() {
+        let rpsl: &[u8] = b"\
+mntner:  MAINT-EXAMPLE
+descr:   M\xfcnchen
+
+mntner:  MAINT-AS3257
+descr:   Z\xc3\xbcrich
+
+email;   rpsl-rs@github.com
+descr:   K\xf6ln
+
+";
+        let mut reader = ObjectReader::with_options(Cursor::new(rpsl), ParserOptions::strict());
+
+        assert_eq!(reader.next().unwrap().unwrap().get("descr"), ["München"]);
+        assert_eq!(reader.next().unwrap().unwrap().get("descr"), ["Zürich"]);
+        let Some(Err(ReadError::Parse(err))) = reader.next() else {
+            unreachable!("expected a parse error");
+        };
+        assert_eq!((err.line(), err.column()), (7, 6));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    /// Objects read display like their source.
+    fn object_reader_objects_display_like_source() {
+        let block = concat!(
+            "email:   rpsl-rs@github.com\n",
+            "nic-hdl: RPSL1-RIPE\n",
+            "\n",
+        );
+        let object =
+            ObjectReader::with_options(Cursor::new(format!("\n{block}")), ParserOptions::strict())
+                .next()
+                .unwrap()
+                .unwrap();
+
+        assert_eq!(object.to_string(), block);
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+        }
+    }
+
+    #[test]
+    /// Iteration ends after the underlying source returns an error.
+    fn object_reader_io_error_ends_iteration() {
+        let mut reader =
+            ObjectReader::with_options(io::BufReader::new(FailingReader), ParserOptions::strict());
+
+        assert!(matches!(reader.next(), Some(Err(ReadError::Io(_)))));
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{Object, ParseError};
+
+/// The result of a recovering parse, containing every object that could be parsed as well as
+/// an error for every object that could not.
+///
+/// Created by [`parse_whois_response_recovering`](crate::parse_whois_response_recovering).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Recovered<'a> {
+    /// The objects that were parsed successfully, in order of their appearance.
+    pub objects: Vec<Object<'a>>,
+    /// The errors of the objects that could not be parsed, in order of their appearance.
+    pub errors: Vec<ParseError>,
+}
+
+impl Recovered<'_> {
+    /// Whether all objects were parsed successfully.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl<'a> FromIterator<Result<Object<'a>, ParseError>> for Recovered<'a> {
+    fn from_iter<I: IntoIterator<Item = Result<Object<'a>, ParseError>>>(iter: I) -> Self {
+        let mut recovered = Self::default();
+        for result in iter {
+            match result {
+                Ok(object) => recovered.objects.push(object),
+                Err(err) => recovered.errors.push(err),
+            }
+        }
+        recovered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::{parse_whois_response_recovering, ParseErrorKind};
+
+    #[rstest]
+    #[case("", 0, &[])]
+    #[case("email: a@example.com\n\n", 1, &[])]
+    #[case(
+        concat!(
+            "% Note: this output has been filtered.\n",
+            "\n",
+            "email;  a@example.com\n",
+            "\n",
+            "email:  b@example.com\n",
+            "\n",
+            "email:  c@example.com\n",
+            "\x01\n",
+            "\n",
+            "email:  d@example.com\n",
+            "\n",
+            "email:  e@example.com\n",
+        ),
+        2,
+        &[
+            (3, ParseErrorKind::InvalidSeparator),
+            (8, ParseErrorKind::InvalidName),
+            (13, ParseErrorKind::MissingNewline)
+        ]
+    )]
+    fn recovering_parse_collects_all_errors(
+        #[case] rpsl: &str,
+        #[case] expected_objects: usize,
+        #[case] expected_errors: &[(usize, ParseErrorKind)],
+    ) {
+        let recovered = parse_whois_response_recovering(rpsl);
+        let errors: Vec<(usize, ParseErrorKind)> = recovered
+            .errors
+            .iter()
+            .map(|err| (err.line(), err.kind()))
+            .collect();
+        assert_eq!(recovered.objects.len(), expected_objects);
+        assert_eq!(errors, expected_errors);
+        assert_eq!(recovered.is_ok(), expected_errors.is_empty());
+    }
+}
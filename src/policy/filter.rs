@@ -0,0 +1,310 @@
+use std::{fmt, str::FromStr};
+
+use super::{parser, AsPathRegex, PolicyError};
+use crate::{AddressPrefixRange, Asn, RangeOperator, SetName};
+
+/// A filter selecting routes, as used by policies and the `filter` attribute of a `filter-set`.
+///
+/// Filters follow the grammar described in
+/// [RFC 2622, Section 5.4](https://datatracker.ietf.org/doc/html/rfc2622#section-5.4). `NOT` binds
+/// stronger than `AND`, which binds stronger than `OR`. Filters written next to each other
+/// without an operator are combined using `OR`.
+///
+/// # Example
+/// ```
+/// # use rpsl::policy::Filter;
+/// let filter: Filter = "AS-FOO AND NOT {0.0.0.0/0^+}".parse()?;
+///
+/// assert_eq!(
+///     filter,
+///     Filter::And(
+///         Box::new(Filter::Set { name: "AS-FOO".parse()?, operator: None }),
+///         Box::new(Filter::Not(Box::new(Filter::PrefixSet {
+///             prefixes: vec!["0.0.0.0/0^+".parse()?],
+///             operator: None,
+///         }))),
+///     )
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    /// Any route, written as `ANY`.
+    Any,
+    /// The routes originated by the peer, written as `PeerAS`.
+    PeerAs {
+        /// The range operator applied to the routes.
+        operator: Option<RangeOperator>,
+    },
+    /// The routes originated by an autonomous system.
+    Asn {
+        /// The AS number.
+        asn: Asn,
+        /// The range operator applied to the routes.
+        operator: Option<RangeOperator>,
+    },
+    /// The routes contained in an `as-set`, `route-set` or `filter-set`.
+    Set {
+        /// The name of the set.
+        name: SetName,
+        /// The range operator applied to the routes.
+        operator: Option<RangeOperator>,
+    },
+    /// An explicit set of address prefixes, e.g. `{192.0.2.0/24^+, 198.51.100.0/24}`.
+    PrefixSet {
+        /// The prefix ranges contained in the set.
+        prefixes: Vec<AddressPrefixRange>,
+        /// The range operator applied to all prefixes of the set.
+        operator: Option<RangeOperator>,
+    },
+    /// Routes whose AS path matches a regular expression, e.g. `<^AS1 AS2*$>`.
+    AsPath(AsPathRegex),
+    /// A method called on a route attribute, e.g. `community(65536:1)` or
+    /// `community.contains(65536:1)`.
+    Method {
+        /// The route attribute, e.g. `community`.
+        attribute: String,
+        /// The method called, if not using the short form.
+        method: Option<String>,
+        /// The arguments passed to the method.
+        arguments: Vec<String>,
+    },
+    /// A comparison of a route attribute, e.g. `community == {65536:1}`.
+    Comparison {
+        /// The route attribute, e.g. `community`.
+        attribute: String,
+        /// The comparison operator, e.g. `==`.
+        operator: String,
+        /// The value compared against.
+        value: String,
+    },
+    /// Routes not matched by the filter.
+    Not(Box<Filter>),
+    /// Routes matched by both filters.
+    And(Box<Filter>, Box<Filter>),
+    /// Routes matched by either filter.
+    Or(Box<Filter>, Box<Filter>),
+}
+
+impl Filter {
+    /// The binding strength of the filter's operator, where terms bind the strongest.
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Or(..) => 0,
+            Self::And(..) => 1,
+            Self::Not(_) => 2,
+            _ => 3,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, precedence: u8) -> fmt::Result {
+        if self.precedence() < precedence {
+            write!(f, "({self})")
+        } else {
+            write!(f, "{self}")
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = PolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::filter(s).map_err(|(offset, message)| PolicyError::at(s, offset, message))
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = |operator: &Option<RangeOperator>| match operator {
+            Some(operator) => operator.to_string(),
+            None => String::new(),
+        };
+        match self {
+            Self::Any => write!(f, "ANY"),
+            Self::PeerAs { operator: op } => write!(f, "PeerAS{}", operator(op)),
+            Self::Asn { asn, operator: op } => write!(f, "{asn}{}", operator(op)),
+            Self::Set { name, operator: op } => write!(f, "{name}{}", operator(op)),
+            Self::PrefixSet {
+                prefixes,
+                operator: op,
+            } => {
+                let prefixes: Vec<String> = prefixes.iter().map(ToString::to_string).collect();
+                write!(f, "{{{}}}{}", prefixes.join(", "), operator(op))
+            }
+            Self::AsPath(regex) => write!(f, "{regex}"),
+            Self::Method {
+                attribute,
+                method,
+                arguments,
+            } => match method {
+                Some(method) => write!(f, "{attribute}.{method}({})", arguments.join(", ")),
+                None => write!(f, "{attribute}({})", arguments.join(", ")),
+            },
+            Self::Comparison {
+                attribute,
+                operator,
+                value,
+            } => write!(f, "{attribute} {operator} {value}"),
+            Self::Not(filter) => {
+                write!(f, "NOT ")?;
+                filter.fmt_operand(f, 2)
+            }
+            Self::And(left, right) => {
+                left.fmt_operand(f, 1)?;
+                write!(f, " AND ")?;
+                right.fmt_operand(f, 2)
+            }
+            Self::Or(left, right) => {
+                left.fmt_operand(f, 0)?;
+                write!(f, " OR ")?;
+                right.fmt_operand(f, 1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn set(name: &str) -> Filter {
+        Filter::Set {
+            name: name.parse().unwrap(),
+            operator: None,
+        }
+    }
+
+    #[rstest]
+    #[case("ANY", Filter::Any)]
+    #[case("peeras", Filter::PeerAs { operator: None })]
+    #[case("AS1.10^+", Filter::Asn { asn: Asn::from(65546), operator: Some(RangeOperator::Inclusive) })]
+    #[case(
+        "RS-FOO:AS65536^24-32",
+        Filter::Set {
+            name: "RS-FOO:AS65536".parse().unwrap(),
+            operator: Some(RangeOperator::Range(24, 32)),
+        }
+    )]
+    #[case("fltr-martian", set("fltr-martian"))]
+    #[case(
+        "{ }^-",
+        Filter::PrefixSet { prefixes: Vec::new(), operator: Some(RangeOperator::Exclusive) }
+    )]
+    #[case(
+        "{192.0.2.0/24^+, 2001:db8::/32^48}",
+        Filter::PrefixSet {
+            prefixes: vec!["192.0.2.0/24^+".parse().unwrap(), "2001:db8::/32^48".parse().unwrap()],
+            operator: None,
+        }
+    )]
+    #[case(
+        "<^AS1 AS2*$>",
+        Filter::AsPath(AsPathRegex::Sequence(vec![
+            AsPathRegex::Start,
+            AsPathRegex::Asn(Asn::from(1)),
+            AsPathRegex::Repeat {
+                regex: Box::new(AsPathRegex::Asn(Asn::from(2))),
+                min: 0,
+                max: None,
+                same_as: false,
+            },
+            AsPathRegex::End,
+        ]))
+    )]
+    #[case(
+        "community(65536:1, 65536:2)",
+        Filter::Method {
+            attribute: "community".to_string(),
+            method: None,
+            arguments: vec!["65536:1".to_string(), "65536:2".to_string()],
+        }
+    )]
+    #[case(
+        "community.contains({65536:1})",
+        Filter::Method {
+            attribute: "community".to_string(),
+            method: Some("contains".to_string()),
+            arguments: vec!["{65536:1}".to_string()],
+        }
+    )]
+    #[case(
+        "community == {65536:1, 65536:2}",
+        Filter::Comparison {
+            attribute: "community".to_string(),
+            operator: "==".to_string(),
+            value: "{65536:1, 65536:2}".to_string(),
+        }
+    )]
+    #[case(
+        "AS-FOO OR AS-BAR AND AS-BAZ",
+        Filter::Or(
+            Box::new(set("AS-FOO")),
+            Box::new(Filter::And(Box::new(set("AS-BAR")), Box::new(set("AS-BAZ"))))
+        )
+    )]
+    #[case(
+        "(AS-FOO or AS-BAR) and AS-BAZ",
+        Filter::And(
+            Box::new(Filter::Or(Box::new(set("AS-FOO")), Box::new(set("AS-BAR")))),
+            Box::new(set("AS-BAZ"))
+        )
+    )]
+    #[case(
+        "AS-FOO AS-BAR AS-BAZ",
+        Filter::Or(
+            Box::new(Filter::Or(Box::new(set("AS-FOO")), Box::new(set("AS-BAR")))),
+            Box::new(set("AS-BAZ"))
+        )
+    )]
+    #[case(
+        "NOT NOT AS-FOO",
+        Filter::Not(Box::new(Filter::Not(Box::new(set("AS-FOO")))))
+    )]
+    #[case(
+        "NOT AS-FOO AND AS-BAR",
+        Filter::And(
+            Box::new(Filter::Not(Box::new(set("AS-FOO")))),
+            Box::new(set("AS-BAR"))
+        )
+    )]
+    fn filter_from_str(#[case] filter: &str, #[case] expected: Filter) {
+        assert_eq!(filter.parse::<Filter>().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("AS-FOO AND NOT {0.0.0.0/0^+}", "AS-FOO AND NOT {0.0.0.0/0^+}")]
+    #[case(
+        "as-foo as-bar and (as-baz or AS1)",
+        "as-foo OR as-bar AND (as-baz OR AS1)"
+    )]
+    #[case("NOT (AS-FOO OR AS-BAR)", "NOT (AS-FOO OR AS-BAR)")]
+    #[case("AS-FOO AND (AS-BAR AND AS-BAZ)", "AS-FOO AND (AS-BAR AND AS-BAZ)")]
+    #[case("PeerAS^+ AND <^PeerAS+$>", "PeerAS^+ AND <^PeerAS+$>")]
+    #[case(
+        "{ 192.0.2.0/24 , 198.51.100.0/24 }^25-32",
+        "{192.0.2.0/24, 198.51.100.0/24}^25-32"
+    )]
+    #[case("community( 65536:1 )", "community(65536:1)")]
+    fn filter_display(#[case] filter: &str, #[case] expected: &str) {
+        let filter: Filter = filter.parse().unwrap();
+        assert_eq!(filter.to_string(), expected);
+        assert_eq!(expected.parse::<Filter>().unwrap(), filter);
+    }
+
+    #[rstest]
+    #[case("", 0, "expected filter")]
+    #[case("AS-FOO AND", 10, "expected filter")]
+    #[case("(AS-FOO", 7, "expected `)`")]
+    #[case("{192.0.2.0/24", 13, "expected `}`")]
+    #[case("AS-FOO^33-32", 6, "expected range operator")]
+    #[case("{192.0.2.0/24^16}", 1, "expected address prefix")]
+    #[case("FOO", 0, "expected filter")]
+    #[case("AS-FOO; ANY", 6, "expected end of filter")]
+    fn invalid_filter_is_err(#[case] filter: &str, #[case] column: usize, #[case] message: &str) {
+        let err = filter.parse::<Filter>().unwrap_err();
+        assert_eq!((err.column() - 1, err.message()), (column, message));
+    }
+}
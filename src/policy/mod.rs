@@ -0,0 +1,530 @@
+//! Routing policy expressions as used by the `import`, `export`, `mp-import` and `mp-export`
+//! attributes of an `aut-num` object.
+//!
+//! Policies are parsed into a [`Policy`], following the grammar described in
+//! [RFC 2622, Section 6](https://datatracker.ietf.org/doc/html/rfc2622#section-6) and its
+//! multiprotocol extensions in [RFC 4012](https://datatracker.ietf.org/doc/html/rfc4012).
+//! Keywords are matched regardless of their case.
+//!
+//! # Example
+//! ```
+//! # use rpsl::{
+//! #     parse_object,
+//! #     policy::{Expression, Filter, Peering, Policy, PolicyExpression},
+//! #     AsTerm, Asn,
+//! # };
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let aut_num = parse_object("
+//! aut-num:        AS65536
+//! import:         from AS65537 action pref = 100;
+//!                 accept AS-EXAMPLE
+//!
+//! ")?;
+//! let policy = Policy::try_from(&aut_num[1])?;
+//!
+//! let PolicyExpression::Term(term) = policy.expression else { unreachable!() };
+//! let peering = &term[0].peerings[0];
+//! assert_eq!(
+//!     peering.peering,
+//!     Peering::Peering {
+//!         as_expression: Expression::Term(AsTerm::Asn(Asn::from(65537))),
+//!         remote_router: None,
+//!         local_router: None,
+//!     }
+//! );
+//! assert_eq!(peering.actions.len(), 1);
+//! assert_eq!(term[0].filter, Filter::Set { name: "AS-EXAMPLE".parse()?, operator: None });
+//! # Ok(())
+//! # }
+//! ```
+use std::{fmt, net::IpAddr, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{AsTerm, Attribute, SetName};
+
+pub use filter::Filter;
+pub use regex::{AsPathRegex, ClassMember};
+
+mod filter;
+mod parser;
+mod regex;
+
+/// The kind of a policy, determined by the attribute it is contained in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PolicyKind {
+    /// An `import` policy.
+    Import,
+    /// An `export` policy.
+    Export,
+    /// A multiprotocol `mp-import` policy.
+    MpImport,
+    /// A multiprotocol `mp-export` policy.
+    MpExport,
+}
+
+impl PolicyKind {
+    /// The name of the attribute containing policies of this kind.
+    #[must_use]
+    pub fn attribute_name(self) -> &'static str {
+        match self {
+            Self::Import => "import",
+            Self::Export => "export",
+            Self::MpImport => "mp-import",
+            Self::MpExport => "mp-export",
+        }
+    }
+
+    /// Whether the policy describes routes received from peers.
+    #[must_use]
+    pub fn is_import(self) -> bool {
+        matches!(self, Self::Import | Self::MpImport)
+    }
+
+    /// Whether the policy is a multiprotocol policy that may specify address families.
+    #[must_use]
+    pub fn is_multiprotocol(self) -> bool {
+        matches!(self, Self::MpImport | Self::MpExport)
+    }
+}
+
+impl FromStr for PolicyKind {
+    type Err = PolicyError;
+
+    /// Determine the kind of policy from the name of its attribute, ignoring ASCII case.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        [Self::Import, Self::Export, Self::MpImport, Self::MpExport]
+            .into_iter()
+            .find(|kind| kind.attribute_name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| PolicyError::unsupported_attribute(name))
+    }
+}
+
+/// A parsed routing policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    /// The kind of the policy.
+    pub kind: PolicyKind,
+    /// The protocol routes are exchanged with, e.g. `BGP4`.
+    pub protocol: Option<String>,
+    /// The protocol routes are imported into or exported from.
+    pub into: Option<String>,
+    /// The address families the policy applies to, only present in multiprotocol policies.
+    pub afi: Option<Vec<Afi>>,
+    /// The policy expression.
+    pub expression: PolicyExpression,
+}
+
+impl Policy {
+    /// Parse a single line policy of the given kind.
+    ///
+    /// # Errors
+    /// Returns a [`PolicyError`] pointing to the position in the text that could not be parsed.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::policy::{Policy, PolicyKind};
+    /// let policy = Policy::parse(PolicyKind::MpExport, "afi ipv6 to AS65537 announce AS65536")?;
+    /// assert_eq!(policy.afi.unwrap()[0].to_string(), "ipv6");
+    ///
+    /// let err = Policy::parse(PolicyKind::Import, "from AS65537 announce AS65536").unwrap_err();
+    /// assert_eq!(err.column(), 14);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parse(kind: PolicyKind, text: &str) -> Result<Self, PolicyError> {
+        parser::policy(kind, text)
+            .map_err(|(offset, message)| PolicyError::at(text, offset, message))
+    }
+}
+
+impl TryFrom<&Attribute<'_>> for Policy {
+    type Error = PolicyError;
+
+    /// Parse the policy contained in an `import`, `export`, `mp-import` or `mp-export` attribute.
+    ///
+    /// Values spanning multiple lines are joined by a single space before being parsed. The
+    /// position of a [`PolicyError`] refers to the line and column within the attribute value.
+    fn try_from(attribute: &Attribute<'_>) -> Result<Self, Self::Error> {
+        let kind: PolicyKind = attribute.name.parse()?;
+
+        let mut text = String::new();
+        let mut line_starts: Vec<(usize, usize)> = Vec::new();
+        for (i, line) in attribute.value.values().into_iter().enumerate() {
+            let Some(line) = line else { continue };
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            line_starts.push((text.len(), i + 1));
+            text.push_str(line);
+        }
+
+        parser::policy(kind, &text).map_err(|(offset, message)| {
+            let (start, line) = line_starts
+                .iter()
+                .rev()
+                .find(|(start, _)| *start <= offset)
+                .copied()
+                .unwrap_or((0, 1));
+            let column = text[start..offset].chars().count() + 1;
+            PolicyError::new(message, offset, line, column)
+        })
+    }
+}
+
+/// An import or export expression, optionally combined with further expressions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyExpression {
+    /// A single policy term.
+    Term(PolicyTerm),
+    /// A term whose routes are overridden by the routes matched by the following expression.
+    Except {
+        /// The term that is overridden.
+        term: PolicyTerm,
+        /// The address families the following expression applies to.
+        afi: Option<Vec<Afi>>,
+        /// The expression taking precedence.
+        expression: Box<PolicyExpression>,
+    },
+    /// A term whose routes are further restricted by the following expression.
+    Refine {
+        /// The term that is refined.
+        term: PolicyTerm,
+        /// The address families the following expression applies to.
+        afi: Option<Vec<Afi>>,
+        /// The expression refining the term.
+        expression: Box<PolicyExpression>,
+    },
+}
+
+/// One or more policy factors, written within curly braces if there are multiple.
+pub type PolicyTerm = Vec<PolicyFactor>;
+
+/// A filter applied to routes exchanged with one or more peerings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyFactor {
+    /// The peerings and the actions applied to their routes.
+    pub peerings: Vec<PeeringAction>,
+    /// The filter selecting the routes that are accepted or announced.
+    pub filter: Filter,
+}
+
+/// A peering and the actions applied to routes exchanged with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeeringAction {
+    /// The peering routes are exchanged with.
+    pub peering: Peering,
+    /// The actions applied to matching routes.
+    pub actions: Vec<Action>,
+}
+
+/// A set of BGP peerings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Peering {
+    /// A reference to a `peering-set` object.
+    Set(SetName),
+    /// A peering described inline.
+    Peering {
+        /// The autonomous systems peered with.
+        as_expression: Expression<AsTerm>,
+        /// The routers of the peer.
+        remote_router: Option<Expression<Router>>,
+        /// The local routers, following the `at` keyword.
+        local_router: Option<Expression<Router>>,
+    },
+}
+
+/// An expression combining terms using the `AND`, `OR` and `EXCEPT` operators.
+///
+/// `AND` and `EXCEPT` bind stronger than `OR`, while operators of the same precedence are
+/// evaluated from left to right.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression<T> {
+    /// A single term.
+    Term(T),
+    /// Both expressions.
+    And(Box<Expression<T>>, Box<Expression<T>>),
+    /// Either expression.
+    Or(Box<Expression<T>>, Box<Expression<T>>),
+    /// The first expression, excluding the second.
+    Except(Box<Expression<T>>, Box<Expression<T>>),
+}
+
+/// A router, referenced by its address or by the name of an `inet-rtr` or `rtr-set` object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Router {
+    /// An IP address of a router.
+    Address(IpAddr),
+    /// The name of an `inet-rtr` or `rtr-set` object.
+    Name(String),
+}
+
+/// An action modifying a route attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// An operator applied to a route attribute, e.g. `pref = 100`.
+    Operator {
+        /// The route attribute, e.g. `pref`.
+        attribute: String,
+        /// The operator, e.g. `=`.
+        operator: String,
+        /// The operand.
+        value: String,
+    },
+    /// A method called on a route attribute, e.g. `community.append(65536:1)`.
+    Method {
+        /// The route attribute, e.g. `community`.
+        attribute: String,
+        /// The method, e.g. `append`.
+        method: String,
+        /// The arguments passed to the method.
+        arguments: Vec<String>,
+    },
+}
+
+/// An address family identifier as defined in RFC 4012, e.g. `ipv6.unicast`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Afi {
+    /// The address family.
+    pub family: AddressFamily,
+    /// The subsequent address family, if specified.
+    pub safi: Option<Safi>,
+}
+
+/// An address family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressFamily {
+    /// IPv4.
+    Ipv4,
+    /// IPv6.
+    Ipv6,
+    /// Both IPv4 and IPv6.
+    Any,
+}
+
+/// A subsequent address family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Safi {
+    /// Unicast routes.
+    Unicast,
+    /// Multicast routes.
+    Multicast,
+}
+
+impl FromStr for Afi {
+    type Err = PolicyError;
+
+    /// Parse an address family identifier, ignoring ASCII case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (family, safi) = match s.split_once('.') {
+            Some((family, safi)) => (family, Some(safi)),
+            None => (s, None),
+        };
+        let invalid = || PolicyError::new(format!("invalid afi `{s}`"), 0, 1, 1);
+
+        let family = match family.to_ascii_lowercase().as_str() {
+            "ipv4" => AddressFamily::Ipv4,
+            "ipv6" => AddressFamily::Ipv6,
+            "any" => AddressFamily::Any,
+            _ => return Err(invalid()),
+        };
+        let safi = match safi.map(str::to_ascii_lowercase).as_deref() {
+            None => None,
+            Some("unicast") => Some(Safi::Unicast),
+            Some("multicast") => Some(Safi::Multicast),
+            Some(_) => return Err(invalid()),
+        };
+
+        Ok(Self { family, safi })
+    }
+}
+
+impl fmt::Display for Afi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let family = match self.family {
+            AddressFamily::Ipv4 => "ipv4",
+            AddressFamily::Ipv6 => "ipv6",
+            AddressFamily::Any => "any",
+        };
+        match self.safi {
+            None => write!(f, "{family}"),
+            Some(Safi::Unicast) => write!(f, "{family}.unicast"),
+            Some(Safi::Multicast) => write!(f, "{family}.multicast"),
+        }
+    }
+}
+
+/// An error that can occur when parsing a policy.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid policy at line {line}, column {column}: {message}")]
+pub struct PolicyError {
+    message: String,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl PolicyError {
+    fn new(message: String, offset: usize, line: usize, column: usize) -> Self {
+        Self {
+            message,
+            offset,
+            line,
+            column,
+        }
+    }
+
+    /// Create an error at the given byte offset of a single line of text.
+    fn at(text: &str, offset: usize, message: String) -> Self {
+        Self::new(message, offset, 1, text[..offset].chars().count() + 1)
+    }
+
+    fn unsupported_attribute(name: &str) -> Self {
+        Self::new(format!("`{name}` is not a policy attribute"), 0, 1, 1)
+    }
+
+    /// A description of the error.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The byte offset of the error within the policy text, with multiple lines joined by a
+    /// single space.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The line of the attribute value the error occurred on, starting at 1.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column within the line the error occurred on, starting at 1.
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::{parse_object, Asn};
+
+    #[test]
+    /// Values spanning multiple lines are joined before being parsed.
+    fn policy_from_multi_line_attribute() {
+        let object = parse_object(concat!(
+            "aut-num:        AS65536\n",
+            "mp-import:      afi ipv6.unicast\n",
+            "                from AS65537 at 2001:db8::1\n",
+            "                action pref = 100;\n",
+            "                accept AS65537\n",
+            "\n"
+        ))
+        .unwrap();
+
+        let policy = Policy::try_from(&object[1]).unwrap();
+        assert_eq!(policy.kind, PolicyKind::MpImport);
+        assert_eq!(
+            policy.afi,
+            Some(vec![Afi {
+                family: AddressFamily::Ipv6,
+                safi: Some(Safi::Unicast)
+            }])
+        );
+        assert_eq!(
+            policy.expression,
+            PolicyExpression::Term(vec![PolicyFactor {
+                peerings: vec![PeeringAction {
+                    peering: Peering::Peering {
+                        as_expression: Expression::Term(AsTerm::Asn(Asn::from(65537))),
+                        remote_router: None,
+                        local_router: Some(Expression::Term(Router::Address(
+                            "2001:db8::1".parse().unwrap()
+                        ))),
+                    },
+                    actions: vec![Action::Operator {
+                        attribute: "pref".to_string(),
+                        operator: "=".to_string(),
+                        value: "100".to_string(),
+                    }],
+                }],
+                filter: Filter::Asn {
+                    asn: Asn::from(65537),
+                    operator: None,
+                },
+            }])
+        );
+    }
+
+    #[rstest]
+    #[case(
+        concat!(
+            "aut-num:        AS65536\n",
+            "import:         from AS65537\n",
+            "                accept\n",
+            "\n"
+        ),
+        2,
+        7
+    )]
+    #[case(
+        concat!(
+            "aut-num:        AS65536\n",
+            "import:         from AS65537\n",
+            "+\n",
+            "                action pref = 100;\n",
+            "                announce AS65537\n",
+            "\n"
+        ),
+        4,
+        1
+    )]
+    #[case(
+        concat!(
+            "aut-num:        AS65536\n",
+            "import:         fromm AS65537 accept ANY\n",
+            "\n"
+        ),
+        1,
+        1
+    )]
+    /// Errors point to the line and column within the original attribute value.
+    fn policy_error_points_into_attribute_value(
+        #[case] rpsl: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+    ) {
+        let object = parse_object(rpsl).unwrap();
+        let err = Policy::try_from(&object[1]).unwrap_err();
+        assert_eq!((err.line(), err.column()), (line, column));
+    }
+
+    #[test]
+    fn policy_from_non_policy_attribute_is_err() {
+        let object = parse_object("aut-num: AS65536\n\n").unwrap();
+        let err = Policy::try_from(&object[0]).unwrap_err();
+        assert_eq!(err.message(), "`aut-num` is not a policy attribute");
+    }
+
+    #[rstest]
+    #[case("ipv4", AddressFamily::Ipv4, None)]
+    #[case("IPv6.Unicast", AddressFamily::Ipv6, Some(Safi::Unicast))]
+    #[case("any.multicast", AddressFamily::Any, Some(Safi::Multicast))]
+    fn afi_from_str(#[case] s: &str, #[case] family: AddressFamily, #[case] safi: Option<Safi>) {
+        assert_eq!(s.parse::<Afi>().unwrap(), Afi { family, safi });
+    }
+
+    #[rstest]
+    #[case("ipv5")]
+    #[case("ipv4.broadcast")]
+    #[case("")]
+    fn afi_from_invalid_str_is_err(#[case] s: &str) {
+        assert!(s.parse::<Afi>().is_err());
+    }
+}
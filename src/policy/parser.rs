@@ -0,0 +1,1151 @@
+//! Parsers for the grammar of policy expressions.
+//!
+//! Keywords are matched case insensitively and must be followed by a character that cannot be
+//! part of a word. Every parser consumes the whitespace following the tokens it parses, while
+//! failing parsers leave the input untouched so that errors point to the offending token.
+use std::net::IpAddr;
+
+use winnow::{
+    ascii::{dec_uint, multispace0},
+    combinator::{alt, cut_err, delimited, eof, fail, opt, preceded, repeat, terminated},
+    error::{ContextError, StrContext, StrContextValue},
+    stream::Stream,
+    token::take_while,
+    PResult, Parser,
+};
+
+use super::{
+    Action, Afi, AsPathRegex, ClassMember, Expression, Filter, Peering, PeeringAction, Policy,
+    PolicyExpression, PolicyFactor, PolicyKind, PolicyTerm, Router,
+};
+use crate::{AddressPrefixRange, AsTerm, Asn, RangeOperator, SetClass, SetName};
+
+/// The classes of sets that can be referenced by filters.
+const FILTER_SET_CLASSES: [SetClass; 3] =
+    [SetClass::AsSet, SetClass::RouteSet, SetClass::FilterSet];
+
+/// Words that cannot be used as the name of a router.
+const KEYWORDS: [&str; 13] = [
+    "accept", "action", "afi", "and", "announce", "at", "except", "from", "into", "or", "protocol",
+    "refine", "to",
+];
+
+/// Parse a policy of the given kind, returning the byte offset and a description of the
+/// error if it cannot be parsed.
+pub(super) fn policy(kind: PolicyKind, text: &str) -> Result<Policy, (usize, String)> {
+    complete(policy_parser(kind), text, "end of policy")
+}
+
+/// Parse a filter, returning the byte offset and a description of the error if it cannot be
+/// parsed.
+pub(super) fn filter(text: &str) -> Result<Filter, (usize, String)> {
+    complete(filter_expression, text, "end of filter")
+}
+
+/// Parse an AS path regular expression enclosed in angle brackets, returning the byte offset
+/// and a description of the error if it cannot be parsed.
+pub(super) fn as_path_regex(text: &str) -> Result<AsPathRegex, (usize, String)> {
+    complete(as_path, text, "end of AS path regular expression")
+}
+
+/// Apply the parser to the entire text, allowing leading whitespace.
+fn complete<'s, O, P>(parser: P, text: &'s str, end: &'static str) -> Result<O, (usize, String)>
+where
+    P: Parser<&'s str, O, ContextError>,
+{
+    delimited(
+        multispace0,
+        parser,
+        cut_err(eof.context(expected_description(end))),
+    )
+    .parse(text)
+    .map_err(|err| {
+        let message = err.inner().to_string();
+        if message.is_empty() {
+            (err.offset(), "unexpected input".to_string())
+        } else {
+            (err.offset(), message)
+        }
+    })
+}
+
+/// Generate a parser for a complete policy of the given kind.
+fn policy_parser<'s>(kind: PolicyKind) -> impl Parser<&'s str, Policy, ContextError> {
+    move |input: &mut &'s str| {
+        let protocol =
+            opt(preceded(keyword("protocol"), cut_err(protocol_name()))).parse_next(input)?;
+        let into = opt(preceded(keyword("into"), cut_err(protocol_name()))).parse_next(input)?;
+        let afi = if kind.is_multiprotocol() {
+            opt(afi_list).parse_next(input)?
+        } else {
+            None
+        };
+        let expression = policy_expression(kind, input)?;
+
+        Ok(Policy {
+            kind,
+            protocol: protocol.map(ToString::to_string),
+            into: into.map(ToString::to_string),
+            afi,
+            expression,
+        })
+    }
+}
+
+/// A policy term, optionally followed by `EXCEPT` or `REFINE` and another policy expression.
+fn policy_expression(kind: PolicyKind, input: &mut &str) -> PResult<PolicyExpression> {
+    let term = policy_term(kind, input)?;
+
+    let Some(operator) = opt(alt((keyword("except"), keyword("refine")))).parse_next(input)? else {
+        return Ok(PolicyExpression::Term(term));
+    };
+    let afi = if kind.is_multiprotocol() {
+        opt(afi_list).parse_next(input)?
+    } else {
+        None
+    };
+    let expression =
+        Box::new(cut_err(|i: &mut &str| policy_expression(kind, i)).parse_next(input)?);
+
+    if operator.eq_ignore_ascii_case("except") {
+        Ok(PolicyExpression::Except {
+            term,
+            afi,
+            expression,
+        })
+    } else {
+        Ok(PolicyExpression::Refine {
+            term,
+            afi,
+            expression,
+        })
+    }
+}
+
+/// A single policy factor, or a list of factors terminated by semicolons within curly braces.
+fn policy_term(kind: PolicyKind, input: &mut &str) -> PResult<PolicyTerm> {
+    if opt(token('{')).parse_next(input)?.is_none() {
+        let factor = policy_factor(kind, input)?;
+        opt(token(';')).parse_next(input)?;
+        return Ok(vec![factor]);
+    }
+
+    let mut factors = Vec::new();
+    while opt(token('}')).parse_next(input)?.is_none() {
+        factors.push(cut_err(|i: &mut &str| policy_factor(kind, i)).parse_next(input)?);
+        if opt(token(';')).parse_next(input)?.is_none() {
+            cut_err(token('}')).parse_next(input)?;
+            break;
+        }
+    }
+    Ok(factors)
+}
+
+/// One or more peerings with optional actions, followed by a filter.
+fn policy_factor(kind: PolicyKind, input: &mut &str) -> PResult<PolicyFactor> {
+    let (peering_keyword, filter_keyword) = if kind.is_import() {
+        ("from", "accept")
+    } else {
+        ("to", "announce")
+    };
+
+    let peerings = repeat(
+        1..,
+        preceded(
+            keyword(peering_keyword),
+            cut_err((peering, opt(preceded(keyword("action"), cut_err(actions))))),
+        )
+        .map(|(peering, actions)| PeeringAction {
+            peering,
+            actions: actions.unwrap_or_default(),
+        }),
+    )
+    .parse_next(input)?;
+    cut_err(keyword(filter_keyword)).parse_next(input)?;
+    let filter = cut_err(filter_expression).parse_next(input)?;
+
+    Ok(PolicyFactor { peerings, filter })
+}
+
+/// A reference to a peering set, or an AS expression followed by optional router expressions.
+fn peering(input: &mut &str) -> PResult<Peering> {
+    let peering_set = |word: &str| parse_set_name(word, &[SetClass::PeeringSet]);
+    if let Some(name) = opt(word_map(peering_set, "peering")).parse_next(input)? {
+        return Ok(Peering::Set(name));
+    }
+
+    let as_expression = expression(as_term, input)?;
+    let remote_router = opt(|i: &mut &str| expression(router, i)).parse_next(input)?;
+    let local_router = opt(preceded(
+        keyword("at"),
+        cut_err(|i: &mut &str| expression(router, i)),
+    ))
+    .parse_next(input)?;
+
+    Ok(Peering::Peering {
+        as_expression,
+        remote_router,
+        local_router,
+    })
+}
+
+/// Terms joined by `OR`, where each operand may itself be joined by `AND` or `EXCEPT`.
+fn expression<'s, T>(
+    term: fn(&mut &'s str) -> PResult<T>,
+    input: &mut &'s str,
+) -> PResult<Expression<T>> {
+    let mut expression = and_expression(term, input)?;
+    while opt(keyword("or")).parse_next(input)?.is_some() {
+        let right = cut_err(|i: &mut &'s str| and_expression(term, i)).parse_next(input)?;
+        expression = Expression::Or(Box::new(expression), Box::new(right));
+    }
+    Ok(expression)
+}
+
+/// Terms joined by `AND` or `EXCEPT`.
+fn and_expression<'s, T>(
+    term: fn(&mut &'s str) -> PResult<T>,
+    input: &mut &'s str,
+) -> PResult<Expression<T>> {
+    let mut expression = primary_expression(term, input)?;
+    while let Some(operator) = opt(alt((keyword("and"), keyword("except")))).parse_next(input)? {
+        let right =
+            Box::new(cut_err(|i: &mut &'s str| primary_expression(term, i)).parse_next(input)?);
+        expression = if operator.eq_ignore_ascii_case("and") {
+            Expression::And(Box::new(expression), right)
+        } else {
+            Expression::Except(Box::new(expression), right)
+        };
+    }
+    Ok(expression)
+}
+
+/// A single term, or an expression within parentheses.
+fn primary_expression<'s, T>(
+    term: fn(&mut &'s str) -> PResult<T>,
+    input: &mut &'s str,
+) -> PResult<Expression<T>> {
+    if opt(token('(')).parse_next(input)?.is_none() {
+        return term.map(Expression::Term).parse_next(input);
+    }
+    let expression = cut_err(|i: &mut &'s str| expression(term, i)).parse_next(input)?;
+    cut_err(token(')')).parse_next(input)?;
+    Ok(expression)
+}
+
+/// An AS number or the name of an `as-set`.
+fn as_term(input: &mut &str) -> PResult<AsTerm> {
+    word_map(parse_as_term, "AS number or as-set").parse_next(input)
+}
+
+/// An IP address or the name of an `inet-rtr` or `rtr-set`.
+fn router(input: &mut &str) -> PResult<Router> {
+    word_matching(
+        |word| !KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(word)),
+        "router",
+    )
+    .map(|word| {
+        word.parse::<IpAddr>()
+            .map_or_else(|_| Router::Name(word.to_string()), Router::Address)
+    })
+    .parse_next(input)
+}
+
+/// One or more actions, each terminated by a semicolon.
+///
+/// The semicolon following the last action may be omitted.
+fn actions(input: &mut &str) -> PResult<Vec<Action>> {
+    let mut actions = vec![action(input)?];
+    while opt(token(';')).parse_next(input)?.is_some() {
+        if input.is_empty() || starts_with_keyword(input, &["from", "to", "accept", "announce"]) {
+            break;
+        }
+        actions.push(cut_err(action).parse_next(input)?);
+    }
+    Ok(actions)
+}
+
+/// An operator applied to, or a method called on a route attribute.
+fn action(input: &mut &str) -> PResult<Action> {
+    let attribute = terminated(
+        take_while(1.., ('A'..='Z', 'a'..='z', '0'..='9', '-', '_')),
+        multispace0,
+    )
+    .context(expected_description("action"))
+    .parse_next(input)?;
+
+    let method_call =
+        input.starts_with('.') && input[1..].starts_with(|c: char| c.is_ascii_alphabetic());
+    if method_call {
+        let method = preceded(
+            '.',
+            terminated(
+                take_while(1.., ('A'..='Z', 'a'..='z', '0'..='9', '-', '_')),
+                multispace0,
+            ),
+        )
+        .parse_next(input)?;
+        cut_err(token('(')).parse_next(input)?;
+        let arguments = scan(input, "{(", "})", &[')'], &[]);
+        cut_err(token(')')).parse_next(input)?;
+
+        return Ok(Action::Method {
+            attribute: attribute.to_string(),
+            method: method.to_string(),
+            arguments: split_arguments(arguments),
+        });
+    }
+
+    let operator = cut_err(terminated(
+        take_while(1.., ['=', '<', '>', '!', '+', '-', '*', '/', '.']),
+        multispace0,
+    ))
+    .context(expected_description("operator"))
+    .parse_next(input)?;
+    let value = scan(
+        input,
+        "{(",
+        "})",
+        &[';'],
+        &["from", "to", "accept", "announce"],
+    );
+    if value.is_empty() {
+        return cut_err(fail)
+            .context(expected_description("value"))
+            .parse_next(input);
+    }
+
+    Ok(Action::Operator {
+        attribute: attribute.to_string(),
+        operator: operator.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Filters combined using `OR`, which is implied between filters without an operator.
+fn filter_expression(input: &mut &str) -> PResult<Filter> {
+    let mut filter = and_filter(input)?;
+    loop {
+        let right = if opt(keyword("or")).parse_next(input)?.is_some() {
+            cut_err(and_filter).parse_next(input)?
+        } else if let Some(right) = opt(and_filter).parse_next(input)? {
+            right
+        } else {
+            break;
+        };
+        filter = Filter::Or(Box::new(filter), Box::new(right));
+    }
+    Ok(filter)
+}
+
+/// Filters combined using `AND`.
+fn and_filter(input: &mut &str) -> PResult<Filter> {
+    let mut filter = not_filter(input)?;
+    while opt(keyword("and")).parse_next(input)?.is_some() {
+        let right = cut_err(not_filter).parse_next(input)?;
+        filter = Filter::And(Box::new(filter), Box::new(right));
+    }
+    Ok(filter)
+}
+
+/// A filter, optionally negated using `NOT`.
+fn not_filter(input: &mut &str) -> PResult<Filter> {
+    if opt(keyword("not")).parse_next(input)?.is_some() {
+        let filter = cut_err(not_filter).parse_next(input)?;
+        return Ok(Filter::Not(Box::new(filter)));
+    }
+    primary_filter(input)
+}
+
+/// A single filter term, or a filter within parentheses.
+fn primary_filter(input: &mut &str) -> PResult<Filter> {
+    if input.starts_with('{') {
+        return prefix_set(input);
+    }
+    if input.starts_with('<') {
+        return as_path.map(Filter::AsPath).parse_next(input);
+    }
+    if opt(token('(')).parse_next(input)?.is_some() {
+        let filter = cut_err(filter_expression).parse_next(input)?;
+        cut_err(token(')')).parse_next(input)?;
+        return Ok(filter);
+    }
+    filter_term(input)
+}
+
+/// A named filter term, a method called on a route attribute or a comparison of a route
+/// attribute.
+fn filter_term(input: &mut &str) -> PResult<Filter> {
+    let word = peek_word(input);
+    let filter = if word.eq_ignore_ascii_case("ANY") {
+        input.next_slice(word.len());
+        Filter::Any
+    } else if word.eq_ignore_ascii_case("PeerAS") {
+        input.next_slice(word.len());
+        Filter::PeerAs {
+            operator: opt(range_operator_parser).parse_next(input)?,
+        }
+    } else if let Some(asn) = parse_asn(word) {
+        input.next_slice(word.len());
+        Filter::Asn {
+            asn,
+            operator: opt(range_operator_parser).parse_next(input)?,
+        }
+    } else if let Some(name) = parse_set_name(word, &FILTER_SET_CLASSES) {
+        input.next_slice(word.len());
+        Filter::Set {
+            name,
+            operator: opt(range_operator_parser).parse_next(input)?,
+        }
+    } else {
+        let checkpoint = input.checkpoint();
+        if !word.is_empty() {
+            input.next_slice(word.len());
+            multispace0.parse_next(input)?;
+            if let Some(filter) = opt(attribute_filter(word)).parse_next(input)? {
+                return Ok(filter);
+            }
+        }
+        input.reset(&checkpoint);
+        return fail
+            .context(expected_description("filter"))
+            .parse_next(input);
+    };
+    multispace0.parse_next(input)?;
+    Ok(filter)
+}
+
+/// Generate a parser for the method call or comparison following a route attribute.
+fn attribute_filter<'s>(word: &'s str) -> impl Parser<&'s str, Filter, ContextError> {
+    move |input: &mut &'s str| {
+        if opt(token('(')).parse_next(input)?.is_some() {
+            let arguments = scan(input, "{(", "})", &[')'], &[]);
+            cut_err(token(')')).parse_next(input)?;
+            let (attribute, method) = match word.split_once('.') {
+                Some((attribute, method)) => (attribute, Some(method.to_string())),
+                None => (word, None),
+            };
+            return Ok(Filter::Method {
+                attribute: attribute.to_string(),
+                method,
+                arguments: split_arguments(arguments),
+            });
+        }
+
+        let operator =
+            terminated(take_while(1.., ['=', '<', '>', '!']), multispace0).parse_next(input)?;
+        let value = if input.starts_with('{') {
+            terminated(
+                (
+                    '{',
+                    |i: &mut &'s str| Ok(scan(i, "{", "}", &['}'], &[])),
+                    '}',
+                )
+                    .take(),
+                multispace0,
+            )
+            .parse_next(input)?
+        } else {
+            cut_err(word_matching(|_| true, "value")).parse_next(input)?
+        };
+        Ok(Filter::Comparison {
+            attribute: word.to_string(),
+            operator: operator.to_string(),
+            value: value.trim_end().to_string(),
+        })
+    }
+}
+
+/// A comma separated set of address prefixes within curly braces, optionally followed by a
+/// range operator.
+fn prefix_set(input: &mut &str) -> PResult<Filter> {
+    token('{').parse_next(input)?;
+    let mut prefixes = Vec::new();
+    if !input.starts_with('}') {
+        loop {
+            prefixes.push(cut_err(prefix_range).parse_next(input)?);
+            if opt(token(',')).parse_next(input)?.is_none() {
+                break;
+            }
+        }
+    }
+    cut_err('}'.context(StrContext::Expected(StrContextValue::CharLiteral('}'))))
+        .parse_next(input)?;
+    let operator = opt(range_operator_parser).parse_next(input)?;
+    multispace0.parse_next(input)?;
+
+    Ok(Filter::PrefixSet { prefixes, operator })
+}
+
+/// An address prefix with an optional range operator.
+fn prefix_range(input: &mut &str) -> PResult<AddressPrefixRange> {
+    terminated(
+        take_while(1.., |c: char| {
+            c.is_ascii_hexdigit() || matches!(c, '.' | ':' | '/' | '^' | '-' | '+')
+        })
+        .verify_map(|range: &str| range.parse().ok()),
+        multispace0,
+    )
+    .context(expected_description("address prefix"))
+    .parse_next(input)
+}
+
+/// A range operator such as `^+` or `^24-32`.
+fn range_operator_parser(input: &mut &str) -> PResult<RangeOperator> {
+    if !input.starts_with('^') {
+        return fail.parse_next(input);
+    }
+    cut_err(
+        take_while(1.., ('^', '-', '+', '0'..='9'))
+            .verify_map(|operator: &str| operator.parse().ok())
+            .context(expected_description("range operator")),
+    )
+    .parse_next(input)
+}
+
+/// An AS path regular expression enclosed in angle brackets.
+fn as_path(input: &mut &str) -> PResult<AsPathRegex> {
+    token('<').parse_next(input)?;
+    let regex = regex_alternation(input)?;
+    cut_err(token('>')).parse_next(input)?;
+    Ok(regex)
+}
+
+/// Regular expressions separated by `|`.
+fn regex_alternation(input: &mut &str) -> PResult<AsPathRegex> {
+    let mut alternatives = vec![regex_sequence(input)?];
+    while opt(token('|')).parse_next(input)?.is_some() {
+        alternatives.push(regex_sequence(input)?);
+    }
+    if alternatives.len() == 1 {
+        return Ok(alternatives.remove(0));
+    }
+    Ok(AsPathRegex::Alternation(alternatives))
+}
+
+/// Regular expressions matched one after another.
+fn regex_sequence(input: &mut &str) -> PResult<AsPathRegex> {
+    let mut regexes: Vec<AsPathRegex> = repeat(0.., regex_item).parse_next(input)?;
+    if regexes.len() == 1 {
+        return Ok(regexes.remove(0));
+    }
+    Ok(AsPathRegex::Sequence(regexes))
+}
+
+/// An anchor, or an atom followed by any number of repetition operators.
+fn regex_item(input: &mut &str) -> PResult<AsPathRegex> {
+    if opt(token('^')).parse_next(input)?.is_some() {
+        return Ok(AsPathRegex::Start);
+    }
+    if opt(token('$')).parse_next(input)?.is_some() {
+        return Ok(AsPathRegex::End);
+    }
+
+    let mut regex = regex_atom(input)?;
+    loop {
+        let same_as = input.starts_with('~');
+        if same_as && !regex.is_single_as() {
+            return cut_err(fail.context(expected_description("single AS before `~`")))
+                .parse_next(input);
+        }
+        let (min, max) = if same_as {
+            preceded('~', cut_err(repetition)).parse_next(input)?
+        } else if let Some(repetition) = opt(repetition).parse_next(input)? {
+            repetition
+        } else {
+            break;
+        };
+        multispace0.parse_next(input)?;
+        regex = AsPathRegex::Repeat {
+            regex: Box::new(regex),
+            min,
+            max,
+            same_as,
+        };
+    }
+    Ok(regex)
+}
+
+/// A repetition operator, returning the minimum and the optional maximum number of repetitions.
+fn repetition(input: &mut &str) -> PResult<(usize, Option<usize>)> {
+    if input.starts_with('{') {
+        return cut_err(
+            delimited('{', (dec_uint, opt(preceded(',', opt(dec_uint)))), '}')
+                .verify_map(|(min, max): (usize, Option<Option<usize>>)| match max {
+                    None => Some((min, Some(min))),
+                    Some(None) => Some((min, None)),
+                    Some(Some(max)) => (min <= max).then_some((min, Some(max))),
+                })
+                .context(expected_description("repetition bounds")),
+        )
+        .parse_next(input);
+    }
+    alt((
+        '*'.value((0, None)),
+        '+'.value((1, None)),
+        '?'.value((0, Some(1))),
+    ))
+    .parse_next(input)
+}
+
+/// A group within parentheses, any AS number, a class or a single AS term.
+fn regex_atom(input: &mut &str) -> PResult<AsPathRegex> {
+    if opt(token('(')).parse_next(input)?.is_some() {
+        let regex = regex_alternation(input)?;
+        cut_err(token(')')).parse_next(input)?;
+        return Ok(regex);
+    }
+    if opt(token('.')).parse_next(input)?.is_some() {
+        return Ok(AsPathRegex::Any);
+    }
+    if opt(token('[')).parse_next(input)?.is_some() {
+        let negated = opt(token('^')).parse_next(input)?.is_some();
+        let members = repeat(0.., regex_word(class_member, "class member")).parse_next(input)?;
+        cut_err(token(']')).parse_next(input)?;
+        return Ok(AsPathRegex::Class { negated, members });
+    }
+    regex_word(
+        |word| {
+            if word.eq_ignore_ascii_case("PeerAS") {
+                Some(AsPathRegex::PeerAs)
+            } else if let Some(asn) = parse_asn(word) {
+                Some(AsPathRegex::Asn(asn))
+            } else {
+                parse_set_name(word, &[SetClass::AsSet]).map(AsPathRegex::Set)
+            }
+        },
+        "AS number, as-set or `.`",
+    )
+    .parse_next(input)
+}
+
+fn class_member(word: &str) -> Option<ClassMember> {
+    if word.eq_ignore_ascii_case("PeerAS") {
+        return Some(ClassMember::PeerAs);
+    }
+    if let Some(asn) = parse_asn(word) {
+        return Some(ClassMember::Asn(asn));
+    }
+    if let Some((first, last)) = word.split_once('-') {
+        if let (Some(first), Some(last)) = (parse_asn(first), parse_asn(last)) {
+            return (first <= last).then_some(ClassMember::Range(first, last));
+        }
+    }
+    parse_set_name(word, &[SetClass::AsSet]).map(ClassMember::Set)
+}
+
+/// Generate a parser for a word within an AS path regular expression that is mapped to a value.
+///
+/// In contrast to other words, dots are only allowed within AS numbers in asdot notation, as
+/// a single dot matches any AS number.
+fn regex_word<'s, O, F>(map: F, description: &'static str) -> impl Parser<&'s str, O, ContextError>
+where
+    F: Fn(&'s str) -> Option<O>,
+{
+    move |input: &mut &'s str| {
+        let text: &'s str = input;
+        let mut end = text
+            .find(|c: char| !is_word_char(c) || c == '.')
+            .unwrap_or(text.len());
+        let dotted = text[end..]
+            .strip_prefix('.')
+            .filter(|rest| is_asn(&text[..end]) && rest.starts_with(|c: char| c.is_ascii_digit()));
+        if let Some(rest) = dotted {
+            end += 1 + rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+        }
+
+        let Some(value) = (end > 0).then(|| map(&text[..end])).flatten() else {
+            return fail
+                .context(expected_description(description))
+                .parse_next(input);
+        };
+        input.next_slice(end);
+        multispace0.parse_next(input)?;
+        Ok(value)
+    }
+}
+
+/// The `afi` keyword followed by a comma separated list of address family identifiers.
+fn afi_list(input: &mut &str) -> PResult<Vec<Afi>> {
+    keyword("afi").parse_next(input)?;
+    let mut afis = vec![cut_err(afi).parse_next(input)?];
+    while opt(token(',')).parse_next(input)?.is_some() {
+        afis.push(cut_err(afi).parse_next(input)?);
+    }
+    Ok(afis)
+}
+
+fn afi(input: &mut &str) -> PResult<Afi> {
+    word_matching(|word| word.parse::<Afi>().is_ok(), "afi")
+        .parse_to()
+        .parse_next(input)
+}
+
+/// Generate a parser for the name of a routing protocol, e.g. `BGP4`.
+fn protocol_name<'s>() -> impl Parser<&'s str, &'s str, ContextError> {
+    terminated(
+        take_while(1.., ('A'..='Z', 'a'..='z', '0'..='9', '-', '_')),
+        multispace0,
+    )
+    .context(expected_description("protocol"))
+}
+
+/// Generate a parser for the given case insensitive keyword.
+fn keyword<'s>(keyword: &'static str) -> impl Parser<&'s str, &'s str, ContextError> {
+    move |input: &mut &'s str| {
+        if !peek_word(input).eq_ignore_ascii_case(keyword) {
+            return fail
+                .context(StrContext::Expected(StrContextValue::StringLiteral(
+                    keyword,
+                )))
+                .parse_next(input);
+        }
+        let word = input.next_slice(keyword.len());
+        multispace0.parse_next(input)?;
+        Ok(word)
+    }
+}
+
+/// Generate a parser for a single character followed by optional whitespace.
+fn token<'s>(c: char) -> impl Parser<&'s str, char, ContextError> {
+    terminated(c, multispace0).context(StrContext::Expected(StrContextValue::CharLiteral(c)))
+}
+
+/// Generate a parser for a word satisfying the given predicate.
+fn word_matching<'s, F>(
+    predicate: F,
+    description: &'static str,
+) -> impl Parser<&'s str, &'s str, ContextError>
+where
+    F: Fn(&str) -> bool,
+{
+    word_map(move |word| predicate(word).then_some(word), description)
+}
+
+/// Generate a parser for a word that is mapped to a value, failing if the word is mapped to
+/// `None`.
+fn word_map<'s, O, F>(map: F, description: &'static str) -> impl Parser<&'s str, O, ContextError>
+where
+    F: Fn(&'s str) -> Option<O>,
+{
+    move |input: &mut &'s str| {
+        let word = peek_word(input);
+        let Some(value) = (!word.is_empty()).then(|| map(word)).flatten() else {
+            return fail
+                .context(expected_description(description))
+                .parse_next(input);
+        };
+        input.next_slice(word.len());
+        multispace0.parse_next(input)?;
+        Ok(value)
+    }
+}
+
+fn expected_description(description: &'static str) -> StrContext {
+    StrContext::Expected(StrContextValue::Description(description))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')
+}
+
+/// The word at the start of the input, without consuming it.
+fn peek_word<'s>(input: &&'s str) -> &'s str {
+    let end = input.find(|c| !is_word_char(c)).unwrap_or(input.len());
+    &input[..end]
+}
+
+fn starts_with_keyword(input: &str, keywords: &[&str]) -> bool {
+    let word = peek_word(&input);
+    keywords.iter().any(|k| k.eq_ignore_ascii_case(word))
+}
+
+/// Parse an `AS` prefixed AS number or the name of an `as-set`.
+fn parse_as_term(word: &str) -> Option<AsTerm> {
+    word.parse().ok()
+}
+
+fn is_asn(word: &str) -> bool {
+    parse_asn(word).is_some()
+}
+
+/// Parse an `AS` prefixed AS number in asplain or asdot notation, e.g. `AS65536` or `AS1.0`.
+fn parse_asn(word: &str) -> Option<Asn> {
+    has_prefix(word, "AS").then(|| word.parse().ok()).flatten()
+}
+
+/// Parse the name of a set that is of one of the given classes.
+fn parse_set_name(word: &str, classes: &[SetClass]) -> Option<SetName> {
+    word.parse::<SetName>()
+        .ok()
+        .filter(|name| classes.contains(&name.class()))
+}
+
+fn has_prefix(word: &str, prefix: &str) -> bool {
+    word.get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+/// Consume the input up to the first of the given stop characters or keywords that is not
+/// enclosed in brackets, returning the consumed text with surrounding whitespace removed.
+fn scan<'s>(
+    input: &mut &'s str,
+    opening: &str,
+    closing: &str,
+    stops: &[char],
+    keywords: &[&str],
+) -> &'s str {
+    let mut depth = 0usize;
+    let mut previous = ' ';
+    let mut end = input.len();
+    for (i, c) in input.char_indices() {
+        if depth == 0
+            && (stops.contains(&c)
+                || (!is_word_char(previous) && starts_with_keyword(&input[i..], keywords)))
+        {
+            end = i;
+            break;
+        }
+        if opening.contains(c) {
+            depth += 1;
+        } else if closing.contains(c) {
+            depth = depth.saturating_sub(1);
+        }
+        previous = c;
+    }
+    input.next_slice(end).trim()
+}
+
+/// Split method arguments on commas that are not enclosed in brackets.
+fn split_arguments(arguments: &str) -> Vec<String> {
+    if arguments.is_empty() {
+        return Vec::new();
+    }
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut split = Vec::new();
+    for (i, c) in arguments.char_indices() {
+        match c {
+            '{' | '(' => depth += 1,
+            '}' | ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                split.push(arguments[start..i].trim().to_string());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(arguments[start..].trim().to_string());
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::policy::{AddressFamily, Safi};
+
+    fn as_term(name: &str) -> Expression<AsTerm> {
+        Expression::Term(name.parse().unwrap())
+    }
+
+    fn peering(as_expression: Expression<AsTerm>) -> PeeringAction {
+        PeeringAction {
+            peering: Peering::Peering {
+                as_expression,
+                remote_router: None,
+                local_router: None,
+            },
+            actions: Vec::new(),
+        }
+    }
+
+    fn factor(peerings: Vec<PeeringAction>, filter: &str) -> PolicyFactor {
+        PolicyFactor {
+            peerings,
+            filter: filter.parse().unwrap(),
+        }
+    }
+
+    #[rstest]
+    #[case(
+        PolicyKind::Import,
+        "from AS65537 accept ANY",
+        PolicyExpression::Term(vec![factor(vec![peering(as_term("AS65537"))], "ANY")])
+    )]
+    #[case(
+        PolicyKind::Export,
+        "TO as65537 ANNOUNCE AS65536:AS-CUSTOMERS;",
+        PolicyExpression::Term(vec![factor(
+            vec![peering(as_term("as65537"))],
+            "AS65536:AS-CUSTOMERS"
+        )])
+    )]
+    #[case(
+        PolicyKind::Import,
+        "from AS65537 from AS65538 accept <^AS65537+ AS65538*$>",
+        PolicyExpression::Term(vec![factor(
+            vec![peering(as_term("AS65537")), peering(as_term("AS65538"))],
+            "<^AS65537+ AS65538*$>"
+        )])
+    )]
+    #[case(
+        PolicyKind::Import,
+        "from AS65537 or AS65538 and AS-FOO except AS65539 accept { 192.0.2.0/24^+ }",
+        PolicyExpression::Term(vec![factor(
+            vec![peering(Expression::Or(
+                Box::new(as_term("AS65537")),
+                Box::new(Expression::Except(
+                    Box::new(Expression::And(
+                        Box::new(as_term("AS65538")),
+                        Box::new(as_term("AS-FOO"))
+                    )),
+                    Box::new(as_term("AS65539"))
+                ))
+            ))],
+            "{ 192.0.2.0/24^+ }"
+        )])
+    )]
+    #[case(
+        PolicyKind::Import,
+        "from (AS65537 or AS65538) and AS-FOO accept ANY",
+        PolicyExpression::Term(vec![factor(
+            vec![peering(Expression::And(
+                Box::new(Expression::Or(
+                    Box::new(as_term("AS65537")),
+                    Box::new(as_term("AS65538"))
+                )),
+                Box::new(as_term("AS-FOO"))
+            ))],
+            "ANY"
+        )])
+    )]
+    #[case(
+        PolicyKind::Import,
+        "{ from AS65537 accept AS65537; from AS-ANY accept NOT ANY; }",
+        PolicyExpression::Term(vec![
+            factor(vec![peering(as_term("AS65537"))], "AS65537"),
+            factor(vec![peering(as_term("AS-ANY"))], "NOT ANY"),
+        ])
+    )]
+    #[case(
+        PolicyKind::Import,
+        "{ from AS65537 accept AS65537 }",
+        PolicyExpression::Term(vec![factor(vec![peering(as_term("AS65537"))], "AS65537")])
+    )]
+    #[case(
+        PolicyKind::Import,
+        "from AS65537 accept ANY except from AS65538 accept AS65538",
+        PolicyExpression::Except {
+            term: vec![factor(vec![peering(as_term("AS65537"))], "ANY")],
+            afi: None,
+            expression: Box::new(PolicyExpression::Term(vec![factor(
+                vec![peering(as_term("AS65538"))],
+                "AS65538"
+            )])),
+        }
+    )]
+    #[case(
+        PolicyKind::Export,
+        "{ to AS-ANY announce AS65536; } refine to AS65537 announce ANY",
+        PolicyExpression::Refine {
+            term: vec![factor(vec![peering(as_term("AS-ANY"))], "AS65536")],
+            afi: None,
+            expression: Box::new(PolicyExpression::Term(vec![factor(
+                vec![peering(as_term("AS65537"))],
+                "ANY"
+            )])),
+        }
+    )]
+    #[case(
+        PolicyKind::Import,
+        "from PRNG-EXAMPLE accept ANY",
+        PolicyExpression::Term(vec![factor(
+            vec![PeeringAction {
+                peering: Peering::Set("PRNG-EXAMPLE".parse().unwrap()),
+                actions: Vec::new(),
+            }],
+            "ANY"
+        )])
+    )]
+    fn policy_expression_is_parsed(
+        #[case] kind: PolicyKind,
+        #[case] text: &str,
+        #[case] expected: PolicyExpression,
+    ) {
+        assert_eq!(policy(kind, text).unwrap().expression, expected);
+    }
+
+    #[test]
+    fn policy_with_protocols_and_afi_is_parsed() {
+        let parsed = policy(
+            PolicyKind::MpImport,
+            "protocol BGP4 into OSPF afi ipv4.unicast, ipv6 from AS65537 accept ANY",
+        )
+        .unwrap();
+
+        assert_eq!(parsed.protocol.as_deref(), Some("BGP4"));
+        assert_eq!(parsed.into.as_deref(), Some("OSPF"));
+        assert_eq!(
+            parsed.afi,
+            Some(vec![
+                Afi {
+                    family: AddressFamily::Ipv4,
+                    safi: Some(Safi::Unicast)
+                },
+                Afi {
+                    family: AddressFamily::Ipv6,
+                    safi: None
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn afi_after_except_is_parsed() {
+        let parsed = policy(
+            PolicyKind::MpExport,
+            "afi any to AS65537 announce AS65536 \
+             except afi ipv6.unicast to AS65537 announce NOT ANY",
+        )
+        .unwrap();
+
+        let PolicyExpression::Except { afi, .. } = parsed.expression else {
+            unreachable!();
+        };
+        assert_eq!(afi.unwrap()[0].to_string(), "ipv6.unicast");
+    }
+
+    #[test]
+    fn peering_routers_are_parsed() {
+        let parsed = policy(
+            PolicyKind::Import,
+            "from AS65537 192.0.2.1 or rtr1.example.net at 2001:db8::1 accept ANY",
+        )
+        .unwrap();
+
+        let PolicyExpression::Term(term) = parsed.expression else {
+            unreachable!();
+        };
+        assert_eq!(
+            term[0].peerings[0].peering,
+            Peering::Peering {
+                as_expression: as_term("AS65537"),
+                remote_router: Some(Expression::Or(
+                    Box::new(Expression::Term(Router::Address(
+                        "192.0.2.1".parse().unwrap()
+                    ))),
+                    Box::new(Expression::Term(Router::Name(
+                        "rtr1.example.net".to_string()
+                    )))
+                )),
+                local_router: Some(Expression::Term(Router::Address(
+                    "2001:db8::1".parse().unwrap()
+                ))),
+            }
+        );
+    }
+
+    #[test]
+    fn actions_are_parsed() {
+        let parsed = policy(
+            PolicyKind::Import,
+            "from AS65537 action pref = 100; med=0; community.append(65536:1, {65536:2, 65536:3}); \
+             aspath.prepend(AS65536, AS65536); community .= { 65536:4 }; accept ANY",
+        )
+        .unwrap();
+
+        let PolicyExpression::Term(term) = parsed.expression else {
+            unreachable!();
+        };
+        assert_eq!(
+            term[0].peerings[0].actions,
+            vec![
+                Action::Operator {
+                    attribute: "pref".to_string(),
+                    operator: "=".to_string(),
+                    value: "100".to_string(),
+                },
+                Action::Operator {
+                    attribute: "med".to_string(),
+                    operator: "=".to_string(),
+                    value: "0".to_string(),
+                },
+                Action::Method {
+                    attribute: "community".to_string(),
+                    method: "append".to_string(),
+                    arguments: vec!["65536:1".to_string(), "{65536:2, 65536:3}".to_string()],
+                },
+                Action::Method {
+                    attribute: "aspath".to_string(),
+                    method: "prepend".to_string(),
+                    arguments: vec!["AS65536".to_string(), "AS65536".to_string()],
+                },
+                Action::Operator {
+                    attribute: "community".to_string(),
+                    operator: ".=".to_string(),
+                    value: "{ 65536:4 }".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(PolicyKind::Import, "", 0, "expected `from`")]
+    #[case(
+        PolicyKind::Import,
+        "from accept ANY",
+        5,
+        "expected AS number or as-set"
+    )]
+    #[case(
+        PolicyKind::Import,
+        "from AS65537 announce ANY",
+        13,
+        "expected `accept`"
+    )]
+    #[case(PolicyKind::Import, "from AS65537 accept", 19, "expected filter")]
+    #[case(
+        PolicyKind::Import,
+        "from AS65537 action pref accept ANY",
+        25,
+        "expected operator"
+    )]
+    #[case(
+        PolicyKind::Import,
+        "from AS65537 accept ANY; ANY",
+        25,
+        "expected end of policy"
+    )]
+    #[case(PolicyKind::Import, "{ from AS65537 accept ANY", 25, "expected `}`")]
+    #[case(PolicyKind::Import, "from (AS65537 accept ANY", 14, "expected `)`")]
+    #[case(
+        PolicyKind::MpImport,
+        "afi ipv5 from AS65537 accept ANY",
+        4,
+        "expected afi"
+    )]
+    #[case(
+        PolicyKind::Import,
+        "afi ipv4 from AS65537 accept ANY",
+        0,
+        "expected `from`"
+    )]
+    #[case(
+        PolicyKind::Export,
+        "to AS65537 announce ANY except",
+        30,
+        "expected `to`"
+    )]
+    fn invalid_policy_is_err(
+        #[case] kind: PolicyKind,
+        #[case] text: &str,
+        #[case] offset: usize,
+        #[case] message: &str,
+    ) {
+        assert_eq!(policy(kind, text), Err((offset, message.to_string())));
+    }
+}
@@ -0,0 +1,438 @@
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+use super::{parser, PolicyError};
+use crate::{Asn, SetName};
+
+/// A regular expression over the AS path of a route, written within angle brackets in a filter.
+///
+/// As described in [RFC 2622, Section 5.4](https://datatracker.ietf.org/doc/html/rfc2622#section-5.4),
+/// the symbols of the expression are AS numbers rather than characters.
+///
+/// # Example
+/// ```
+/// # use rpsl::{policy::AsPathRegex, Asn};
+/// let regex: AsPathRegex = "<^AS65537 AS65538* $>".parse()?;
+///
+/// assert!(regex.is_match(&[65537, 65538, 65538].map(Asn::from)));
+/// assert!(!regex.is_match(&[65538, 65537].map(Asn::from)));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsPathRegex {
+    /// The start of the AS path, written as `^`.
+    Start,
+    /// The end of the AS path, written as `$`.
+    End,
+    /// Any AS number, written as `.`.
+    Any,
+    /// A single AS number.
+    Asn(Asn),
+    /// Any AS number contained in an `as-set`.
+    Set(SetName),
+    /// The AS number of the peer, written as `PeerAS`.
+    PeerAs,
+    /// Any AS number matched by the members of a class, e.g. `[AS1 AS10-AS20]` or `[^AS1]`.
+    Class {
+        /// Whether the class matches AS numbers not matched by its members.
+        negated: bool,
+        /// The members of the class.
+        members: Vec<ClassMember>,
+    },
+    /// Expressions matched one after another.
+    Sequence(Vec<AsPathRegex>),
+    /// Any of the expressions, separated by `|`.
+    Alternation(Vec<AsPathRegex>),
+    /// An expression repeated a number of times, e.g. `AS1*`, `AS1{2,3}` or `AS1~+`.
+    Repeat {
+        /// The repeated expression.
+        regex: Box<AsPathRegex>,
+        /// The minimum number of repetitions.
+        min: usize,
+        /// The maximum number of repetitions, if bounded.
+        max: Option<usize>,
+        /// Whether every repetition must match the same AS number, as with the `~` operators.
+        same_as: bool,
+    },
+}
+
+/// A member of an [`AsPathRegex::Class`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassMember {
+    /// A single AS number.
+    Asn(Asn),
+    /// An inclusive range of AS numbers, e.g. `AS10-AS20`.
+    Range(Asn, Asn),
+    /// Any AS number contained in an `as-set`.
+    Set(SetName),
+    /// The AS number of the peer.
+    PeerAs,
+}
+
+impl AsPathRegex {
+    /// Whether the expression matches the given AS path, listed from the peer to the origin.
+    ///
+    /// `PeerAS` matches the first AS number of the path, while sets never match since their
+    /// members are unknown. Use [`is_match_with`](Self::is_match_with) to resolve sets.
+    #[must_use]
+    pub fn is_match(&self, path: &[Asn]) -> bool {
+        self.is_match_with(path, |_, _| false)
+    }
+
+    /// Whether the expression matches the given AS path, listed from the peer to the origin,
+    /// using `in_set` to determine whether an AS number is a member of the named set.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{policy::AsPathRegex, Asn, SetName};
+    /// let regex: AsPathRegex = "<^PeerAS as-customers+$>".parse()?;
+    ///
+    /// let customers: SetName = "AS-CUSTOMERS".parse()?;
+    /// let in_set = |name: &SetName, asn: Asn| {
+    ///     *name == customers && (65540..65550).contains(&asn.number())
+    /// };
+    /// assert!(regex.is_match_with(&[65537, 65541, 65542].map(Asn::from), in_set));
+    /// assert!(!regex.is_match_with(&[65537, 65551].map(Asn::from), in_set));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_match_with<F>(&self, path: &[Asn], in_set: F) -> bool
+    where
+        F: Fn(&SetName, Asn) -> bool,
+    {
+        let matcher = Matcher {
+            path,
+            in_set: &in_set,
+        };
+        (0..=path.len()).any(|start| !matcher.ends(self, start).is_empty())
+    }
+
+    /// Whether the expression matches exactly one AS number, making it a valid operand for
+    /// the `~` operators.
+    pub(super) fn is_single_as(&self) -> bool {
+        matches!(
+            self,
+            Self::Any | Self::Asn(_) | Self::Set(_) | Self::PeerAs | Self::Class { .. }
+        )
+    }
+
+    fn fmt_inner(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Start => write!(f, "^"),
+            Self::End => write!(f, "$"),
+            Self::Any => write!(f, "."),
+            Self::Asn(asn) => write!(f, "{asn}"),
+            Self::Set(name) => write!(f, "{name}"),
+            Self::PeerAs => write!(f, "PeerAS"),
+            Self::Class { negated, members } => {
+                write!(f, "[{}", if *negated { "^" } else { "" })?;
+                for (i, member) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    match member {
+                        ClassMember::Asn(asn) => write!(f, "{asn}")?,
+                        ClassMember::Range(first, last) => write!(f, "{first}-{last}")?,
+                        ClassMember::Set(name) => write!(f, "{name}")?,
+                        ClassMember::PeerAs => write!(f, "PeerAS")?,
+                    }
+                }
+                write!(f, "]")
+            }
+            Self::Sequence(regexes) => {
+                let mut previous: Option<&Self> = None;
+                for regex in regexes {
+                    if previous.is_some_and(|p| *p != Self::Start) && *regex != Self::End {
+                        write!(f, " ")?;
+                    }
+                    regex.fmt_grouped(
+                        f,
+                        matches!(regex, Self::Sequence(_) | Self::Alternation(_)),
+                    )?;
+                    previous = Some(regex);
+                }
+                Ok(())
+            }
+            Self::Alternation(regexes) => {
+                for (i, regex) in regexes.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    regex.fmt_inner(f)?;
+                }
+                Ok(())
+            }
+            Self::Repeat {
+                regex,
+                min,
+                max,
+                same_as,
+            } => {
+                regex.fmt_grouped(f, !regex.is_single_as())?;
+                if *same_as {
+                    write!(f, "~")?;
+                }
+                match (min, max) {
+                    (0, None) => write!(f, "*"),
+                    (1, None) => write!(f, "+"),
+                    (0, Some(1)) if !same_as => write!(f, "?"),
+                    (min, None) => write!(f, "{{{min},}}"),
+                    (min, Some(max)) if min == max => write!(f, "{{{min}}}"),
+                    (min, Some(max)) => write!(f, "{{{min},{max}}}"),
+                }
+            }
+        }
+    }
+
+    fn fmt_grouped(&self, f: &mut fmt::Formatter<'_>, grouped: bool) -> fmt::Result {
+        if grouped {
+            write!(f, "(")?;
+            self.fmt_inner(f)?;
+            write!(f, ")")
+        } else {
+            self.fmt_inner(f)
+        }
+    }
+}
+
+impl FromStr for AsPathRegex {
+    type Err = PolicyError;
+
+    /// Parse an AS path regular expression, including its enclosing angle brackets.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::as_path_regex(s).map_err(|(offset, message)| PolicyError::at(s, offset, message))
+    }
+}
+
+impl fmt::Display for AsPathRegex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<")?;
+        self.fmt_inner(f)?;
+        write!(f, ">")
+    }
+}
+
+/// Finds the positions in an AS path at which a match of an expression can end.
+struct Matcher<'p, F> {
+    path: &'p [Asn],
+    in_set: &'p F,
+}
+
+impl<F> Matcher<'_, F>
+where
+    F: Fn(&SetName, Asn) -> bool,
+{
+    /// The positions at which a match of the expression starting at `start` ends.
+    fn ends(&self, regex: &AsPathRegex, start: usize) -> BTreeSet<usize> {
+        match regex {
+            AsPathRegex::Start => (start == 0).then_some(start).into_iter().collect(),
+            AsPathRegex::End => (start == self.path.len())
+                .then_some(start)
+                .into_iter()
+                .collect(),
+            AsPathRegex::Sequence(regexes) => {
+                regexes
+                    .iter()
+                    .fold(BTreeSet::from([start]), |positions, regex| {
+                        positions
+                            .into_iter()
+                            .flat_map(|position| self.ends(regex, position))
+                            .collect()
+                    })
+            }
+            AsPathRegex::Alternation(regexes) => regexes
+                .iter()
+                .flat_map(|regex| self.ends(regex, start))
+                .collect(),
+            AsPathRegex::Repeat {
+                regex,
+                min,
+                max,
+                same_as: false,
+            } => self.repeat_ends(regex, start, *min, *max),
+            AsPathRegex::Repeat {
+                regex,
+                min,
+                max,
+                same_as: true,
+            } => self.same_as_repeat_ends(regex, start, *min, *max),
+            _ => self
+                .path
+                .get(start)
+                .filter(|asn| self.matches_as(regex, **asn))
+                .map(|_| start + 1)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn repeat_ends(
+        &self,
+        regex: &AsPathRegex,
+        start: usize,
+        min: usize,
+        max: Option<usize>,
+    ) -> BTreeSet<usize> {
+        // Repetitions beyond the length of the path can only match the empty path, so they
+        // cannot produce further positions.
+        let limit = min + self.path.len() + 1;
+        let limit = max.map_or(limit, |max| max.min(limit));
+
+        let mut ends = BTreeSet::new();
+        let mut positions = BTreeSet::from([start]);
+        if min == 0 {
+            ends.insert(start);
+        }
+        for count in 1..=limit {
+            positions = positions
+                .into_iter()
+                .flat_map(|position| self.ends(regex, position))
+                .collect();
+            if positions.is_empty() {
+                break;
+            }
+            if count >= min {
+                ends.extend(&positions);
+            }
+        }
+        ends
+    }
+
+    fn same_as_repeat_ends(
+        &self,
+        regex: &AsPathRegex,
+        start: usize,
+        min: usize,
+        max: Option<usize>,
+    ) -> BTreeSet<usize> {
+        let mut ends = BTreeSet::new();
+        if min == 0 {
+            ends.insert(start);
+        }
+        let Some(first) = self
+            .path
+            .get(start)
+            .filter(|asn| self.matches_as(regex, **asn))
+        else {
+            return ends;
+        };
+        let run = self.path[start..]
+            .iter()
+            .take_while(|asn| *asn == first)
+            .count();
+        let last = max.map_or(run, |max| max.min(run));
+        ends.extend((min.max(1)..=last).map(|count| start + count));
+        ends
+    }
+
+    /// Whether a single AS number is matched by the expression.
+    fn matches_as(&self, regex: &AsPathRegex, asn: Asn) -> bool {
+        match regex {
+            AsPathRegex::Any => true,
+            AsPathRegex::Asn(expected) => asn == *expected,
+            AsPathRegex::Set(name) => (self.in_set)(name, asn),
+            AsPathRegex::PeerAs => self.path.first() == Some(&asn),
+            AsPathRegex::Class { negated, members } => {
+                members.iter().any(|member| match member {
+                    ClassMember::Asn(expected) => asn == *expected,
+                    ClassMember::Range(first, last) => (*first..=*last).contains(&asn),
+                    ClassMember::Set(name) => (self.in_set)(name, asn),
+                    ClassMember::PeerAs => self.path.first() == Some(&asn),
+                }) != *negated
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("<AS1>", &[1], true)]
+    #[case("<AS1>", &[2, 1, 3], true)]
+    #[case("<AS1>", &[2, 3], false)]
+    #[case("<^AS1>", &[2, 1], false)]
+    #[case("<AS1$>", &[2, 1], true)]
+    #[case("<^AS1 AS2 $>", &[1, 2], true)]
+    #[case("<^AS1 AS2 $>", &[1, 2, 3], false)]
+    #[case("<^$>", &[], true)]
+    #[case("<^$>", &[1], false)]
+    #[case("<^AS1 .* AS3$>", &[1, 3], true)]
+    #[case("<^AS1 .* AS3$>", &[1, 2, 2, 3], true)]
+    #[case("<^AS1 . AS3$>", &[1, 3], false)]
+    #[case("<^AS1+$>", &[1, 1, 1], true)]
+    #[case("<^AS1+$>", &[], false)]
+    #[case("<^AS1? AS2$>", &[2], true)]
+    #[case("<^AS1{2}$>", &[1, 1], true)]
+    #[case("<^AS1{2}$>", &[1, 1, 1], false)]
+    #[case("<^AS1{2,}$>", &[1, 1, 1, 1], true)]
+    #[case("<^AS1{2,3}$>", &[1, 1, 1, 1], false)]
+    #[case("<^(AS1 AS2)+$>", &[1, 2, 1, 2], true)]
+    #[case("<^(AS1 AS2)+$>", &[1, 2, 1], false)]
+    #[case("<^AS1 (AS2 | AS3) $>", &[1, 3], true)]
+    #[case("<^AS1 | AS2$>", &[3, 2], true)]
+    #[case("<^[AS1 AS10-AS20]+$>", &[1, 15, 20], true)]
+    #[case("<^[AS1 AS10-AS20]+$>", &[1, 21], false)]
+    #[case("<^[^AS1 AS2]+$>", &[3, 4], true)]
+    #[case("<^[^AS1 AS2]+$>", &[3, 2], false)]
+    #[case("<^.~*$>", &[5, 5, 5], true)]
+    #[case("<^.~*$>", &[5, 6], false)]
+    #[case("<^.~+ AS6$>", &[5, 5, 6], true)]
+    #[case("<^[AS1 AS2]~{2,3}$>", &[2, 2, 2], true)]
+    #[case("<^[AS1 AS2]~{2,3}$>", &[1, 2], false)]
+    #[case("<^PeerAS+ AS2$>", &[7, 7, 2], true)]
+    #[case("<^AS1 PeerAS>", &[1, 1], true)]
+    #[case("<^AS1 PeerAS>", &[1, 2], false)]
+    #[case("<AS1.10>", &[65546], true)]
+    #[case("<(AS1*)*$>", &[2], true)]
+    fn as_path_regex_is_match(#[case] regex: &str, #[case] path: &[u32], #[case] expected: bool) {
+        let regex: AsPathRegex = regex.parse().unwrap();
+        let path: Vec<Asn> = path.iter().copied().map(Asn::from).collect();
+        assert_eq!(regex.is_match(&path), expected);
+    }
+
+    #[test]
+    fn as_path_regex_sets_are_resolved() {
+        let regex: AsPathRegex = "<^AS1 [AS-FOO AS3]* AS-FOO$>".parse().unwrap();
+        let foo: SetName = "as-foo".parse().unwrap();
+        let in_set = |name: &SetName, asn: Asn| *name == foo && asn == Asn::from(2);
+
+        assert!(regex.is_match_with(&[1, 2, 3, 2].map(Asn::from), in_set));
+        assert!(!regex.is_match_with(&[1, 2, 3].map(Asn::from), in_set));
+        assert!(!regex.is_match(&[1, 2].map(Asn::from)));
+    }
+
+    #[rstest]
+    #[case("<^AS1 AS2* $>", "<^AS1 AS2*$>")]
+    #[case("<  AS1.10 as-foo  >", "<AS65546 as-foo>")]
+    #[case(
+        "<^(AS1|AS2)+ AS3~{2,} [^AS4-AS5 PeerAS]?$>",
+        "<^(AS1 | AS2)+ AS3~{2,} [^AS4-AS5 PeerAS]?$>"
+    )]
+    #[case("<AS1{3,3} . AS2{1,4}>", "<AS1{3} . AS2{1,4}>")]
+    #[case("<AS1 | AS2 AS3>", "<AS1 | AS2 AS3>")]
+    #[case("<(AS1 | AS2) AS3>", "<(AS1 | AS2) AS3>")]
+    fn as_path_regex_display(#[case] regex: &str, #[case] expected: &str) {
+        let regex: AsPathRegex = regex.parse().unwrap();
+        assert_eq!(regex.to_string(), expected);
+        assert_eq!(expected.parse::<AsPathRegex>().unwrap(), regex);
+    }
+
+    #[rstest]
+    #[case("AS1", 0, "expected `<`")]
+    #[case("<AS1", 4, "expected `>`")]
+    #[case("<(AS1 AS2)~*>", 10, "expected single AS before `~`")]
+    #[case("<AS1{3,1}>", 4, "expected repetition bounds")]
+    #[case("<AS1 FOO>", 5, "expected `>`")]
+    #[case("<[AS1>", 5, "expected `]`")]
+    fn invalid_as_path_regex_is_err(
+        #[case] regex: &str,
+        #[case] column: usize,
+        #[case] message: &str,
+    ) {
+        let err = regex.parse::<AsPathRegex>().unwrap_err();
+        assert_eq!((err.column() - 1, err.message()), (column, message));
+    }
+}
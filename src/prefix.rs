@@ -0,0 +1,544 @@
+use std::{fmt, net::IpAddr, str::FromStr};
+
+use crate::PrefixError;
+
+/// An IPv4 or IPv6 address prefix, e.g. `192.0.2.0/24` or `2001:db8::/32`.
+///
+/// The host bits of the address, which are not covered by the prefix length, must be zero.
+///
+/// # Example
+/// ```
+/// # use rpsl::AddressPrefix;
+/// let prefix: AddressPrefix = "192.0.2.0/24".parse()?;
+///
+/// assert!(prefix.contains(&"192.0.2.128/25".parse()?));
+/// assert!(!prefix.contains(&"192.0.0.0/16".parse()?));
+/// assert!("192.0.2.1/24".parse::<AddressPrefix>().is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AddressPrefix {
+    address: IpAddr,
+    length: u8,
+}
+
+impl AddressPrefix {
+    /// Create a new address prefix.
+    ///
+    /// # Errors
+    /// Returns a [`PrefixError`] if the length exceeds the length of the address or the address
+    /// has host bits set.
+    pub fn new(address: IpAddr, length: u8) -> Result<Self, PrefixError> {
+        let prefix = Self { address, length };
+        if length > prefix.max_length() {
+            return Err(PrefixError::InvalidPrefix(format!("{address}/{length}")));
+        }
+        if prefix.bits() & !mask(length, prefix.max_length()) != 0 {
+            return Err(PrefixError::HostBitsSet(prefix.to_string()));
+        }
+        Ok(prefix)
+    }
+
+    /// The network address of the prefix.
+    #[must_use]
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    /// The length of the prefix.
+    #[must_use]
+    pub fn length(&self) -> u8 {
+        self.length
+    }
+
+    /// The length of an address of the prefix's family, i.e. 32 for IPv4 and 128 for IPv6.
+    #[must_use]
+    pub fn max_length(&self) -> u8 {
+        match self.address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    /// Whether the prefix is an IPv4 prefix.
+    #[must_use]
+    pub fn is_ipv4(&self) -> bool {
+        self.address.is_ipv4()
+    }
+
+    /// Whether the prefix is an IPv6 prefix.
+    #[must_use]
+    pub fn is_ipv6(&self) -> bool {
+        self.address.is_ipv6()
+    }
+
+    /// Whether the other prefix is equal to or a more specific of this prefix.
+    #[must_use]
+    pub fn contains(&self, other: &AddressPrefix) -> bool {
+        self.is_ipv4() == other.is_ipv4()
+            && other.length >= self.length
+            && other.bits() & mask(self.length, self.max_length()) == self.bits()
+    }
+
+    /// The address as an integer, aligned to the right for IPv4.
+    pub(crate) fn bits(&self) -> u128 {
+        match self.address {
+            IpAddr::V4(address) => u128::from(u32::from(address)),
+            IpAddr::V6(address) => u128::from(address),
+        }
+    }
+
+    /// The prefix one bit shorter that contains this prefix, or `None` for a default route.
+    pub(crate) fn parent(&self) -> Option<Self> {
+        let length = self.length.checked_sub(1)?;
+        Some(self.with_bits(self.bits() & mask(length, self.max_length()), length))
+    }
+
+    /// The other half of the parent of this prefix, or `None` for a default route.
+    pub(crate) fn sibling(&self) -> Option<Self> {
+        if self.length == 0 {
+            return None;
+        }
+        let bit = 1u128 << (self.max_length() - self.length);
+        Some(self.with_bits(self.bits() ^ bit, self.length))
+    }
+
+    /// A prefix of the same family with the given address bits and length.
+    fn with_bits(&self, bits: u128, length: u8) -> Self {
+        let address = match self.address {
+            // The bits of an IPv4 prefix never exceed 32 bits.
+            #[allow(clippy::cast_possible_truncation)]
+            IpAddr::V4(_) => IpAddr::V4((bits as u32).into()),
+            IpAddr::V6(_) => IpAddr::V6(bits.into()),
+        };
+        Self { address, length }
+    }
+}
+
+/// A mask covering the first `length` bits of an address that is `width` bits long.
+fn mask(length: u8, width: u8) -> u128 {
+    let all = u128::MAX >> (128 - u32::from(width));
+    match length {
+        0 => 0,
+        length if length >= width => all,
+        length => all & !(all >> length),
+    }
+}
+
+impl FromStr for AddressPrefix {
+    type Err = PrefixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || PrefixError::InvalidPrefix(s.to_string());
+        let (address, length) = s.split_once('/').ok_or_else(invalid)?;
+        if length.is_empty() || !length.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let address = address.parse().map_err(|_| invalid())?;
+        let length = length.parse().map_err(|_| invalid())?;
+        Self::new(address, length)
+    }
+}
+
+impl fmt::Display for AddressPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.length)
+    }
+}
+
+/// A range operator, selecting more specifics of an address prefix.
+///
+/// As described in [RFC 2622, Section 2](https://datatracker.ietf.org/doc/html/rfc2622#section-2),
+/// range operators can be applied to address prefixes and sets of routes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RangeOperator {
+    /// `^-`, the exclusive more specifics.
+    Exclusive,
+    /// `^+`, the inclusive more specifics.
+    Inclusive,
+    /// `^n`, the more specifics of length `n`.
+    Length(u8),
+    /// `^n-m`, the more specifics with a length from `n` to `m`.
+    Range(u8, u8),
+}
+
+impl RangeOperator {
+    /// The minimum and maximum length of the more specifics selected from a prefix of the given
+    /// length, or `None` if no more specifics are selected.
+    fn bounds(self, length: u8, max_length: u8) -> Option<(u8, u8)> {
+        let (min, max) = match self {
+            Self::Exclusive => (length + 1, max_length),
+            Self::Inclusive => (length, max_length),
+            Self::Length(n) => (n, n),
+            Self::Range(n, m) => (n, m),
+        };
+        (length <= min && min <= max && max <= max_length).then_some((min, max))
+    }
+}
+
+impl FromStr for RangeOperator {
+    type Err = PrefixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || PrefixError::InvalidRangeOperator(s.to_string());
+        let length = |digits: &str| {
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            digits
+                .parse::<u8>()
+                .ok()
+                .filter(|length| *length <= 128)
+                .ok_or_else(invalid)
+        };
+
+        match s.strip_prefix('^').ok_or_else(invalid)? {
+            "-" => Ok(Self::Exclusive),
+            "+" => Ok(Self::Inclusive),
+            lengths => match lengths.split_once('-') {
+                Some((min, max)) => {
+                    let (min, max) = (length(min)?, length(max)?);
+                    if min > max {
+                        return Err(invalid());
+                    }
+                    Ok(Self::Range(min, max))
+                }
+                None => Ok(Self::Length(length(lengths)?)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for RangeOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exclusive => write!(f, "^-"),
+            Self::Inclusive => write!(f, "^+"),
+            Self::Length(length) => write!(f, "^{length}"),
+            Self::Range(min, max) => write!(f, "^{min}-{max}"),
+        }
+    }
+}
+
+/// An address prefix with an optional range operator, e.g. `192.0.2.0/24^+`.
+///
+/// A prefix range contains the prefixes that are more specifics of its prefix with a length
+/// within the bounds selected by its range operator. Without an operator, only the prefix
+/// itself is contained.
+///
+/// # Example
+/// ```
+/// # use rpsl::AddressPrefixRange;
+/// let range: AddressPrefixRange = "2001:db8::/32^48-56".parse()?;
+///
+/// assert_eq!(range.length_bounds(), (48, 56));
+/// assert!(range.contains(&"2001:db8:1::/48".parse()?));
+/// assert!(!range.contains(&"2001:db8::/32".parse()?));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AddressPrefixRange {
+    prefix: AddressPrefix,
+    operator: Option<RangeOperator>,
+}
+
+impl AddressPrefixRange {
+    /// Create a new prefix range.
+    ///
+    /// # Errors
+    /// Returns a [`PrefixError`] if the operator selects no more specifics of the prefix, e.g.
+    /// `192.0.2.0/24^16`.
+    pub fn new(
+        prefix: AddressPrefix,
+        operator: Option<RangeOperator>,
+    ) -> Result<Self, PrefixError> {
+        if let Some(operator) = operator {
+            if operator
+                .bounds(prefix.length, prefix.max_length())
+                .is_none()
+            {
+                return Err(PrefixError::InvalidRange { prefix, operator });
+            }
+        }
+        Ok(Self { prefix, operator })
+    }
+
+    /// The address prefix.
+    #[must_use]
+    pub fn prefix(&self) -> &AddressPrefix {
+        &self.prefix
+    }
+
+    /// The range operator.
+    #[must_use]
+    pub fn operator(&self) -> Option<RangeOperator> {
+        self.operator
+    }
+
+    /// The minimum and maximum length of the prefixes contained in the range.
+    #[must_use]
+    pub fn length_bounds(&self) -> (u8, u8) {
+        let length = self.prefix.length;
+        self.operator
+            .and_then(|operator| operator.bounds(length, self.prefix.max_length()))
+            .unwrap_or((length, length))
+    }
+
+    /// Whether the prefix is contained in the range.
+    #[must_use]
+    pub fn contains(&self, prefix: &AddressPrefix) -> bool {
+        let (min, max) = self.length_bounds();
+        self.prefix.contains(prefix) && (min..=max).contains(&prefix.length)
+    }
+
+    /// Apply a range operator to the range, as done for the members of a set followed by a
+    /// range operator, e.g. `{192.0.2.0/24^+}^26`.
+    ///
+    /// The operator is applied to each prefix contained in the range, and the resulting range
+    /// contains the union of the more specifics. Returns `None` if no prefixes remain.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{AddressPrefixRange, RangeOperator};
+    /// let range: AddressPrefixRange = "10.0.0.0/8^16-24".parse()?;
+    ///
+    /// let applied = range.apply(RangeOperator::Exclusive).unwrap();
+    /// assert_eq!(applied.to_string(), "10.0.0.0/8^17-32");
+    /// assert_eq!(range.apply(RangeOperator::Length(12)), None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn apply(&self, operator: RangeOperator) -> Option<Self> {
+        let (min, _) = self.length_bounds();
+        let max_length = self.prefix.max_length();
+        let (min, max) = match operator {
+            RangeOperator::Exclusive => (min.checked_add(1)?, max_length),
+            RangeOperator::Inclusive => (min, max_length),
+            RangeOperator::Length(n) => (n.max(min), n),
+            RangeOperator::Range(n, m) => (n.max(min), m),
+        };
+        if min > max || max > max_length {
+            return None;
+        }
+        Some(Self::with_bounds(self.prefix, min, max))
+    }
+
+    /// Create a range containing the more specifics of the prefix with a length between `min`
+    /// and `max`, using the shortest notation of the range operator.
+    pub(crate) fn with_bounds(prefix: AddressPrefix, min: u8, max: u8) -> Self {
+        let length = prefix.length;
+        let operator = match (min, max) {
+            (min, max) if min == length && max == length => None,
+            (min, max) if min == length && max == prefix.max_length() => {
+                Some(RangeOperator::Inclusive)
+            }
+            (min, max) if min == length + 1 && max == prefix.max_length() => {
+                Some(RangeOperator::Exclusive)
+            }
+            (min, max) if min == max => Some(RangeOperator::Length(min)),
+            (min, max) => Some(RangeOperator::Range(min, max)),
+        };
+        Self { prefix, operator }
+    }
+}
+
+impl From<AddressPrefix> for AddressPrefixRange {
+    fn from(prefix: AddressPrefix) -> Self {
+        Self {
+            prefix,
+            operator: None,
+        }
+    }
+}
+
+impl FromStr for AddressPrefixRange {
+    type Err = PrefixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('^') {
+            Some((prefix, _)) => Self::new(prefix.parse()?, Some(s[prefix.len()..].parse()?)),
+            None => Ok(s.parse::<AddressPrefix>()?.into()),
+        }
+    }
+}
+
+impl fmt::Display for AddressPrefixRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.prefix)?;
+        if let Some(operator) = self.operator {
+            write!(f, "{operator}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("0.0.0.0/0")]
+    #[case("192.0.2.0/24")]
+    #[case("192.0.2.1/32")]
+    #[case("::/0")]
+    #[case("2001:db8::/32")]
+    #[case("2001:db8::1/128")]
+    fn address_prefix_from_str_and_display(#[case] s: &str) {
+        assert_eq!(s.parse::<AddressPrefix>().unwrap().to_string(), s);
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24", Some("192.0.2.0/23"), Some("192.0.3.0/24"))]
+    #[case("192.0.3.0/24", Some("192.0.2.0/23"), Some("192.0.2.0/24"))]
+    #[case("128.0.0.0/1", Some("0.0.0.0/0"), Some("0.0.0.0/1"))]
+    #[case("2001:db8::1/128", Some("2001:db8::/127"), Some("2001:db8::/128"))]
+    #[case("::/0", None, None)]
+    fn address_prefix_parent_and_sibling(
+        #[case] prefix: &str,
+        #[case] parent: Option<&str>,
+        #[case] sibling: Option<&str>,
+    ) {
+        let prefix: AddressPrefix = prefix.parse().unwrap();
+        let parse = |s: Option<&str>| s.map(|s| s.parse::<AddressPrefix>().unwrap());
+        assert_eq!(prefix.parent(), parse(parent));
+        assert_eq!(prefix.sibling(), parse(sibling));
+    }
+
+    #[rstest]
+    #[case("192.0.2.0", PrefixError::InvalidPrefix("192.0.2.0".to_string()))]
+    #[case("192.0.2.0/", PrefixError::InvalidPrefix("192.0.2.0/".to_string()))]
+    #[case("192.0.2.0/+24", PrefixError::InvalidPrefix("192.0.2.0/+24".to_string()))]
+    #[case("192.0.2.0/33", PrefixError::InvalidPrefix("192.0.2.0/33".to_string()))]
+    #[case("192.0.2/24", PrefixError::InvalidPrefix("192.0.2/24".to_string()))]
+    #[case("2001:db8::/129", PrefixError::InvalidPrefix("2001:db8::/129".to_string()))]
+    #[case("192.0.2.1/24", PrefixError::HostBitsSet("192.0.2.1/24".to_string()))]
+    #[case("2001:db8::/16", PrefixError::HostBitsSet("2001:db8::/16".to_string()))]
+    fn invalid_address_prefix_is_err(#[case] s: &str, #[case] expected: PrefixError) {
+        assert_eq!(s.parse::<AddressPrefix>().unwrap_err(), expected);
+    }
+
+    #[rstest]
+    #[case("0.0.0.0/0", "198.51.100.0/24", true)]
+    #[case("192.0.2.0/24", "192.0.2.0/24", true)]
+    #[case("192.0.2.0/24", "192.0.2.255/32", true)]
+    #[case("192.0.2.0/24", "192.0.3.0/24", false)]
+    #[case("192.0.2.0/24", "192.0.0.0/22", false)]
+    #[case("::/0", "0.0.0.0/0", false)]
+    #[case("2001:db8::/32", "2001:db8:ffff::/48", true)]
+    #[case("2001:db8::/32", "2001:db9::/48", false)]
+    fn address_prefix_contains(#[case] prefix: &str, #[case] other: &str, #[case] expected: bool) {
+        let prefix: AddressPrefix = prefix.parse().unwrap();
+        assert_eq!(prefix.contains(&other.parse().unwrap()), expected);
+    }
+
+    #[rstest]
+    #[case("^-", RangeOperator::Exclusive)]
+    #[case("^+", RangeOperator::Inclusive)]
+    #[case("^24", RangeOperator::Length(24))]
+    #[case("^24-32", RangeOperator::Range(24, 32))]
+    fn range_operator_from_str_and_display(#[case] s: &str, #[case] expected: RangeOperator) {
+        assert_eq!(s.parse::<RangeOperator>().unwrap(), expected);
+        assert_eq!(expected.to_string(), s);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("^")]
+    #[case("+")]
+    #[case("^256")]
+    #[case("^129")]
+    #[case("^24-")]
+    #[case("^-24")]
+    #[case("^32-24")]
+    #[case("^+ ")]
+    fn invalid_range_operator_is_err(#[case] s: &str) {
+        assert_eq!(
+            s.parse::<RangeOperator>(),
+            Err(PrefixError::InvalidRangeOperator(s.to_string()))
+        );
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24", (24, 24))]
+    #[case("192.0.2.0/24^-", (25, 32))]
+    #[case("192.0.2.0/24^+", (24, 32))]
+    #[case("192.0.2.0/24^24", (24, 24))]
+    #[case("192.0.2.0/24^28", (28, 28))]
+    #[case("10.0.0.0/8^16-24", (16, 24))]
+    #[case("2001:db8::/32^-", (33, 128))]
+    #[case("2001:db8::/32^+", (32, 128))]
+    #[case("2001:db8::/32^48-64", (48, 64))]
+    fn prefix_range_length_bounds(#[case] s: &str, #[case] expected: (u8, u8)) {
+        let range: AddressPrefixRange = s.parse().unwrap();
+        assert_eq!(range.length_bounds(), expected);
+        assert_eq!(range.to_string(), s);
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24^16")]
+    #[case("192.0.2.0/24^33")]
+    #[case("192.0.2.0/24^16-24")]
+    #[case("192.0.2.0/24^24-33")]
+    #[case("192.0.2.1/32^-")]
+    #[case("192.0.2.0/24^")]
+    #[case("192.0.2.1/24^+")]
+    fn invalid_prefix_range_is_err(#[case] s: &str) {
+        assert!(s.parse::<AddressPrefixRange>().is_err());
+    }
+
+    #[rstest]
+    #[case("192.0.2.0/24", "192.0.2.0/24", true)]
+    #[case("192.0.2.0/24", "192.0.2.0/25", false)]
+    #[case("192.0.2.0/24^-", "192.0.2.0/24", false)]
+    #[case("192.0.2.0/24^-", "192.0.2.128/25", true)]
+    #[case("192.0.2.0/24^+", "192.0.2.0/24", true)]
+    #[case("192.0.2.0/24^+", "192.0.3.0/32", false)]
+    #[case("10.0.0.0/8^16-24", "10.20.0.0/16", true)]
+    #[case("10.0.0.0/8^16-24", "10.20.30.128/25", false)]
+    #[case("2001:db8::/32^48", "2001:db8:1::/48", true)]
+    #[case("2001:db8::/32^48", "2001:db8:1::/56", false)]
+    fn prefix_range_contains(#[case] range: &str, #[case] prefix: &str, #[case] expected: bool) {
+        let range: AddressPrefixRange = range.parse().unwrap();
+        assert_eq!(range.contains(&prefix.parse().unwrap()), expected);
+    }
+
+    #[rstest]
+    #[case("5.0.0.0/8", RangeOperator::Exclusive, Some("5.0.0.0/8^-"))]
+    #[case("5.0.0.0/8^-", RangeOperator::Inclusive, Some("5.0.0.0/8^-"))]
+    #[case("5.0.0.0/8^+", RangeOperator::Exclusive, Some("5.0.0.0/8^-"))]
+    #[case("5.0.0.0/8^+", RangeOperator::Length(24), Some("5.0.0.0/8^24"))]
+    #[case(
+        "5.0.0.0/8^24-32",
+        RangeOperator::Range(26, 28),
+        Some("5.0.0.0/8^26-28")
+    )]
+    #[case(
+        "5.0.0.0/8^24-26",
+        RangeOperator::Range(26, 28),
+        Some("5.0.0.0/8^26-28")
+    )]
+    #[case(
+        "5.0.0.0/8^24-26",
+        RangeOperator::Range(20, 28),
+        Some("5.0.0.0/8^24-28")
+    )]
+    #[case("5.0.0.0/8^24", RangeOperator::Length(16), None)]
+    #[case("192.0.2.1/32", RangeOperator::Exclusive, None)]
+    #[case("192.0.2.0/24", RangeOperator::Range(24, 24), Some("192.0.2.0/24"))]
+    fn prefix_range_apply(
+        #[case] range: &str,
+        #[case] operator: RangeOperator,
+        #[case] expected: Option<&str>,
+    ) {
+        let range: AddressPrefixRange = range.parse().unwrap();
+        assert_eq!(
+            range
+                .apply(operator)
+                .map(|range| range.to_string())
+                .as_deref(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,575 @@
+//! Class templates describing the attributes objects of a class may contain.
+//!
+//! A [`Schema`] is a registry of [`ClassTemplate`]s, each describing which attributes are
+//! mandatory or optional, whether they may occur multiple times and whether they are part of the
+//! primary key or used as a lookup key. Objects can be checked against a schema using
+//! [`Object::validate`], which reports every [`Violation`] found.
+//!
+//! The templates returned by [`Schema::builtin`] cover the classes defined by
+//! [RFC 2622](https://datatracker.ietf.org/doc/html/rfc2622),
+//! [RFC 2725](https://datatracker.ietf.org/doc/html/rfc2725) and
+//! [RFC 4012](https://datatracker.ietf.org/doc/html/rfc4012), including the extensions
+//! used by the RIPE database and `IRRd`.
+//!
+//! # Example
+//! ```
+//! # use rpsl::{object, schema::{Schema, Violation}};
+//! let schema = Schema::builtin();
+//! let route = object! {
+//!     "route": "192.0.2.0/24";
+//!     "origin": "AS65536";
+//!     "origin": "AS65537";
+//!     "source": "RIPE";
+//! };
+//! assert_eq!(
+//!     route.validate(&schema).unwrap_err(),
+//!     vec![
+//!         Violation::RepeatedAttribute { name: "origin".to_string(), count: 2 },
+//!         Violation::MissingAttribute("mnt-by".to_string()),
+//!     ]
+//! );
+//! ```
+use std::{borrow::Cow, collections::HashMap, sync::OnceLock};
+
+use thiserror::Error;
+
+use crate::Object;
+
+mod templates;
+
+/// Whether an attribute must be present in an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Presence {
+    /// The attribute must be present.
+    Mandatory,
+    /// The attribute may be omitted.
+    Optional,
+}
+
+/// How often an attribute may occur in an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Multiplicity {
+    /// The attribute may occur at most once.
+    Single,
+    /// The attribute may occur any number of times.
+    Multiple,
+}
+
+/// The rules for a single attribute of a [`ClassTemplate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeTemplate {
+    name: Cow<'static, str>,
+    presence: Presence,
+    multiplicity: Multiplicity,
+    primary_key: bool,
+    lookup_key: bool,
+}
+
+impl AttributeTemplate {
+    /// Create a new attribute template that is neither a primary nor a lookup key.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::schema::{AttributeTemplate, Multiplicity, Presence};
+    /// let origin = AttributeTemplate::new("origin", Presence::Mandatory, Multiplicity::Single)
+    ///     .with_primary_key();
+    /// assert!(origin.is_mandatory());
+    /// assert!(origin.is_primary_key());
+    /// assert!(!origin.is_lookup_key());
+    /// ```
+    #[must_use]
+    pub fn new<N>(name: N, presence: Presence, multiplicity: Multiplicity) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        Self {
+            name: name.into(),
+            presence,
+            multiplicity,
+            primary_key: false,
+            lookup_key: false,
+        }
+    }
+
+    /// Mark the attribute as being part of the primary key of its class.
+    #[must_use]
+    pub fn with_primary_key(mut self) -> Self {
+        self.primary_key = true;
+        self
+    }
+
+    /// Mark the attribute as a lookup key that objects can be queried by.
+    #[must_use]
+    pub fn with_lookup_key(mut self) -> Self {
+        self.lookup_key = true;
+        self
+    }
+
+    /// The name of the attribute.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the attribute must be present.
+    #[must_use]
+    pub fn presence(&self) -> Presence {
+        self.presence
+    }
+
+    /// How often the attribute may occur.
+    #[must_use]
+    pub fn multiplicity(&self) -> Multiplicity {
+        self.multiplicity
+    }
+
+    /// Whether the attribute must be present.
+    #[must_use]
+    pub fn is_mandatory(&self) -> bool {
+        self.presence == Presence::Mandatory
+    }
+
+    /// Whether the attribute may occur more than once.
+    #[must_use]
+    pub fn is_multiple(&self) -> bool {
+        self.multiplicity == Multiplicity::Multiple
+    }
+
+    /// Whether the attribute is part of the primary key of its class.
+    #[must_use]
+    pub fn is_primary_key(&self) -> bool {
+        self.primary_key
+    }
+
+    /// Whether the attribute is a lookup key.
+    #[must_use]
+    pub fn is_lookup_key(&self) -> bool {
+        self.lookup_key
+    }
+}
+
+/// The template of an RPSL class, describing the attributes its objects may contain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassTemplate {
+    name: Cow<'static, str>,
+    attributes: Vec<AttributeTemplate>,
+}
+
+impl ClassTemplate {
+    /// Create a new class template from the templates of its attributes.
+    ///
+    /// By convention, the first attribute carries the name of the class.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::schema::{AttributeTemplate, ClassTemplate, Multiplicity, Presence};
+    /// let template = ClassTemplate::new(
+    ///     "peering-policy",
+    ///     vec![
+    ///         AttributeTemplate::new("peering-policy", Presence::Mandatory, Multiplicity::Single)
+    ///             .with_primary_key()
+    ///             .with_lookup_key(),
+    ///         AttributeTemplate::new("url", Presence::Optional, Multiplicity::Multiple),
+    ///         AttributeTemplate::new("source", Presence::Mandatory, Multiplicity::Single),
+    ///     ],
+    /// );
+    /// assert_eq!(template.name(), "peering-policy");
+    /// ```
+    #[must_use]
+    pub fn new<N>(name: N, attributes: Vec<AttributeTemplate>) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        Self {
+            name: name.into(),
+            attributes,
+        }
+    }
+
+    /// The name of the class.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The templates of all attributes, in the order they are conventionally listed.
+    #[must_use]
+    pub fn attributes(&self) -> &[AttributeTemplate] {
+        &self.attributes
+    }
+
+    /// The template of the attribute with the given name, ignoring ASCII case.
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<&AttributeTemplate> {
+        self.attributes
+            .iter()
+            .find(|a| a.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The attributes that, together, make up the primary key of the class.
+    pub fn primary_key(&self) -> impl Iterator<Item = &AttributeTemplate> {
+        self.attributes.iter().filter(|a| a.primary_key)
+    }
+
+    /// The attributes objects of the class can be looked up by.
+    pub fn lookup_keys(&self) -> impl Iterator<Item = &AttributeTemplate> {
+        self.attributes.iter().filter(|a| a.lookup_key)
+    }
+
+    /// Check an object against the template, returning all violations found.
+    /// The class of the object is not checked.
+    fn violations(&self, object: &Object) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for template in &self.attributes {
+            let attributes: Vec<_> = object.attributes_named(template.name()).collect();
+            if template.is_mandatory()
+                && attributes.iter().all(|a| a.value.with_content().is_empty())
+            {
+                violations.push(Violation::MissingAttribute(template.name().to_string()));
+            }
+            if !template.is_multiple() && attributes.len() > 1 {
+                violations.push(Violation::RepeatedAttribute {
+                    name: template.name().to_string(),
+                    count: attributes.len(),
+                });
+            }
+        }
+
+        let mut unknown: Vec<&str> = Vec::new();
+        for attribute in object.iter() {
+            if self.attribute(&attribute.name).is_none()
+                && !unknown
+                    .iter()
+                    .any(|u| u.eq_ignore_ascii_case(&attribute.name))
+            {
+                unknown.push(&attribute.name);
+            }
+        }
+        violations.extend(
+            unknown
+                .into_iter()
+                .map(|name| Violation::UnknownAttribute(name.to_string())),
+        );
+
+        violations
+    }
+}
+
+/// A registry of [`ClassTemplate`]s, keyed by the case insensitive name of their class.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    classes: HashMap<String, ClassTemplate>,
+}
+
+impl Schema {
+    /// Create a new schema that does not contain any classes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new schema containing the templates of all built-in classes.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::schema::Schema;
+    /// let schema = Schema::builtin();
+    /// let route = schema.get("route").unwrap();
+    /// let primary_key: Vec<&str> = route.primary_key().map(|a| a.name()).collect();
+    /// assert_eq!(primary_key, vec!["route", "origin"]);
+    /// ```
+    #[must_use]
+    pub fn builtin() -> Self {
+        let mut schema = Self::new();
+        for template in templates::builtin() {
+            schema.insert(template);
+        }
+        schema
+    }
+
+    /// The built-in schema, created on first use and shared for the lifetime of the program.
+    pub(crate) fn builtin_ref() -> &'static Self {
+        static BUILTIN: OnceLock<Schema> = OnceLock::new();
+        BUILTIN.get_or_init(Self::builtin)
+    }
+
+    /// Add a class template to the schema, returning the template previously registered
+    /// for the same class, if any.
+    pub fn insert(&mut self, template: ClassTemplate) -> Option<ClassTemplate> {
+        self.classes
+            .insert(template.name().to_ascii_lowercase(), template)
+    }
+
+    /// The template of the given class, ignoring ASCII case.
+    #[must_use]
+    pub fn get(&self, class: &str) -> Option<&ClassTemplate> {
+        self.classes.get(&class.to_ascii_lowercase())
+    }
+
+    /// Iterate over all class templates in the schema in arbitrary order.
+    pub fn classes(&self) -> impl Iterator<Item = &ClassTemplate> {
+        self.classes.values()
+    }
+}
+
+/// A way in which an [`Object`] does not conform to a [`Schema`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The object does not contain any attributes.
+    #[error("object does not contain any attributes")]
+    EmptyObject,
+    /// The class of the object is not part of the schema.
+    #[error("unknown class `{0}`")]
+    UnknownClass(String),
+    /// A mandatory attribute is missing or does not have a value.
+    #[error("missing mandatory attribute `{0}`")]
+    MissingAttribute(String),
+    /// An attribute that may only occur once occurs multiple times.
+    #[error("attribute `{name}` may only occur once, but occurs {count} times")]
+    RepeatedAttribute {
+        /// The name of the attribute.
+        name: String,
+        /// The number of times the attribute occurs.
+        count: usize,
+    },
+    /// An attribute is not part of the class template.
+    #[error("unknown attribute `{0}`")]
+    UnknownAttribute(String),
+}
+
+impl Object<'_> {
+    /// Validate the object against the template of its class in the given schema.
+    ///
+    /// # Errors
+    /// Returns all [`Violation`]s found if the object does not conform to the schema.
+    ///
+    /// # Example
+    /// ```
+    /// # use rpsl::{object, schema::{Schema, Violation}};
+    /// let schema = Schema::builtin();
+    /// let mntner = object! {
+    ///     "mntner": "MAINT-EXAMPLE";
+    ///     "admin-c": "RPSL1-RIPE";
+    ///     "upd-to": "noc@example.com";
+    ///     "auth": "PGPKEY-8F9D4C3B";
+    ///     "mnt-by": "MAINT-EXAMPLE";
+    ///     "source": "RIPE";
+    /// };
+    /// assert!(mntner.validate(&schema).is_ok());
+    /// ```
+    pub fn validate(&self, schema: &Schema) -> Result<(), Vec<Violation>> {
+        let Some(class) = self.class() else {
+            return Err(vec![Violation::EmptyObject]);
+        };
+        let Some(template) = schema.get(class) else {
+            return Err(vec![Violation::UnknownClass(class.to_string())]);
+        };
+
+        let violations = template.violations(self);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+    use crate::{object, Attribute};
+
+    #[rstest]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "descr": "Example";
+            "origin": "AS65536";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        }
+    )]
+    #[case(
+        object! {
+            "route6": "2001:db8::/32";
+            "origin": "AS65536";
+            "mnt-by": "MAINT-EXAMPLE", "MAINT-EXAMPLE2";
+            "mnt-by": "MAINT-EXAMPLE3";
+            "source": "RIPE";
+        }
+    )]
+    #[case(
+        object! {
+            "aut-num": "AS65536";
+            "as-name": "EXAMPLE";
+            "import": "from AS65537 accept ANY";
+            "mp-import": "afi ipv6.unicast from AS65537 accept ANY";
+            "admin-c": "RPSL1-RIPE";
+            "tech-c": "RPSL1-RIPE";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        }
+    )]
+    #[case(
+        object! {
+            "AS-SET": "AS-EXAMPLE";
+            "Members": "AS65536, AS65537";
+            "admin-c": "RPSL1-RIPE";
+            "tech-c": "RPSL1-RIPE";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+        }
+    )]
+    fn valid_objects_are_ok(#[case] object: Object) {
+        assert_eq!(object.validate(&Schema::builtin()), Ok(()));
+    }
+
+    #[rstest]
+    #[case(Object::new(vec![]), vec![Violation::EmptyObject])]
+    #[case(
+        object! {
+            "peering-policy": "EXAMPLE";
+        },
+        vec![Violation::UnknownClass("peering-policy".to_string())]
+    )]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "source": "RIPE";
+        },
+        vec![
+            Violation::MissingAttribute("origin".to_string()),
+            Violation::MissingAttribute("mnt-by".to_string()),
+        ]
+    )]
+    #[case(
+        Object::new(vec![
+            Attribute::unchecked_single("route", "192.0.2.0/24"),
+            Attribute::unchecked_single("origin", None),
+            Attribute::unchecked_single("mnt-by", "MAINT-EXAMPLE"),
+            Attribute::unchecked_single("source", "RIPE"),
+        ]),
+        vec![Violation::MissingAttribute("origin".to_string())]
+    )]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "origin": "AS65536";
+            "mnt-by": "MAINT-EXAMPLE";
+            "source": "RIPE";
+            "source": "RADB";
+        },
+        vec![Violation::RepeatedAttribute { name: "source".to_string(), count: 2 }]
+    )]
+    #[case(
+        object! {
+            "route": "192.0.2.0/24";
+            "origin": "AS65536";
+            "favourite-color": "blue";
+            "mnt-by": "MAINT-EXAMPLE";
+            "Favourite-Color": "green";
+            "source": "RIPE";
+        },
+        vec![Violation::UnknownAttribute("favourite-color".to_string())]
+    )]
+    fn invalid_objects_return_all_violations(
+        #[case] object: Object,
+        #[case] expected: Vec<Violation>,
+    ) {
+        assert_eq!(object.validate(&Schema::builtin()), Err(expected));
+    }
+
+    #[test]
+    fn custom_class() {
+        let mut schema = Schema::new();
+        let previous = schema.insert(ClassTemplate::new(
+            "peering-policy",
+            vec![
+                AttributeTemplate::new("peering-policy", Presence::Mandatory, Multiplicity::Single)
+                    .with_primary_key()
+                    .with_lookup_key(),
+                AttributeTemplate::new("url", Presence::Optional, Multiplicity::Multiple),
+            ],
+        ));
+        assert!(previous.is_none());
+
+        assert_eq!(
+            object! {
+                "peering-policy": "EXAMPLE";
+                "url": "https://example.com/peering";
+            }
+            .validate(&schema),
+            Ok(())
+        );
+        assert_eq!(
+            object! {
+                "route": "192.0.2.0/24";
+            }
+            .validate(&schema),
+            Err(vec![Violation::UnknownClass("route".to_string())])
+        );
+    }
+
+    #[test]
+    fn insert_replaces_existing_class() {
+        let mut schema = Schema::builtin();
+        let previous = schema.insert(ClassTemplate::new(
+            "Route",
+            vec![AttributeTemplate::new(
+                "route",
+                Presence::Mandatory,
+                Multiplicity::Single,
+            )],
+        ));
+
+        assert_eq!(previous.unwrap().name(), "route");
+        assert_eq!(schema.get("route").unwrap().attributes().len(), 1);
+    }
+
+    #[test]
+    /// Each built-in class template is named after its first attribute, which is mandatory
+    /// and does not contain duplicate attributes.
+    fn builtin_templates_are_consistent() {
+        let schema = Schema::builtin();
+        assert!(schema.classes().count() >= 19);
+
+        for template in schema.classes() {
+            let first = &template.attributes()[0];
+            assert_eq!(first.name(), template.name());
+            assert!(first.is_mandatory());
+            assert!(template.lookup_keys().next().is_some());
+            assert!(template.primary_key().next().is_some());
+
+            for (i, attribute) in template.attributes().iter().enumerate() {
+                assert!(
+                    !template.attributes()[i + 1..]
+                        .iter()
+                        .any(|a| a.name() == attribute.name()),
+                    "duplicate attribute {} in {}",
+                    attribute.name(),
+                    template.name()
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("route", vec!["route", "origin"])]
+    #[case("route6", vec!["route6", "origin"])]
+    #[case("aut-num", vec!["aut-num"])]
+    #[case("person", vec!["nic-hdl"])]
+    #[case("role", vec!["nic-hdl"])]
+    fn builtin_primary_keys(#[case] class: &str, #[case] expected: Vec<&str>) {
+        let schema = Schema::builtin();
+        let primary_key: Vec<&str> = schema
+            .get(class)
+            .unwrap()
+            .primary_key()
+            .map(AttributeTemplate::name)
+            .collect();
+        assert_eq!(primary_key, expected);
+    }
+}
@@ -0,0 +1,341 @@
+//! Built-in class templates.
+//!
+//! Attributes are listed in the order used by the RIPE database. Attributes that are generated by
+//! the database are treated as optional, while attributes that are mandatory in
+//! RFC 2622 but have been made optional by modern IRR implementations (e.g. `changed`) are
+//! optional as well.
+use std::iter::once;
+
+use super::{
+    AttributeTemplate, ClassTemplate,
+    Multiplicity::{Multiple, Single},
+    Presence::{Mandatory, Optional},
+};
+
+/// The attribute naming the class, which is the primary and a lookup key.
+fn class_key(name: &'static str) -> AttributeTemplate {
+    AttributeTemplate::new(name, Mandatory, Single)
+        .with_primary_key()
+        .with_lookup_key()
+}
+
+fn mandatory_single(name: &'static str) -> AttributeTemplate {
+    AttributeTemplate::new(name, Mandatory, Single)
+}
+
+fn mandatory_multiple(name: &'static str) -> AttributeTemplate {
+    AttributeTemplate::new(name, Mandatory, Multiple)
+}
+
+fn optional_single(name: &'static str) -> AttributeTemplate {
+    AttributeTemplate::new(name, Optional, Single)
+}
+
+fn optional_multiple(name: &'static str) -> AttributeTemplate {
+    AttributeTemplate::new(name, Optional, Multiple)
+}
+
+/// Create a class template from its specific attributes, followed by the attributes common
+/// to all classes.
+fn template<I>(name: &'static str, attributes: I) -> ClassTemplate
+where
+    I: IntoIterator<Item = AttributeTemplate>,
+{
+    let common = [
+        optional_multiple("notify"),
+        mandatory_multiple("mnt-by"),
+        optional_multiple("changed"),
+        optional_single("created"),
+        optional_single("last-modified"),
+        mandatory_single("source"),
+    ];
+    ClassTemplate::new(name, attributes.into_iter().chain(common).collect())
+}
+
+/// The attributes shared by the `inetnum` and `inet6num` classes following the class key.
+fn inetnum_attributes() -> [AttributeTemplate; 19] {
+    [
+        mandatory_single("netname").with_lookup_key(),
+        optional_multiple("descr"),
+        mandatory_multiple("country"),
+        optional_single("geofeed"),
+        optional_single("geoloc"),
+        optional_multiple("language"),
+        optional_single("org"),
+        optional_single("sponsoring-org"),
+        mandatory_multiple("admin-c"),
+        mandatory_multiple("tech-c"),
+        optional_single("abuse-c"),
+        mandatory_single("status"),
+        optional_single("assignment-size"),
+        optional_multiple("remarks"),
+        optional_multiple("mnt-lower"),
+        optional_multiple("mnt-routes"),
+        optional_multiple("mnt-domains"),
+        optional_multiple("mnt-irt"),
+        optional_multiple("mnt-ref"),
+    ]
+}
+
+/// The attributes shared by the `route` and `route6` classes following the class key.
+fn route_attributes() -> [AttributeTemplate; 15] {
+    [
+        optional_multiple("descr"),
+        mandatory_single("origin").with_primary_key(),
+        optional_multiple("pingable"),
+        optional_multiple("ping-hdl"),
+        optional_multiple("holes"),
+        optional_multiple("org"),
+        optional_multiple("member-of"),
+        optional_multiple("inject"),
+        optional_single("aggr-mtd"),
+        optional_single("aggr-bndry"),
+        optional_single("export-comps"),
+        optional_single("components"),
+        optional_multiple("remarks"),
+        optional_multiple("mnt-lower"),
+        optional_multiple("mnt-routes"),
+    ]
+}
+
+/// The attributes shared by the `as-set`, `route-set` and `rtr-set` classes following the
+/// class key.
+fn set_attributes() -> [AttributeTemplate; 10] {
+    [
+        optional_multiple("descr"),
+        optional_multiple("members"),
+        optional_multiple("mp-members"),
+        optional_multiple("mbrs-by-ref"),
+        optional_multiple("remarks"),
+        optional_multiple("org"),
+        mandatory_multiple("tech-c"),
+        mandatory_multiple("admin-c"),
+        optional_multiple("mnt-lower"),
+        optional_multiple("mnt-ref"),
+    ]
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn builtin() -> Vec<ClassTemplate> {
+    vec![
+        template(
+            "as-block",
+            [
+                class_key("as-block"),
+                optional_multiple("descr"),
+                optional_multiple("remarks"),
+                optional_single("org"),
+                optional_multiple("admin-c"),
+                optional_multiple("tech-c"),
+                optional_multiple("mnt-lower"),
+            ],
+        ),
+        template("as-set", once(class_key("as-set")).chain(set_attributes())),
+        template(
+            "aut-num",
+            [
+                class_key("aut-num"),
+                mandatory_single("as-name"),
+                optional_multiple("descr"),
+                optional_multiple("member-of").with_lookup_key(),
+                optional_multiple("import-via"),
+                optional_multiple("import"),
+                optional_multiple("mp-import"),
+                optional_multiple("export-via"),
+                optional_multiple("export"),
+                optional_multiple("mp-export"),
+                optional_multiple("default"),
+                optional_multiple("mp-default"),
+                optional_multiple("remarks"),
+                optional_single("org"),
+                optional_single("sponsoring-org"),
+                mandatory_multiple("admin-c"),
+                mandatory_multiple("tech-c"),
+                optional_single("abuse-c"),
+                optional_single("status"),
+                optional_multiple("mnt-lower"),
+                optional_multiple("mnt-routes"),
+            ],
+        ),
+        template(
+            "domain",
+            [
+                class_key("domain"),
+                optional_multiple("descr"),
+                optional_multiple("org"),
+                mandatory_multiple("admin-c"),
+                mandatory_multiple("tech-c"),
+                mandatory_multiple("zone-c"),
+                mandatory_multiple("nserver"),
+                optional_multiple("ds-rdata"),
+                optional_multiple("remarks"),
+            ],
+        ),
+        template(
+            "filter-set",
+            [
+                class_key("filter-set"),
+                optional_multiple("descr"),
+                optional_single("filter"),
+                optional_single("mp-filter"),
+                optional_multiple("remarks"),
+                optional_multiple("org"),
+                mandatory_multiple("tech-c"),
+                mandatory_multiple("admin-c"),
+                optional_multiple("mnt-lower"),
+            ],
+        ),
+        template(
+            "inet6num",
+            once(class_key("inet6num")).chain(inetnum_attributes()),
+        ),
+        template(
+            "inet-rtr",
+            [
+                class_key("inet-rtr"),
+                optional_multiple("descr"),
+                optional_multiple("alias"),
+                mandatory_single("local-as"),
+                mandatory_multiple("ifaddr"),
+                optional_multiple("interface"),
+                optional_multiple("peer"),
+                optional_multiple("mp-peer"),
+                optional_multiple("member-of"),
+                optional_multiple("remarks"),
+                optional_multiple("org"),
+                mandatory_multiple("admin-c"),
+                mandatory_multiple("tech-c"),
+            ],
+        ),
+        template(
+            "inetnum",
+            once(class_key("inetnum")).chain(inetnum_attributes()),
+        ),
+        template(
+            "irt",
+            [
+                class_key("irt"),
+                mandatory_multiple("address"),
+                optional_multiple("phone"),
+                optional_multiple("fax-no"),
+                mandatory_multiple("e-mail"),
+                optional_multiple("abuse-mailbox"),
+                optional_multiple("signature"),
+                optional_multiple("encryption"),
+                optional_multiple("org"),
+                mandatory_multiple("admin-c"),
+                mandatory_multiple("tech-c"),
+                mandatory_multiple("auth"),
+                optional_multiple("remarks"),
+                optional_multiple("irt-nfy"),
+            ],
+        ),
+        template(
+            "key-cert",
+            [
+                class_key("key-cert"),
+                optional_single("method"),
+                optional_multiple("owner"),
+                optional_single("fingerprint"),
+                mandatory_multiple("certif"),
+                optional_multiple("org"),
+                optional_multiple("remarks"),
+                optional_multiple("admin-c"),
+                optional_multiple("tech-c"),
+            ],
+        ),
+        template(
+            "mntner",
+            [
+                class_key("mntner"),
+                optional_multiple("descr"),
+                optional_multiple("org"),
+                mandatory_multiple("admin-c"),
+                optional_multiple("tech-c"),
+                mandatory_multiple("upd-to"),
+                optional_multiple("mnt-nfy"),
+                mandatory_multiple("auth"),
+                optional_multiple("remarks"),
+            ],
+        ),
+        template(
+            "organisation",
+            [
+                class_key("organisation"),
+                mandatory_single("org-name").with_lookup_key(),
+                mandatory_single("org-type"),
+                optional_multiple("descr"),
+                optional_multiple("remarks"),
+                mandatory_multiple("address"),
+                optional_single("country"),
+                optional_multiple("phone"),
+                optional_multiple("fax-no"),
+                mandatory_multiple("e-mail"),
+                optional_single("geoloc"),
+                optional_multiple("language"),
+                optional_multiple("org"),
+                optional_multiple("admin-c"),
+                optional_multiple("tech-c"),
+                optional_single("abuse-c"),
+                optional_multiple("ref-nfy"),
+                mandatory_multiple("mnt-ref"),
+            ],
+        ),
+        template(
+            "peering-set",
+            [
+                class_key("peering-set"),
+                optional_multiple("descr"),
+                optional_multiple("peering"),
+                optional_multiple("mp-peering"),
+                optional_multiple("remarks"),
+                optional_multiple("org"),
+                mandatory_multiple("tech-c"),
+                mandatory_multiple("admin-c"),
+                optional_multiple("mnt-lower"),
+            ],
+        ),
+        template(
+            "person",
+            [
+                mandatory_single("person").with_lookup_key(),
+                mandatory_multiple("address"),
+                mandatory_multiple("phone"),
+                optional_multiple("fax-no"),
+                optional_multiple("e-mail"),
+                optional_multiple("org"),
+                class_key("nic-hdl"),
+                optional_multiple("remarks"),
+            ],
+        ),
+        template(
+            "role",
+            [
+                mandatory_single("role").with_lookup_key(),
+                mandatory_multiple("address"),
+                optional_multiple("phone"),
+                optional_multiple("fax-no"),
+                mandatory_multiple("e-mail"),
+                optional_multiple("org"),
+                optional_multiple("admin-c"),
+                optional_multiple("tech-c"),
+                class_key("nic-hdl"),
+                optional_multiple("abuse-mailbox"),
+                optional_multiple("remarks"),
+            ],
+        ),
+        template("route", once(class_key("route")).chain(route_attributes())),
+        template(
+            "route-set",
+            once(class_key("route-set")).chain(set_attributes()),
+        ),
+        template(
+            "route6",
+            once(class_key("route6")).chain(route_attributes()),
+        ),
+        template(
+            "rtr-set",
+            once(class_key("rtr-set")).chain(set_attributes()),
+        ),
+    ]
+}
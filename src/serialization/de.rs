@@ -0,0 +1,654 @@
+use std::borrow::Cow;
+
+use serde::de::{self, value::StrDeserializer, Deserialize, Unexpected, Visitor};
+
+use crate::{error::SerdeError, parse_object, Attribute, Object, Value};
+
+/// Deserialize a data structure from an RPSL [`Object`].
+///
+/// See [`from_str`] for how data structures are represented.
+///
+/// # Errors
+/// Returns an error if the object cannot be represented by the data structure, e.g. because a
+/// mandatory attribute is missing or a value cannot be parsed.
+pub fn from_object<'de, T>(object: &Object<'de>) -> Result<T, SerdeError>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(ObjectDeserializer { object })
+}
+
+/// Deserialize a data structure from an RPSL object.
+///
+/// The data structure is expected to be a struct or map, with each field deserialized from the
+/// attributes named like the field:
+/// - A `Vec` or other sequence contains the value of each attribute of that name, so that a
+///   repeated attribute can be represented. It is empty if there is no attribute of that name.
+/// - An `Option` is `None` if there is no attribute of that name, or a single one with an
+///   empty value.
+/// - Any other field requires exactly one attribute of that name, whose value is parsed into
+///   numbers, booleans and unit variants of enums as needed. Values spanning multiple lines are
+///   joined by `\n`, with empty lines being empty. Unlike a sequence or an `Option`, such a
+///   field that no attribute is named like is missing, even if it has a `#[serde(default)]`.
+///
+/// Attribute names are matched against the names of struct fields ignoring ASCII case, while
+/// the keys of a map are named like the first attribute of that name. Strings borrow from the
+/// input where possible. See [`to_string`](crate::to_string) for an example.
+///
+/// # Errors
+/// Returns an error if the input is not valid RPSL, or the object cannot be represented by the
+/// data structure, e.g. because a mandatory attribute is missing or a value cannot be parsed.
+pub fn from_str<'de, T>(rpsl: &'de str) -> Result<T, SerdeError>
+where
+    T: Deserialize<'de>,
+{
+    from_object(&parse_object(rpsl)?)
+}
+
+/// Deserializes a struct or map from the attributes of an object.
+struct ObjectDeserializer<'o, 'de> {
+    object: &'o Object<'de>,
+}
+
+impl<'o, 'de> ObjectDeserializer<'o, 'de> {
+    /// Access the attributes grouped by name, followed by the given struct fields that no
+    /// attribute is named like.
+    fn attributes(self, fields: &'static [&'static str]) -> AttributesAccess<'o, 'de> {
+        // The attributes of the same name, in order of the first appearance of the name.
+        let mut groups: Vec<Vec<&Attribute<'de>>> = Vec::new();
+        for attribute in self.object.iter() {
+            match groups
+                .iter_mut()
+                .find(|group| group[0].name.eq_ignore_ascii_case(&attribute.name))
+            {
+                Some(group) => group.push(attribute),
+                None => groups.push(vec![attribute]),
+            }
+        }
+        let missing: Vec<&'static str> = fields
+            .iter()
+            .copied()
+            .filter(|field| {
+                !groups
+                    .iter()
+                    .any(|group| group[0].name.eq_ignore_ascii_case(field))
+            })
+            .collect();
+        AttributesAccess {
+            groups: groups.into_iter(),
+            fields,
+            missing: missing.into_iter(),
+            next: None,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ObjectDeserializer<'_, 'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_map(self.attributes(&[]))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_map(self.attributes(fields))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map enum
+        identifier ignored_any
+    }
+}
+
+struct AttributesAccess<'o, 'de> {
+    groups: std::vec::IntoIter<Vec<&'o Attribute<'de>>>,
+    /// The fields of the struct being deserialized, if any.
+    fields: &'static [&'static str],
+    /// The fields of the struct that no attribute is named like.
+    missing: std::vec::IntoIter<&'static str>,
+    /// The attributes whose key was deserialized last.
+    next: Option<AttributesDeserializer<'o, 'de>>,
+}
+
+impl<'de> de::MapAccess<'de> for AttributesAccess<'_, 'de> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, SerdeError> {
+        if let Some(group) = self.groups.next() {
+            let name: &str = &group[0].name;
+            let field = self
+                .fields
+                .iter()
+                .find(|field| field.eq_ignore_ascii_case(name));
+            let key = seed.deserialize(StrDeserializer::<SerdeError>::new(
+                field.copied().unwrap_or(name),
+            ))?;
+            self.next = Some(AttributesDeserializer {
+                attributes: group,
+                missing: None,
+            });
+            return Ok(Some(key));
+        }
+        let Some(field) = self.missing.next() else {
+            return Ok(None);
+        };
+        let key = seed.deserialize(StrDeserializer::<SerdeError>::new(field))?;
+        self.next = Some(AttributesDeserializer {
+            attributes: Vec::new(),
+            missing: Some(field),
+        });
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, SerdeError> {
+        let attributes = self
+            .next
+            .take()
+            .ok_or_else(|| <SerdeError as de::Error>::custom("value requested before key"))?;
+        seed.deserialize(attributes)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.groups.len() + self.missing.len())
+    }
+}
+
+/// Deserializes a field from all attributes of the same name.
+struct AttributesDeserializer<'o, 'de> {
+    attributes: Vec<&'o Attribute<'de>>,
+    /// The struct field that no attribute is named like, if the attributes are missing.
+    missing: Option<&'static str>,
+}
+
+impl<'o, 'de> AttributesDeserializer<'o, 'de> {
+    /// The value of the only attribute.
+    fn single(&self) -> Result<ValueDeserializer<'o, 'de>, SerdeError> {
+        if let Some(field) = self.missing {
+            return Err(de::Error::missing_field(field));
+        }
+        match self.attributes.as_slice() {
+            [attribute] => Ok(ValueDeserializer {
+                value: &attribute.value,
+            }),
+            attributes => Err(<SerdeError as de::Error>::custom(format_args!(
+                "attribute `{}` occurs {} times, expected once",
+                attributes[0].name,
+                attributes.len()
+            ))),
+        }
+    }
+}
+
+macro_rules! forward_to_single {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+                self.single()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for AttributesDeserializer<'_, 'de> {
+    type Error = SerdeError;
+
+    forward_to_single! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32
+        deserialize_u64 deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char
+        deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf deserialize_unit
+        deserialize_map deserialize_identifier
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.attributes.as_slice() {
+            [] => visitor.visit_none(),
+            [attribute] if attribute.value == Value::SingleLine(None) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_seq(ValuesAccess {
+            values: self.attributes.into_iter(),
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.single()?.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.single()?.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+}
+
+struct ValuesAccess<'o, 'de> {
+    values: std::vec::IntoIter<&'o Attribute<'de>>,
+}
+
+impl<'de> de::SeqAccess<'de> for ValuesAccess<'_, 'de> {
+    type Error = SerdeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, SerdeError> {
+        self.values
+            .next()
+            .map(|attribute| {
+                seed.deserialize(ValueDeserializer {
+                    value: &attribute.value,
+                })
+            })
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// Deserializes a scalar from the value of a single attribute.
+struct ValueDeserializer<'o, 'de> {
+    value: &'o Value<'de>,
+}
+
+/// The text of a value, borrowing from the input if possible.
+enum Text<'o, 'de> {
+    Borrowed(&'de str),
+    /// Text outliving the deserializer but not the input, or joined from multiple lines.
+    Transient(Cow<'o, str>),
+}
+
+impl Text<'_, '_> {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Borrowed(s) => s,
+            Self::Transient(s) => s,
+        }
+    }
+}
+
+impl<'o, 'de> ValueDeserializer<'o, 'de> {
+    fn text(&self) -> Text<'o, 'de> {
+        match self.value {
+            Value::SingleLine(None) => Text::Borrowed(""),
+            Value::SingleLine(Some(Cow::Borrowed(value))) => Text::Borrowed(value),
+            Value::SingleLine(Some(Cow::Owned(value))) => Text::Transient(Cow::Borrowed(value)),
+            Value::MultiLine(values) => Text::Transient(Cow::Owned(
+                values
+                    .iter()
+                    .map(|value| value.as_deref().unwrap_or_default())
+                    .collect::<Vec<&str>>()
+                    .join("\n"),
+            )),
+        }
+    }
+
+    fn visit_text<V: Visitor<'de>>(&self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.text() {
+            Text::Borrowed(s) => visitor.visit_borrowed_str(s),
+            Text::Transient(Cow::Borrowed(s)) => visitor.visit_str(s),
+            Text::Transient(Cow::Owned(s)) => visitor.visit_string(s),
+        }
+    }
+
+    fn parse<T: std::str::FromStr, V: Visitor<'de>>(&self, visitor: &V) -> Result<T, SerdeError> {
+        let text = self.text();
+        text.as_str()
+            .parse()
+            .map_err(|_| de::Error::invalid_value(Unexpected::Str(text.as_str()), visitor))
+    }
+
+    fn invalid_type<V: Visitor<'de>>(&self, visitor: &V) -> SerdeError {
+        de::Error::invalid_type(Unexpected::Str(self.text().as_str()), visitor)
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+                let value = self.parse(&visitor)?;
+                visitor.$visit(value)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'_, 'de> {
+    type Error = SerdeError;
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.visit_text(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.visit_text(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.visit_text(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.visit_text(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.visit_text(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.value {
+            Value::SingleLine(None) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        Err(self.invalid_type(&visitor))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        Err(self.invalid_type(&visitor))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        Err(self.invalid_type(&visitor))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        Err(self.invalid_type(&visitor))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        Err(self.invalid_type(&visitor))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_enum(StrDeserializer::<SerdeError>::new(self.text().as_str()))
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.visit_text(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use rstest::*;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::object;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "kebab-case")]
+    enum Status {
+        Assigned,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Assignment<'a> {
+        inetnum: &'a str,
+        netname: Option<String>,
+        #[serde(default)]
+        descr: Vec<&'a str>,
+        status: Status,
+        #[serde(rename = "mnt-by")]
+        mnt_by: Vec<String>,
+        remarks: Option<String>,
+        #[serde(rename = "x-size")]
+        size: u32,
+    }
+
+    #[test]
+    fn struct_from_str() {
+        let rpsl = concat!(
+            "inetnum:   192.0.2.0 - 192.0.2.255\n",
+            "descr:     Documentation\n",
+            "status:    assigned\n",
+            "DESCR:     TEST-NET-1\n",
+            "mnt-by:    MAINT-A\n",
+            "remarks:   Line 1\n",
+            "+\n",
+            "           Line 3\n",
+            "x-size:    256\n",
+            "source:    RIPE\n",
+            "\n",
+        );
+        let inetnum: Assignment = from_str(rpsl).unwrap();
+        assert_eq!(
+            inetnum,
+            Assignment {
+                inetnum: "192.0.2.0 - 192.0.2.255",
+                netname: None,
+                descr: vec!["Documentation", "TEST-NET-1"],
+                status: Status::Assigned,
+                mnt_by: vec!["MAINT-A".to_string()],
+                remarks: Some("Line 1\n\nLine 3".to_string()),
+                size: 256,
+            }
+        );
+    }
+
+    #[test]
+    fn map_from_object() {
+        let object = object! {
+            "as-set": "AS-EXAMPLE";
+            "members": "AS1";
+            "members": "AS2";
+            "remarks": "";
+        };
+        let map: BTreeMap<String, Vec<String>> = from_object(&object).unwrap();
+        assert_eq!(
+            map,
+            BTreeMap::from([
+                ("as-set".to_string(), vec!["AS-EXAMPLE".to_string()]),
+                (
+                    "members".to_string(),
+                    vec!["AS1".to_string(), "AS2".to_string()]
+                ),
+                ("remarks".to_string(), vec![String::new()]),
+            ])
+        );
+
+        let map: HashMap<String, Option<String>> = from_object(&object! {
+            "mntner": "MAINT-A";
+            "remarks": "";
+        })
+        .unwrap();
+        assert_eq!(map["mntner"].as_deref(), Some("MAINT-A"));
+        assert_eq!(map["remarks"], None);
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct RouteObject {
+        route: String,
+        descr: Option<String>,
+        #[serde(rename = "mnt-by")]
+        mnt_by: Vec<String>,
+    }
+
+    #[rstest]
+    #[case(
+        "Route: 192.0.2.0/24\nMNT-BY: MAINT-A\nmnt-by: MAINT-B\n\n",
+        RouteObject {
+            route: "192.0.2.0/24".to_string(),
+            descr: None,
+            mnt_by: vec!["MAINT-A".to_string(), "MAINT-B".to_string()],
+        }
+    )]
+    #[case(
+        "ROUTE: 192.0.2.0/24\nDescr: Documentation\n\n",
+        RouteObject {
+            route: "192.0.2.0/24".to_string(),
+            descr: Some("Documentation".to_string()),
+            mnt_by: Vec::new(),
+        }
+    )]
+    /// Fields are matched ignoring ASCII case, with missing sequences being empty.
+    fn struct_fields_from_attributes(#[case] rpsl: &str, #[case] expected: RouteObject) {
+        assert_eq!(from_str::<RouteObject>(rpsl).unwrap(), expected);
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Announcement {
+        route: String,
+        origin: String,
+        #[serde(rename = "x-size")]
+        size: Option<u8>,
+    }
+
+    #[rstest]
+    #[case("route: 192.0.2.0/24\n\n", "missing field `origin`")]
+    #[case(
+        "route: 192.0.2.0/24\norigin: AS1\norigin: AS2\n\n",
+        "attribute `origin` occurs 2 times, expected once"
+    )]
+    #[case(
+        "route: 192.0.2.0/24\norigin: AS1\nx-size: 256\n\n",
+        "invalid value: string \"256\", expected u8"
+    )]
+    #[case("route; 192.0.2.0/24\n\n", "parse error at line 1")]
+    fn invalid_objects_are_rejected(#[case] rpsl: &str, #[case] error: &str) {
+        let err = from_str::<Announcement>(rpsl).unwrap_err();
+        assert!(err.to_string().starts_with(error), "{err}");
+    }
+}
@@ -0,0 +1,82 @@
+//! A Serde data format representing data structures as RPSL objects.
+pub use de::{from_object, from_str};
+pub use ser::{to_object, to_string};
+
+mod de;
+mod ser;
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct RouteObject {
+        route: String,
+        descr: Option<String>,
+        #[serde(rename = "mnt-by")]
+        mnt_by: Vec<String>,
+        remarks: Vec<Option<String>>,
+    }
+
+    /// Any string of extended ASCII characters without control characters other than `\n`,
+    /// including values that cannot be represented, such as those containing a `#`.
+    fn text() -> impl Strategy<Value = String> {
+        proptest::string::string_regex(r"[\x20-\x7E\x80-\xFF\n]{0,20}").unwrap()
+    }
+
+    /// A string that can be represented as a value, with no line starting with whitespace and
+    /// without a `#`.
+    fn representable_text() -> impl Strategy<Value = String> {
+        let line = r"([\x21-\x22\x24-\x7E][\x20-\x22\x24-\x7E\x80-\xFF]*)?";
+        proptest::string::string_regex(&format!(r"{line}(\n{line}){{0,3}}")).unwrap()
+    }
+
+    /// A route using the given strategies for its strings and for the strings within `Some`.
+    fn route(
+        text: impl Fn() -> BoxedStrategy<String>,
+        some: impl Fn() -> BoxedStrategy<String>,
+    ) -> impl Strategy<Value = RouteObject> {
+        (
+            text(),
+            proptest::option::of(some()),
+            proptest::collection::vec(text(), 0..4),
+            proptest::collection::vec(prop_oneof![Just(None), some().prop_map(Some)], 0..4),
+        )
+            .prop_map(|(route, descr, mnt_by, remarks)| RouteObject {
+                route,
+                descr,
+                mnt_by,
+                remarks,
+            })
+    }
+
+    proptest! {
+        /// Data structures are either serialized to RPSL that deserializes to the same data
+        /// structure or cannot be serialized.
+        #[test]
+        fn serialized_data_round_trips(route in route(|| text().boxed(), || text().boxed())) {
+            if let Ok(rpsl) = to_string(&route) {
+                prop_assert_eq!(from_str::<RouteObject>(&rpsl).unwrap(), route);
+            }
+        }
+
+        /// Data structures without an empty `Some`, `None` in a sequence, lines starting with
+        /// whitespace or a `#` can be serialized.
+        #[test]
+        fn representable_data_is_serialized(
+            route in route(
+                || representable_text().boxed(),
+                || representable_text().prop_filter("Must not be empty", |s| !s.is_empty()).boxed(),
+            ).prop_map(|mut route| {
+                route.remarks.retain(Option::is_some);
+                route
+            })
+        ) {
+            let rpsl = to_string(&route).unwrap();
+            prop_assert_eq!(from_str::<RouteObject>(&rpsl).unwrap(), route);
+        }
+    }
+}
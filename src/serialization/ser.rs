@@ -0,0 +1,545 @@
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::{error::SerdeError, Attribute, AttributeError, Name, Object, Value};
+
+/// Serialize a data structure into an RPSL [`Object`].
+///
+/// See [`to_string`] for how data structures are represented.
+///
+/// # Errors
+/// Returns an error if the data structure cannot be represented as RPSL, or contains an invalid
+/// attribute name or value.
+pub fn to_object<T>(value: &T) -> Result<Object<'static>, SerdeError>
+where
+    T: Serialize + ?Sized,
+{
+    let Field::Map(entries) = value.serialize(FieldSerializer)? else {
+        return Err(SerdeError::Unsupported(
+            "a value other than a struct or map",
+        ));
+    };
+
+    let mut attributes = Vec::with_capacity(entries.len());
+    for (name, field) in entries {
+        let name: Name<'static> = name.parse().map_err(AttributeError::from)?;
+        match field {
+            Field::Empty => {}
+            Field::Scalar(value) => attributes.push(Attribute::new(name, value_of(&value)?)),
+            Field::Seq(fields) => {
+                for field in fields {
+                    match field {
+                        Field::Empty => {
+                            return Err(SerdeError::Unsupported("`None` in a sequence"))
+                        }
+                        Field::Scalar(value) => {
+                            attributes.push(Attribute::new(name.clone(), value_of(&value)?));
+                        }
+                        Field::Seq(_) => return Err(SerdeError::Unsupported("a nested sequence")),
+                        Field::Map(_) => {
+                            return Err(SerdeError::Unsupported("a nested struct or map"))
+                        }
+                    }
+                }
+            }
+            Field::Map(_) => return Err(SerdeError::Unsupported("a nested struct or map")),
+        }
+    }
+    Ok(Object::new(attributes))
+}
+
+/// Serialize a data structure as RPSL.
+///
+/// The data structure must be a struct or map, each field of which becomes one or more
+/// attributes named like the field:
+/// - Strings, numbers, booleans, characters and unit variants of enums become a single attribute,
+///   with strings containing `\n` becoming a value spanning multiple lines.
+/// - Sequences such as a `Vec` become an attribute for each of their elements, so that a
+///   repeated attribute can be represented.
+/// - `None` and unit values are left out.
+///
+/// Nested structs and maps, nested sequences and enum variants containing data cannot be
+/// represented. Neither can values that would be read back differently, i.e. `Some` containing
+/// an empty string or sequence, `None` within a sequence, lines starting with whitespace and
+/// strings containing a `#`, which starts a comment. The object is laid out like its
+/// [`Display`](std::fmt::Display) implementation.
+///
+/// # Example
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// struct Route {
+///     route: String,
+///     descr: Option<String>,
+///     origin: String,
+///     #[serde(rename = "mnt-by")]
+///     mnt_by: Vec<String>,
+/// }
+///
+/// let route = Route {
+///     route: "192.0.2.0/24".to_string(),
+///     descr: None,
+///     origin: "AS65536".to_string(),
+///     mnt_by: vec!["MAINT-A".to_string(), "MAINT-B".to_string()],
+/// };
+/// let rpsl = rpsl::to_string(&route)?;
+/// assert_eq!(
+///     rpsl,
+///     concat!(
+///         "route:          192.0.2.0/24\n",
+///         "origin:         AS65536\n",
+///         "mnt-by:         MAINT-A\n",
+///         "mnt-by:         MAINT-B\n",
+///         "\n",
+///     )
+/// );
+/// assert_eq!(rpsl::from_str::<Route>(&rpsl)?, route);
+/// # Ok::<(), rpsl::SerdeError>(())
+/// ```
+///
+/// # Errors
+/// Returns an error if the data structure cannot be represented as RPSL, or contains an invalid
+/// attribute name or value.
+pub fn to_string<T>(value: &T) -> Result<String, SerdeError>
+where
+    T: Serialize + ?Sized,
+{
+    to_object(value).map(|object| object.to_string())
+}
+
+/// Create a value from a string, where each line separated by `\n` becomes a line of the value.
+fn value_of(s: &str) -> Result<Value<'static>, SerdeError> {
+    // Whitespace preceding a line is not part of the value when parsed.
+    if s.split('\n')
+        .any(|line| line.starts_with(char::is_whitespace))
+    {
+        return Err(SerdeError::Unsupported("a line starting with whitespace"));
+    }
+    let value = if s.contains('\n') {
+        Value::try_from(s.split('\n').collect::<Vec<&str>>()).map_err(AttributeError::from)?
+    } else {
+        s.parse().map_err(AttributeError::from)?
+    };
+    Ok(value)
+}
+
+/// The representation of a serialized data structure, before it is converted into attributes.
+#[derive(Debug)]
+enum Field {
+    /// A value that is left out, such as `None`.
+    Empty,
+    Scalar(String),
+    Seq(Vec<Field>),
+    Map(Vec<(String, Field)>),
+}
+
+/// Serializes any data structure into a [`Field`].
+struct FieldSerializer;
+
+fn scalar<T: ToString + ?Sized>(value: &T) -> Field {
+    Field::Scalar(value.to_string())
+}
+
+impl ser::Serializer for FieldSerializer {
+    type Ok = Field;
+    type Error = SerdeError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = Impossible<Field, SerdeError>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = Impossible<Field, SerdeError>;
+
+    fn serialize_bool(self, v: bool) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Field, SerdeError> {
+        Ok(scalar(&v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Field, SerdeError> {
+        Ok(scalar(v))
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Field, SerdeError> {
+        Err(SerdeError::Unsupported("a byte array"))
+    }
+
+    fn serialize_none(self) -> Result<Field, SerdeError> {
+        Ok(Field::Empty)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Field, SerdeError> {
+        // An empty value is read back as `None`.
+        match value.serialize(self)? {
+            Field::Scalar(value) if value.is_empty() => {
+                Err(SerdeError::Unsupported("`Some` containing an empty string"))
+            }
+            Field::Seq(fields) if fields.is_empty() => Err(SerdeError::Unsupported(
+                "`Some` containing an empty sequence",
+            )),
+            field => Ok(field),
+        }
+    }
+
+    fn serialize_unit(self) -> Result<Field, SerdeError> {
+        Ok(Field::Empty)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Field, SerdeError> {
+        Ok(Field::Empty)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Field, SerdeError> {
+        Ok(scalar(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Field, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Field, SerdeError> {
+        Err(SerdeError::Unsupported("an enum variant containing data"))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, SerdeError> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(SerdeError::Unsupported("an enum variant containing data"))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer, SerdeError> {
+        Ok(MapSerializer {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<MapSerializer, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(SerdeError::Unsupported("an enum variant containing data"))
+    }
+}
+
+struct SeqSerializer(Vec<Field>);
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Field;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.0.push(value.serialize(FieldSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Field, SerdeError> {
+        Ok(Field::Seq(self.0))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Field;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Field, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Field;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Field, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    entries: Vec<(String, Field)>,
+    /// The key of the entry whose value is serialized next.
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Field;
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        match key.serialize(FieldSerializer)? {
+            Field::Scalar(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(SerdeError::Unsupported(
+                "a map key other than a string or number",
+            )),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| <SerdeError as ser::Error>::custom("map value without a key"))?;
+        self.entries.push((key, value.serialize(FieldSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Field, SerdeError> {
+        Ok(Field::Map(self.entries))
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Field;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.entries
+            .push((key.to_string(), value.serialize(FieldSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Field, SerdeError> {
+        Ok(Field::Map(self.entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::*;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::object;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Status {
+        Assigned,
+        #[allow(dead_code)]
+        Allocated,
+    }
+
+    #[derive(Serialize)]
+    struct Assignment {
+        inetnum: &'static str,
+        netname: Option<&'static str>,
+        descr: Vec<&'static str>,
+        status: Status,
+        #[serde(rename = "mnt-by")]
+        mnt_by: Vec<&'static str>,
+        remarks: Option<&'static str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        notify: Option<&'static str>,
+        #[serde(rename = "x-size")]
+        size: u32,
+    }
+
+    #[test]
+    fn struct_to_object() {
+        let inetnum = Assignment {
+            inetnum: "192.0.2.0 - 192.0.2.255",
+            netname: None,
+            descr: vec!["Documentation", "TEST-NET-1"],
+            status: Status::Assigned,
+            mnt_by: vec![],
+            remarks: Some("Line 1\n\nLine 3"),
+            notify: None,
+            size: 256,
+        };
+        let expected = object! {
+            "inetnum": "192.0.2.0 - 192.0.2.255";
+            "descr": "Documentation";
+            "descr": "TEST-NET-1";
+            "status": "assigned";
+            "remarks": "Line 1", "", "Line 3";
+            "x-size": "256";
+        };
+        assert_eq!(to_object(&inetnum).unwrap(), expected);
+    }
+
+    #[test]
+    fn map_to_string() {
+        let map = BTreeMap::from([
+            ("as-set", vec!["AS-EXAMPLE"]),
+            ("members", vec!["AS1", "AS2"]),
+        ]);
+        assert_eq!(
+            to_string(&map).unwrap(),
+            "as-set:         AS-EXAMPLE\nmembers:        AS1\nmembers:        AS2\n\n"
+        );
+    }
+
+    #[derive(Serialize)]
+    struct Nested {
+        route: Inner,
+    }
+
+    #[derive(Serialize)]
+    struct Inner {
+        prefix: &'static str,
+    }
+
+    #[derive(Serialize)]
+    enum Data {
+        Variant(u8),
+    }
+
+    #[rstest]
+    #[case(to_object(&"route"), "a value other than a struct or map cannot be represented as RPSL")]
+    #[case(
+        to_object(&Nested { route: Inner { prefix: "192.0.2.0/24" } }),
+        "a nested struct or map cannot be represented as RPSL"
+    )]
+    #[case(
+        to_object(&BTreeMap::from([("members", vec![vec!["AS1"]])])),
+        "a nested sequence cannot be represented as RPSL"
+    )]
+    #[case(
+        to_object(&BTreeMap::from([("origin", Data::Variant(1))])),
+        "an enum variant containing data cannot be represented as RPSL"
+    )]
+    #[case(
+        to_object(&BTreeMap::from([("1origin", "AS1")])),
+        "Invalid attribute name: cannot start with a non-letter ASCII character"
+    )]
+    #[case(
+        to_object(&BTreeMap::from([("descr", "\u{2603}")])),
+        "Invalid attribute value: cannot contain characters that are not part of the extended ASCII set"
+    )]
+    #[case(
+        to_object(&BTreeMap::from([("descr", Some(""))])),
+        "`Some` containing an empty string cannot be represented as RPSL"
+    )]
+    #[case(
+        to_object(&BTreeMap::from([("mnt-by", Some(Vec::<&str>::new()))])),
+        "`Some` containing an empty sequence cannot be represented as RPSL"
+    )]
+    #[case(
+        to_object(&BTreeMap::from([("descr", vec![Some("A"), None])])),
+        "`None` in a sequence cannot be represented as RPSL"
+    )]
+    #[case(
+        to_object(&BTreeMap::from([("remarks", "A\n B")])),
+        "a line starting with whitespace cannot be represented as RPSL"
+    )]
+    #[case(
+        to_object(&BTreeMap::from([("remarks", "see http://x/#a")])),
+        "Invalid attribute value: cannot contain the \"#\" character, since it starts a comment"
+    )]
+    fn unrepresentable_values_are_rejected(
+        #[case] result: Result<Object<'static>, SerdeError>,
+        #[case] error: &str,
+    ) {
+        assert_eq!(result.unwrap_err().to_string(), error);
+    }
+}
@@ -0,0 +1,244 @@
+use std::{cmp::Ordering, fmt, hash, str::FromStr};
+
+use crate::{Asn, SetNameError};
+
+/// The class of a set, as determined by the prefix of the set components of its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SetClass {
+    /// An `as-set`, with components prefixed by `AS-`.
+    AsSet,
+    /// A `route-set`, with components prefixed by `RS-`.
+    RouteSet,
+    /// A `filter-set`, with components prefixed by `FLTR-`.
+    FilterSet,
+    /// An `rtr-set`, with components prefixed by `RTRS-`.
+    RtrSet,
+    /// A `peering-set`, with components prefixed by `PRNG-`.
+    PeeringSet,
+}
+
+impl SetClass {
+    const ALL: [Self; 5] = [
+        Self::AsSet,
+        Self::RouteSet,
+        Self::FilterSet,
+        Self::RtrSet,
+        Self::PeeringSet,
+    ];
+
+    /// The name of the RPSL class, e.g. `as-set`.
+    #[must_use]
+    pub const fn class_name(self) -> &'static str {
+        match self {
+            Self::AsSet => "as-set",
+            Self::RouteSet => "route-set",
+            Self::FilterSet => "filter-set",
+            Self::RtrSet => "rtr-set",
+            Self::PeeringSet => "peering-set",
+        }
+    }
+
+    /// The prefix of the set components of names of this class, e.g. `AS-`.
+    #[must_use]
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::AsSet => "AS-",
+            Self::RouteSet => "RS-",
+            Self::FilterSet => "FLTR-",
+            Self::RtrSet => "RTRS-",
+            Self::PeeringSet => "PRNG-",
+        }
+    }
+
+    /// The class of a set component, if it starts with one of the set prefixes and is followed
+    /// by letters, digits, `-` or `_` only.
+    fn of_component(component: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|class| {
+            let prefix = class.prefix();
+            component.len() > prefix.len()
+                && component[..prefix.len()].eq_ignore_ascii_case(prefix)
+                && component[prefix.len()..]
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+    }
+}
+
+/// The name of a set, e.g. `AS-CUSTOMERS` or the hierarchical `AS3257:AS-CUSTOMERS`.
+///
+/// Set names consist of components separated by `:`, each being either an AS number or a set
+/// name starting with the prefix of the set's class, as described in
+/// [RFC 2622, Section 5](https://datatracker.ietf.org/doc/html/rfc2622#section-5).
+/// Names are compared case-insensitively and regardless of the notation of contained AS numbers,
+/// while displaying a name preserves its original spelling.
+///
+/// # Example
+/// ```
+/// # use rpsl::{SetClass, SetName};
+/// let name: SetName = "AS1.10:as-Customers".parse()?;
+///
+/// assert_eq!(name.class(), SetClass::AsSet);
+/// assert_eq!(name, "AS65546:AS-CUSTOMERS".parse()?);
+/// assert_eq!(name.normalized(), "AS65546:AS-CUSTOMERS");
+/// assert_eq!(name.to_string(), "AS1.10:as-Customers");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SetName {
+    name: String,
+    normalized: String,
+    class: SetClass,
+}
+
+impl SetName {
+    /// The class of the set.
+    #[must_use]
+    pub fn class(&self) -> SetClass {
+        self.class
+    }
+
+    /// The name in its canonical form, with set components in uppercase and AS numbers in
+    /// asplain notation.
+    #[must_use]
+    pub fn normalized(&self) -> &str {
+        &self.normalized
+    }
+
+    /// The name as it was originally written.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+}
+
+impl FromStr for SetName {
+    type Err = SetNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut normalized = Vec::new();
+        let mut class = None;
+        for component in s.split(':') {
+            if let Some(component_class) = SetClass::of_component(component) {
+                if class.is_some_and(|class| class != component_class) {
+                    return Err(SetNameError::MixedClasses(s.to_string()));
+                }
+                class = Some(component_class);
+                normalized.push(component.to_ascii_uppercase());
+            } else if let Some(asn) = component
+                .get(..2)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("AS"))
+                .and_then(|_| component.parse::<Asn>().ok())
+            {
+                normalized.push(asn.to_string());
+            } else {
+                return Err(SetNameError::InvalidComponent {
+                    name: s.to_string(),
+                    component: component.to_string(),
+                });
+            }
+        }
+        let class = class.ok_or_else(|| SetNameError::MissingSetComponent(s.to_string()))?;
+
+        Ok(Self {
+            name: s.to_string(),
+            normalized: normalized.join(":"),
+            class,
+        })
+    }
+}
+
+impl PartialEq for SetName {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized == other.normalized
+    }
+}
+
+impl Eq for SetName {}
+
+impl hash::Hash for SetName {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.normalized.hash(state);
+    }
+}
+
+impl PartialOrd for SetName {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SetName {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.normalized.cmp(&other.normalized)
+    }
+}
+
+impl fmt::Display for SetName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("AS-FOO", SetClass::AsSet, "AS-FOO")]
+    #[case("as-foo_bar", SetClass::AsSet, "AS-FOO_BAR")]
+    #[case("AS3257:AS-CUSTOMERS", SetClass::AsSet, "AS3257:AS-CUSTOMERS")]
+    #[case("as1.10:as-foo:AS-BAR", SetClass::AsSet, "AS65546:AS-FOO:AS-BAR")]
+    #[case("RS-FOO:AS65536", SetClass::RouteSet, "RS-FOO:AS65536")]
+    #[case("fltr-martian", SetClass::FilterSet, "FLTR-MARTIAN")]
+    #[case("RTRS-EDGE", SetClass::RtrSet, "RTRS-EDGE")]
+    #[case("AS1:prng-foo", SetClass::PeeringSet, "AS1:PRNG-FOO")]
+    fn set_name_from_str(#[case] s: &str, #[case] class: SetClass, #[case] normalized: &str) {
+        let name: SetName = s.parse().unwrap();
+        assert_eq!(name.class(), class);
+        assert_eq!(name.normalized(), normalized);
+        assert_eq!(name.to_string(), s);
+    }
+
+    #[rstest]
+    #[case("", SetNameError::InvalidComponent { name: String::new(), component: String::new() })]
+    #[case(
+        "AS-",
+        SetNameError::InvalidComponent { name: "AS-".to_string(), component: "AS-".to_string() }
+    )]
+    #[case(
+        "AS-FOO:",
+        SetNameError::InvalidComponent { name: "AS-FOO:".to_string(), component: String::new() }
+    )]
+    #[case(
+        "AS-FOO.BAR",
+        SetNameError::InvalidComponent {
+            name: "AS-FOO.BAR".to_string(),
+            component: "AS-FOO.BAR".to_string()
+        }
+    )]
+    #[case(
+        "FOO:AS-BAR",
+        SetNameError::InvalidComponent {
+            name: "FOO:AS-BAR".to_string(),
+            component: "FOO".to_string()
+        }
+    )]
+    #[case("AS1:AS2", SetNameError::MissingSetComponent("AS1:AS2".to_string()))]
+    #[case("AS-FOO:RS-BAR", SetNameError::MixedClasses("AS-FOO:RS-BAR".to_string()))]
+    fn invalid_set_name_is_err(#[case] s: &str, #[case] expected: SetNameError) {
+        assert_eq!(s.parse::<SetName>().unwrap_err(), expected);
+    }
+
+    #[test]
+    fn set_names_compare_normalized() {
+        let names: HashSet<SetName> = ["AS-FOO", "as-foo", "AS1:AS-FOO", "AS0.1:As-Foo"]
+            .iter()
+            .map(|name| name.parse().unwrap())
+            .collect();
+        assert_eq!(names.len(), 2);
+    }
+}
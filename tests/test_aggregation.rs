@@ -0,0 +1,113 @@
+use std::collections::BTreeSet;
+
+use proptest::prelude::*;
+use rpsl::{generate::aggregate, AddressPrefixRange};
+
+proptest! {
+    /// Property based test to ensure aggregation does not change the set of accepted prefixes.
+    #[test]
+    fn aggregation_keeps_accepted_prefixes(ranges in strategies::prefix_ranges()) {
+        let aggregated = aggregate(ranges.clone());
+        for prefix in strategies::universe() {
+            let accepted = ranges.iter().any(|range| range.contains(&prefix));
+            let accepted_aggregated = aggregated.iter().any(|range| range.contains(&prefix));
+            prop_assert_eq!(accepted, accepted_aggregated, "prefix {}", prefix);
+        }
+    }
+
+    /// Property based test to ensure aggregation never returns more ranges than it is given.
+    #[test]
+    fn aggregation_does_not_grow(ranges in strategies::prefix_ranges()) {
+        let distinct: BTreeSet<AddressPrefixRange> = ranges.iter().copied().collect();
+        prop_assert!(aggregate(ranges).len() <= distinct.len());
+    }
+
+    /// Property based test to ensure aggregating an aggregated set of ranges does not change it.
+    #[test]
+    fn aggregation_is_idempotent(ranges in strategies::prefix_ranges()) {
+        let aggregated = aggregate(ranges);
+        prop_assert_eq!(aggregate(aggregated.clone()), aggregated);
+    }
+}
+
+mod strategies {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use proptest::prelude::*;
+    use rpsl::{AddressPrefix, AddressPrefixRange, RangeOperator};
+
+    /// The length of the IPv4 and IPv6 prefixes containing all generated prefixes.
+    const BASE_V4: u8 = 8;
+    const BASE_V6: u8 = 32;
+    /// The number of bits following the base prefix used by generated prefixes and ranges.
+    ///
+    /// Keeping this small results in many overlapping and adjacent ranges, which can be
+    /// aggregated, and allows to check every prefix within the base prefix.
+    const DEPTH: u8 = 7;
+
+    /// The address of the prefix with the given length following the base prefix.
+    fn address(ipv6: bool, length: u8, bits: u128) -> IpAddr {
+        if ipv6 {
+            let base = u128::from(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+            IpAddr::V6((base | bits << (128 - length)).into())
+        } else {
+            let base = u32::from(Ipv4Addr::new(10, 0, 0, 0));
+            // The bits never exceed the depth below the base prefix.
+            #[allow(clippy::cast_possible_truncation)]
+            IpAddr::V4((base | (bits as u32) << (32 - length)).into())
+        }
+    }
+
+    fn base(ipv6: bool) -> u8 {
+        if ipv6 {
+            BASE_V6
+        } else {
+            BASE_V4
+        }
+    }
+
+    /// Every IPv4 and IPv6 prefix within the base prefixes, up to the generated depth.
+    pub fn universe() -> Vec<AddressPrefix> {
+        let mut prefixes = Vec::new();
+        for ipv6 in [false, true] {
+            for depth in 0..=DEPTH {
+                let length = base(ipv6) + depth;
+                for bits in 0..1u128 << depth {
+                    prefixes.push(AddressPrefix::new(address(ipv6, length, bits), length).unwrap());
+                }
+            }
+        }
+        prefixes
+    }
+
+    /// A prefix range within one of the base prefixes.
+    ///
+    /// The prefix is at most `DEPTH - 2` bits longer than the base prefix, so that a range
+    /// operator can select more specifics of it.
+    fn prefix_range() -> impl Strategy<Value = AddressPrefixRange> {
+        (
+            any::<bool>(),
+            0..=DEPTH - 2,
+            any::<u128>(),
+            0..=DEPTH,
+            0..=DEPTH,
+            any::<bool>(),
+        )
+            .prop_map(|(ipv6, depth, bits, a, b, exact)| {
+                let length = base(ipv6) + depth;
+                let bits = bits & ((1u128 << depth) - 1);
+                let prefix = AddressPrefix::new(address(ipv6, length, bits), length).unwrap();
+                if exact {
+                    return prefix.into();
+                }
+                let min = (base(ipv6) + a.min(b)).max(length);
+                let max = (base(ipv6) + a.max(b)).max(min);
+                AddressPrefixRange::new(prefix, Some(RangeOperator::Range(min, max))).unwrap()
+            })
+    }
+
+    /// A list of prefix ranges.
+    pub fn prefix_ranges() -> impl Strategy<Value = Vec<AddressPrefixRange>> {
+        proptest::collection::vec(prefix_range(), 0..64)
+    }
+}
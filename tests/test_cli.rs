@@ -0,0 +1,190 @@
+#![cfg(feature = "cli")]
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use rstest::*;
+
+const OBJECTS: &str = "\
+% Information related to 'AS65536'
+
+aut-num: AS65536
+as-name: EXAMPLE
+remarks: Peering
++
+source: RIPE
+
+route: 192.0.2.0/24
+origin: AS65536
+source: RIPE
+
+";
+
+/// Run the `rpsl` binary with the given arguments and standard input, returning the exit code,
+/// standard output and standard error.
+fn rpsl(args: &[&str], stdin: &str) -> (i32, String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rpsl"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[rstest]
+#[case(&["validate"], OBJECTS, 0, "")]
+#[case(
+    &["validate"],
+    "role: A\n\nrole;  B\n\nrole: C\n\x01\n\n",
+    1,
+    "<stdin>:3:5: error: invalid separator in attribute `role`\n<stdin>:6:1: error: invalid attribute name\n"
+)]
+#[case(&["validate", "--lenient"], "role:\tA\n", 0, "<stdin>:1:6: warning: separator contains a tab\n<stdin>:2:1: warning: object is not terminated by a blank line\n")]
+#[case(&["validate", "--lenient", "--deny-warnings"], "role:\tA\n", 1, "<stdin>:1:6: warning: separator contains a tab\n<stdin>:2:1: warning: object is not terminated by a blank line\n")]
+#[case(
+    &["validate", "--lenient"],
+    "role;\tA\n\nrole:\tB\r\n\r\n-role: C\n",
+    1,
+    "<stdin>:1:5: error: invalid separator in attribute `role`\n<stdin>:3:6: warning: separator contains a tab\n\
+     <stdin>:3:8: warning: line ends with CRLF\n<stdin>:4:1: warning: line ends with CRLF\n\
+     <stdin>:5:1: error: invalid attribute name\n"
+)]
+#[case(&["validate", "missing.rpsl"], "", 2, "rpsl: missing.rpsl: No such file or directory (os error 2)\n")]
+fn validate_reports_errors(
+    #[case] args: &[&str],
+    #[case] stdin: &str,
+    #[case] code: i32,
+    #[case] stderr: &str,
+) {
+    let (actual_code, _, actual_stderr) = rpsl(args, stdin);
+    assert_eq!((actual_code, actual_stderr.as_str()), (code, stderr));
+}
+
+#[rstest]
+#[case(
+    &["fmt"],
+    "aut-num:        AS65536\nas-name:        EXAMPLE\nremarks:        Peering\n \nsource:         RIPE\n\n\
+     route:          192.0.2.0/24\norigin:         AS65536\nsource:         RIPE\n\n"
+)]
+#[case(
+    &["fmt", "--align", "auto", "--continuation", "plus", "--order"],
+    "aut-num: AS65536\nas-name: EXAMPLE\nremarks: Peering\n+\nsource:  RIPE\n\n\
+     route:  192.0.2.0/24\norigin: AS65536\nsource: RIPE\n\n"
+)]
+#[case(
+    &["fmt", "--align", "10", "--trim"],
+    "aut-num:  AS65536\nas-name:  EXAMPLE\nremarks:  Peering\nsource:   RIPE\n\n\
+     route:    192.0.2.0/24\norigin:   AS65536\nsource:   RIPE\n\n"
+)]
+fn fmt_formats_objects(#[case] args: &[&str], #[case] expected: &str) {
+    assert_eq!(
+        rpsl(args, OBJECTS),
+        (0, expected.to_string(), String::new())
+    );
+}
+
+#[test]
+fn fmt_check_fails_on_unformatted_objects() {
+    let (code, formatted, _) = rpsl(&["fmt"], OBJECTS);
+    assert_eq!(code, 0);
+    assert_eq!(rpsl(&["fmt", "--check"], &formatted).0, 0);
+
+    let (code, stdout, stderr) = rpsl(&["fmt", "--check"], OBJECTS);
+    assert_eq!((code, stdout.as_str()), (1, ""));
+    assert_eq!(
+        stderr,
+        "<stdin>: object `aut-num: AS65536` is not formatted\n<stdin>: object `route: 192.0.2.0/24` is not formatted\n"
+    );
+}
+
+#[rstest]
+#[case(&["grep", "--class", "ROUTE"], 0, "route: 192.0.2.0/24\norigin: AS65536\nsource: RIPE\n\n")]
+#[case(&["grep", "-a", "origin=as65536", "-a", "source"], 0, "route: 192.0.2.0/24\norigin: AS65536\nsource: RIPE\n\n")]
+#[case(&["grep", "-a", "remarks=Peering"], 0, "aut-num: AS65536\nas-name: EXAMPLE\nremarks: Peering\n+\nsource: RIPE\n\n")]
+#[case(&["grep", "-v", "-c", "aut-num", "--count"], 0, "1\n")]
+#[case(&["grep", "-c", "mntner"], 1, "")]
+fn grep_selects_objects(#[case] args: &[&str], #[case] code: i32, #[case] stdout: &str) {
+    assert_eq!(
+        rpsl(args, OBJECTS),
+        (code, stdout.to_string(), String::new())
+    );
+}
+
+#[test]
+fn convert_to_json_lines() {
+    let (code, stdout, _) = rpsl(&["convert", "--format", "jsonl"], OBJECTS);
+    assert_eq!(code, 0);
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[1],
+        serde_json::json!({"attributes": [
+            {"name": "route", "values": ["192.0.2.0/24"]},
+            {"name": "origin", "values": ["AS65536"]},
+            {"name": "source", "values": ["RIPE"]},
+        ]})
+    );
+}
+
+#[rstest]
+#[case("map", serde_json::json!({"route": ["192.0.2.0/24"], "origin": ["AS65536"], "source": ["RIPE"]}))]
+#[case("joined", serde_json::json!({"aut-num": "AS65536", "as-name": "EXAMPLE", "remarks": "Peering\n", "source": "RIPE"}))]
+fn convert_with_layout(#[case] layout: &str, #[case] expected: serde_json::Value) {
+    let (code, stdout, _) = rpsl(&["convert", "--layout", layout], OBJECTS);
+    assert_eq!(code, 0);
+    let objects: Vec<serde_json::Value> = serde_json::from_str(&stdout).unwrap();
+    assert!(objects.contains(&expected), "{objects:?}");
+}
+
+#[test]
+fn convert_with_layout_keeps_attribute_order() {
+    let (code, stdout, _) = rpsl(
+        &["convert", "--format", "jsonl", "--layout", "joined"],
+        OBJECTS,
+    );
+    assert_eq!(
+        (code, stdout.as_str()),
+        (
+            0,
+            "{\"aut-num\":\"AS65536\",\"as-name\":\"EXAMPLE\",\"remarks\":\"Peering\\n\",\"source\":\"RIPE\"}\n\
+             {\"route\":\"192.0.2.0/24\",\"origin\":\"AS65536\",\"source\":\"RIPE\"}\n"
+        )
+    );
+}
+
+#[test]
+fn invalid_rpsl_is_reported_while_converting() {
+    let (code, stdout, stderr) = rpsl(
+        &["convert", "--format", "jsonl"],
+        "role: A\n\nrole;  B\n\nrole: C\n\n",
+    );
+    assert_eq!(
+        (code, stdout.as_str()),
+        (
+            2,
+            "{\"attributes\":[{\"name\":\"role\",\"values\":[\"A\"]}]}\n\
+             {\"attributes\":[{\"name\":\"role\",\"values\":[\"C\"]}]}\n"
+        )
+    );
+    assert_eq!(
+        stderr,
+        "<stdin>:3:5: error: invalid separator in attribute `role`\n"
+    );
+}
@@ -8,6 +8,29 @@ proptest! {
         let parsed = parse_object(&rpsl).unwrap();
         prop_assert_eq!(parsed, object);
     }
+
+    /// Property based test to ensure the attributes of a parsed object display like the lines they
+    /// were parsed from, once the source of the whole object is discarded by changing the object.
+    #[test]
+    fn rpsl_attributes_display_like_source((_, rpsl) in strategies::object_w_rpsl()) {
+        let mut parsed = parse_object(&rpsl).unwrap();
+        parsed.attributes_mut();
+        prop_assert_eq!(parsed.to_string(), rpsl);
+    }
+
+    /// Property based test to ensure comments following values and on lines of their own are
+    /// parsed in order of their appearance and displayed like the lines they were parsed from.
+    #[test]
+    fn rpsl_comments_parsed((comments, rpsl) in strategies::commented_object_w_rpsl()) {
+        let parsed = parse_object(&rpsl).unwrap();
+        let parsed_comments: Vec<(String, usize, bool)> = parsed
+            .iter()
+            .flat_map(|attribute| attribute.comments())
+            .map(|comment| (comment.text().to_owned(), comment.line(), comment.is_own_line()))
+            .collect();
+        prop_assert_eq!(parsed_comments, comments);
+        prop_assert_eq!(parsed.to_string(), rpsl);
+    }
 }
 
 mod strategies {
@@ -47,6 +70,22 @@ mod strategies {
         proptest::string::string_regex(" {0,}").unwrap()
     }
 
+    /// The value on a line containing a "#", which starts a comment that is not part of the value.
+    fn without_comment(line: &str) -> &str {
+        line.split_once('#')
+            .map_or(line, |(value, _)| value.trim_end_matches(' '))
+    }
+
+    /// A valid attribute value that does not contain a comment.
+    fn attribute_value_uncommented() -> impl Strategy<Value = String> {
+        proptest::string::string_regex(r"[\x20-\x22\x24-\x7E\x80-\xFF]*").unwrap()
+    }
+
+    /// The text of a comment following the "#" character.
+    fn comment_text() -> impl Strategy<Value = String> {
+        proptest::string::string_regex(r"[\x20-\x7E\x80-\xFF]*").unwrap()
+    }
+
     /// The space separator between an attribute name and its value.
     fn space_separator() -> impl Strategy<Value = String> {
         proptest::string::string_regex(" {0,20}").unwrap()
@@ -89,10 +128,10 @@ mod strategies {
                 let attribute = rpsl::Attribute::new(
                     name.parse().unwrap(),
                     match &value {
-                        AttributeValue::Single(value) => value.parse().unwrap(),
+                        AttributeValue::Single(value) => without_comment(value).parse().unwrap(),
                         AttributeValue::Multi(values) => values
                             .iter()
-                            .map(AsRef::as_ref)
+                            .map(|value| without_comment(value))
                             .collect::<Vec<&str>>()
                             .try_into()
                             .unwrap(),
@@ -139,4 +178,45 @@ mod strategies {
             (Just(rpsl::Object::new(attributes)), Just(rpsl))
         })
     }
+
+    /// A commented object and its corresponding RPSL representation, together with the text,
+    /// line and placement of each comment in order of appearance.
+    ///
+    /// Each value line may be followed by a comment, as well as by a line containing only a comment.
+    #[allow(clippy::type_complexity)]
+    pub fn commented_object_w_rpsl() -> impl Strategy<Value = (Vec<(String, usize, bool)>, String)>
+    {
+        let line = (
+            attribute_value_uncommented(),
+            proptest::option::of(comment_text()),
+            proptest::option::of(comment_text()),
+        );
+        let attribute = (attribute_name(), prop::collection::vec(line, 1..10));
+        prop::collection::vec(attribute, 1..20).prop_map(|attributes| {
+            let mut comments = Vec::new();
+            let mut rpsl = String::new();
+
+            for (name, lines) in attributes {
+                rpsl.push_str(&format!("{name}:"));
+                for (i, (value, inline, own_line)) in lines.into_iter().enumerate() {
+                    if i > 0 {
+                        rpsl.push('+');
+                    }
+                    rpsl.push_str(&format!(" {value}"));
+                    if let Some(text) = inline {
+                        rpsl.push_str(&format!(" #{text}"));
+                        comments.push((text, i, false));
+                    }
+                    rpsl.push('\n');
+                    if let Some(text) = own_line {
+                        rpsl.push_str(&format!("#{text}\n"));
+                        comments.push((text, i, true));
+                    }
+                }
+            }
+            rpsl.push('\n');
+
+            (comments, rpsl)
+        })
+    }
 }